@@ -1,32 +1,173 @@
 use crate::config::Config;
-use log::info;
-use syntax;
+use log::{debug, info};
+use std::collections::HashMap;
+use std::sync::Arc;
+use syntax::analysis::Limits;
+use syntax::diagnostic::Diagnostic;
+use syntax::diff;
 use syntax::document::Document;
+use syntax::error::ValidationError;
+use syntax::introspection;
+use syntax::schema::Schema;
 use tokio::sync::{mpsc::Receiver, oneshot};
 
+/// Operations more complex than this are rejected before they are validated or executed.
+const MAX_DEPTH: usize = 15;
+const MAX_FIELDS: usize = 1_000;
+const MAX_COST: u64 = 10_000;
+/// How many items a list is assumed to return when an operation does not say
+const MAX_LIST_SIZE: u64 = 100;
+
 pub(crate) struct Database {
-    schema: Document,
+    schema: Arc<Schema>,
+    limits: Limits,
+    /// The type system definitions sent by clients so far, with every extension merged into
+    /// them. Their locations are cleared, as the requests they came from are gone.
+    definitions: Document,
     // graph
 }
 
+/// Renders errors against the request they were found in. Errors in the stored definitions
+/// have no location, so they are rendered without a snippet of the request.
+fn render_errors(errors: &[ValidationError], source: &str) -> String {
+    errors
+        .iter()
+        .map(|error| Diagnostic::from_validation_error(error).render(source))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 impl Database {
     pub fn new(_config: &Config) -> Self {
         Self {
-            schema: Document::default(),
+            schema: Arc::new(Schema::new(Vec::new()).expect("Default schema is invalid")),
+            limits: Limits::new()
+                .with_max_depth(MAX_DEPTH)
+                .with_max_fields(MAX_FIELDS)
+                .with_max_cost(MAX_COST)
+                .with_max_list_size(MAX_LIST_SIZE),
+            definitions: Document::new(Vec::new()),
         }
     }
 
+    /// Applies type definitions and `extend` definitions to the current schema. A definition
+    /// replaces the current one of the same name. The schema is only replaced when the updated
+    /// schema is valid and does not break existing clients.
+    fn update_schema(&mut self, document: Document) -> Result<(), Vec<ValidationError>> {
+        let merged = self.definitions.clone().update(document)?;
+        let schema = Schema::new(vec![merged.clone()])?;
+
+        let changes = diff::diff(&self.definitions, &merged);
+        let breaking: Vec<ValidationError> = changes
+            .iter()
+            .filter(|change| change.is_breaking())
+            .map(|change| ValidationError::new(&change.to_string()))
+            .collect();
+        if !breaking.is_empty() {
+            return Err(breaking);
+        }
+        for change in changes {
+            info!("Schema updated with {}", change);
+        }
+
+        let mut definitions = merged;
+        definitions.clear_locations();
+        self.schema = Arc::new(schema);
+        self.definitions = definitions;
+        Ok(())
+    }
+
     pub async fn run(&mut self, mut command: Receiver<(String, oneshot::Sender<String>)>) {
         while let Some((gql_str, response)) = command.recv().await {
+            let parsed = match syntax::parse(&gql_str) {
+                Ok(parsed) => parsed,
+                Err(error) => {
+                    let reply = Diagnostic::from_parse_error(&error, &gql_str).render(&gql_str);
+                    send_reply(response, reply);
+                    continue;
+                }
+            };
+            // Schema changes are applied in order, before any later request runs against them
+            if parsed.is_type_system() {
+                let reply = match self.update_schema(parsed) {
+                    Ok(()) => String::from("Schema updated"),
+                    Err(errors) => render_errors(&errors, &gql_str),
+                };
+                send_reply(response, reply);
+                continue;
+            }
+            let schema = Arc::clone(&self.schema);
+            let limits = self.limits;
             // handle connection
             tokio::spawn(async move {
-                let parsed = syntax::parse(&gql_str);
-                println!("Parsed: {:?}", parsed);
-                match response.send("Received input".into()) {
-                    Ok(()) => info!("Response sent successfully"),
-                    Err(e) => info!("Response from db failed: {}", e),
+                // Requests do not carry variable values, so variables count as their defaults
+                let checked = limits
+                    .check(&schema, &parsed, &HashMap::new())
+                    .and_then(|()| schema.validate_document(&parsed));
+                let reply = match checked {
+                    Ok(()) => match introspection::execute(&schema, &parsed, None, &HashMap::new())
+                    {
+                        Ok(Some(data)) => format!("{{\"data\":{}}}", data),
+                        Ok(None) => {
+                            debug!("Parsed: {:?}", parsed);
+                            String::from("Received input")
+                        }
+                        Err(error) => render_errors(&[error], &gql_str),
+                    },
+                    Err(errors) => render_errors(&errors, &gql_str),
                 };
+                send_reply(response, reply);
             });
         }
     }
 }
+
+fn send_reply(response: oneshot::Sender<String>, reply: String) {
+    match response.send(reply) {
+        Ok(()) => info!("Response sent successfully"),
+        Err(e) => info!("Response from db failed: {}", e),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database() -> Database {
+        Database::new(&Config {
+            num_threads: 1,
+            logging_config: String::new(),
+            protocols: Vec::new(),
+        })
+    }
+
+    fn update(database: &mut Database, source: &str) -> Result<(), Vec<String>> {
+        database
+            .update_schema(syntax::parse(source).unwrap())
+            .map_err(|errors| errors.into_iter().map(|error| error.message).collect())
+    }
+
+    #[test]
+    fn replaces_types_and_rejects_breaking_changes() {
+        let mut database = database();
+        update(
+            &mut database,
+            "type Query { user: User }\ntype User { id: ID name: String }",
+        )
+        .unwrap();
+        update(
+            &mut database,
+            "type User { id: ID name: String email: String }",
+        )
+        .unwrap();
+        update(&mut database, "extend scalar String @trim").unwrap();
+
+        assert_eq!(
+            update(&mut database, "type User { id: ID email: String }"),
+            Err(vec![String::from(
+                "breaking change: Field name was removed from User"
+            )])
+        );
+        assert!(database.schema.field("User", "name").is_some());
+    }
+}
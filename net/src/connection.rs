@@ -1,4 +1,4 @@
-use crate::message::{self, Message};
+use crate::message::{self, Message, MessageParser};
 use bytes::{Buf, BytesMut};
 use log::{debug, info};
 use tokio::io::{
@@ -10,6 +10,9 @@ pub struct Connection<T> {
     reader: BufReader<ReadHalf<T>>,
     writer: BufWriter<WriteHalf<T>>,
     buffer: BytesMut,
+    messages: MessageParser,
+    /// The number of bytes at the start of the buffer that were fed to `messages`
+    fed: usize,
 }
 
 pub type Error = Box<dyn std::error::Error + Send + Sync>;
@@ -21,6 +24,8 @@ impl<T: AsyncRead + AsyncWrite> Connection<T> {
             reader: BufReader::new(read),
             writer: BufWriter::new(write),
             buffer: BytesMut::with_capacity(4 * 1024),
+            messages: MessageParser::new(),
+            fed: 0,
         }
     }
 
@@ -29,7 +34,7 @@ impl<T: AsyncRead + AsyncWrite> Connection<T> {
             debug!("start of loop");
             if let Some(message) = self.parse_message()? {
                 debug!("Got message: {}", message);
-                if message == "" {
+                if message.is_empty() {
                     return Ok(None);
                 }
                 return Ok(Some(message));
@@ -47,13 +52,16 @@ impl<T: AsyncRead + AsyncWrite> Connection<T> {
     }
 
     fn parse_message(&mut self) -> Result<Option<String>, Error> {
-        let is_ready = Message::ready(&self.buffer);
+        // Only the bytes read since the last call are new to the parser
+        self.messages.feed(&self.buffer[self.fed..]);
+        self.fed = self.buffer.len();
+        let is_ready = self.messages.ready();
         info!("is ready?: {:?}", is_ready);
         match is_ready {
-            Ok(_) => match Message::parse(&self.buffer) {
+            Ok(_) => match self.messages.parse(&self.buffer) {
                 Ok(Message::Document { content, byte_len }) => {
-                    // self.advance_buffer(byte_len);
                     self.buffer.advance(byte_len);
+                    self.fed -= byte_len;
                     info!("Content pulled from connection:\n{}", content);
                     Ok(Some(content))
                 }
@@ -143,7 +151,7 @@ mod tests {
         pretty_env_logger::init();
     }
 
-    fn create_connection(input: Vec<&[u8]>) -> Connection<MockStream> {
+    fn create_connection(input: Vec<&[u8]>) -> Connection<MockStream<'_>> {
         let inner = MockStream {
             reader: input,
             writer: vec![],
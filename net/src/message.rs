@@ -1,5 +1,7 @@
 use bytes::BytesMut;
 use log::info;
+use std::collections::VecDeque;
+use syntax::stream::{StreamParser, StreamedDefinition};
 
 #[derive(Debug, PartialEq)]
 pub enum Message {
@@ -12,69 +14,61 @@ pub enum Error {
     System(crate::connection::Error),
 }
 
-impl Message {
-    pub fn ready(cursor: &BytesMut) -> Result<(), Error> {
-        if cursor.iter().find(|&&b| b == b'{').is_some() {
-            Message::check_balanced_braces(cursor)
-        // } else if cursor.iter().find(|&&b| b == b'\n').is_some() {
-        //     Ok(())
-        } else {
-            Err(Error::Incomplete(String::from(
-                "Message currently not ready",
-            )))
-        }
+/// Splits the bytes read from a connection into messages. Each byte is fed to one
+/// [`StreamParser`] once, and the definitions it completes wait here until they are taken as
+/// part of a message.
+///
+/// [`StreamParser`]: ../../syntax/stream/struct.StreamParser.html
+#[derive(Debug, Default)]
+pub struct MessageParser {
+    parser: StreamParser,
+    definitions: VecDeque<StreamedDefinition>,
+    /// The number of bytes fed before the start of the buffer that messages are taken from
+    consumed: usize,
+}
+
+impl MessageParser {
+    pub fn new() -> Self {
+        MessageParser::default()
     }
 
-    fn check_balanced_braces(cursor: &BytesMut) -> Result<(), Error> {
-        let mut stop_flag = false;
-        let unmatched_braces = cursor.iter().fold(0, |count, b| {
-            if stop_flag {
-                count
-            } else if *b == b'{' {
-                count + 1
-            } else if *b == b'}' {
-                let new_count = count - 1;
-                if new_count == 0 {
-                    stop_flag = true;
-                }
-                count - 1
-            } else {
-                count
-            }
-        });
-        if unmatched_braces > 0 || !stop_flag {
+    /// Adds the bytes read after the ones fed before.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.parser.feed(bytes);
+        self.definitions.extend(&mut self.parser);
+    }
+
+    pub fn ready(&self) -> Result<(), Error> {
+        if self.definitions.is_empty() {
             Err(Error::Incomplete(String::from(
-                "Unmatched braces. Message currently not ready",
+                "Message currently not ready",
             )))
         } else {
             Ok(())
         }
     }
 
-    pub fn parse(cursor: &BytesMut) -> Result<Message, Error> {
-        let mut last_closed: usize = 0;
-        let mut first_closed: usize = 0;
-        cursor.iter().fold((0, 0), |(index, unmatched), b| {
-            if *b == b'{' {
-                (index + 1, unmatched + 1)
-            } else if *b == b'}' {
-                let new_unmatched = unmatched - 1;
-                if new_unmatched == 0 {
-                    last_closed = index + 1;
-                    if first_closed == 0 {
-                        first_closed = last_closed;
-                    }
-                }
-                (index + 1, new_unmatched)
-            } else {
-                (index + 1, unmatched)
+    /// A message is the run of complete definitions at the start of the buffer that are all
+    /// executable or all type system definitions, so a query is never sent with a schema.
+    /// `cursor` holds the bytes fed since the last message was taken.
+    pub fn parse(&mut self, cursor: &BytesMut) -> Result<Message, Error> {
+        let first = self
+            .definitions
+            .pop_front()
+            .ok_or_else(|| Error::Incomplete(String::from("Message currently not ready")))?;
+        let first_kind = kind(&first);
+        let mut end = first.end;
+        while let Some(definition) = self.definitions.front() {
+            if kind(definition) != first_kind {
+                break;
             }
-        });
-        let slice = match cursor[0] {
-            b'{' => &cursor[..first_closed],
-            _ => &cursor[..last_closed],
-        };
-        info!("Last index of closed brace: {}", last_closed);
+            end = definition.end;
+            self.definitions.pop_front();
+        }
+        let byte_len = end - self.consumed;
+        self.consumed = end;
+        let slice = &cursor[..byte_len];
+        info!("End of last complete definition: {}", byte_len);
         info!("Slice: {:?}", slice);
         match std::str::from_utf8(slice) {
             Ok(content) => Ok(Message::Document {
@@ -86,45 +80,59 @@ impl Message {
     }
 }
 
+fn kind(definition: &StreamedDefinition) -> Option<bool> {
+    definition
+        .document
+        .as_ref()
+        .ok()
+        .map(|document| document.is_type_system())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use bytes::BytesMut;
 
+    fn fed(buf: &BytesMut) -> MessageParser {
+        let mut parser = MessageParser::new();
+        parser.feed(buf);
+        parser
+    }
+
     #[test]
     fn it_checks_for_an_open_brace() {
         let buf = BytesMut::from("{}");
-        assert!(Message::ready(&buf).is_ok());
+        assert!(fed(&buf).ready().is_ok());
 
         let buf = BytesMut::from("type Object");
-        assert!(Message::ready(&buf).is_err());
+        assert!(fed(&buf).ready().is_err());
     }
 
-    // #[test]
-    // fn it_checks_for_a_new_line_if_no_brace() {
-    //     let buf = BytesMut::from("scalar Date\n");
-    //     assert!(Message::ready(&buf).is_ok());
+    #[test]
+    fn it_checks_for_a_new_line_if_no_brace() {
+        let buf = BytesMut::from("scalar Date\n");
+        assert!(fed(&buf).ready().is_ok());
 
-    //     let buf = BytesMut::from("union Pet = Dog | Cat |");
-    //     assert!(Message::ready(&buf).is_err());
-    // }
+        let buf = BytesMut::from("union Pet = Dog | Cat |");
+        assert!(fed(&buf).ready().is_err());
+    }
 
     #[test]
     fn it_checks_that_all_braces_are_paired() {
         let buf = BytesMut::from("{ user { }");
-        assert!(Message::ready(&buf).is_err());
+        assert!(fed(&buf).ready().is_err());
     }
 
     #[test]
     fn it_checks_that_only_first_brace_must_be_paired() {
         let buf = BytesMut::from("type User { name: String, email: Address }\ntype Address {\n");
-        assert!(Message::ready(&buf).is_ok());
+        assert!(fed(&buf).ready().is_ok());
     }
 
     #[test]
     fn it_parses_a_message() {
         let buf = BytesMut::from("type User {\n name: String,\n email: Email,\n}");
-        let parsed = Message::parse(&buf);
+        let parsed = fed(&buf).parse(&buf);
         assert!(parsed.is_ok());
         assert_eq!(
             parsed.unwrap(),
@@ -152,7 +160,7 @@ type Admin {
 type Incomplete {
 "#,
         );
-        let parsed = Message::parse(&buf);
+        let parsed = fed(&buf).parse(&buf);
         assert!(parsed.is_ok());
         assert_eq!(
             parsed.unwrap(),
@@ -185,7 +193,7 @@ type Login {
 }
 "#,
         );
-        let parsed = Message::parse(&buf);
+        let parsed = fed(&buf).parse(&buf);
         assert!(parsed.is_ok());
         assert_eq!(
             parsed.unwrap(),
@@ -197,4 +205,54 @@ type Login {
             }
         );
     }
+
+    #[test]
+    fn it_ignores_braces_in_strings_and_comments() {
+        let buf = BytesMut::from(
+            r#"type User {
+    "Either { or }"
+    name: String # }
+}
+scalar Date
+{ user"#,
+        );
+        assert_eq!(
+            fed(&buf).parse(&buf).unwrap(),
+            Message::Document {
+                content: String::from(
+                    "type User {\n    \"Either { or }\"\n    name: String # }\n}\nscalar Date"
+                ),
+                byte_len: 66,
+            }
+        );
+    }
+
+    #[test]
+    fn it_parses_input_that_arrives_in_pieces() {
+        let mut parser = MessageParser::new();
+        let mut buf = BytesMut::from(
+            "{ user }
+type User { na",
+        );
+        parser.feed(&buf);
+        assert_eq!(
+            parser.parse(&buf).unwrap(),
+            Message::Document {
+                content: String::from("{ user }"),
+                byte_len: 8,
+            }
+        );
+        let _ = buf.split_to(8);
+        assert!(parser.ready().is_err());
+
+        buf.extend_from_slice(b"me: String }");
+        parser.feed(b"me: String }");
+        assert_eq!(
+            parser.parse(&buf).unwrap(),
+            Message::Document {
+                content: String::from("\ntype User { name: String }"),
+                byte_len: 27,
+            }
+        );
+    }
 }
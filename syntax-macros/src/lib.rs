@@ -0,0 +1,200 @@
+//! [![github]](https://github.com/ssedrick/gql.rs)
+//!
+//! [github]: https://img.shields.io/badge/github-8da0cb?style=for-the-badge&labelColor=555555&logo=github
+//!
+//! <br>
+//!
+//! Macros that parse GraphQL at compile time, so mistakes in documents embedded in Rust code are
+//! reported by the compiler rather than when the code runs.
+//!
+//! The code the macros expand to uses the `syntax` crate, which must be a dependency as well.
+
+#![warn(trivial_casts, trivial_numeric_casts, unstable_features)]
+#![forbid(unsafe_code, missing_docs)]
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use std::path::PathBuf;
+use syn::parse::{Parse, ParseStream};
+use syn::{LitStr, Token};
+use syntax::error::ValidationError;
+use syntax::schema::Schema;
+
+mod keyword {
+    syn::custom_keyword!(schema);
+}
+
+/// Parses a GraphQL document at compile time and expands to the [`Document`] it describes.
+///
+/// A document that fails to parse is a compile error, reported with the location of the
+/// problem in the document.
+///
+/// ```
+/// use syntax_macros::gql;
+///
+/// let document = gql!("{ hero { name friends { name } } }");
+/// assert_eq!(document, syntax::parse("{ hero { name friends { name } } }").unwrap());
+/// ```
+///
+/// ```compile_fail
+/// use syntax_macros::gql;
+///
+/// // error: Parse Error: Unexpected token on line 1, column 21: ...
+/// let document = gql!("type Person { name: }");
+/// ```
+///
+/// An executable document can also be validated against a schema, by naming a file of type
+/// system definitions relative to the crate's `Cargo.toml`. The crate is rebuilt when the
+/// schema changes.
+///
+/// ```ignore
+/// let query = gql!(schema = "schema.graphql", "query Hero { hero { name } }");
+/// ```
+///
+/// [`Document`]: ../syntax/document/struct.Document.html
+#[proc_macro]
+pub fn gql(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as Input);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// The arguments of `gql!`: an optional schema file followed by the document.
+struct Input {
+    schema: Option<LitStr>,
+    document: LitStr,
+}
+
+impl Parse for Input {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let schema = if input.peek(keyword::schema) {
+            input.parse::<keyword::schema>()?;
+            input.parse::<Token![=]>()?;
+            let path = input.parse()?;
+            input.parse::<Token![,]>()?;
+            Some(path)
+        } else {
+            None
+        };
+        let document = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Input { schema, document })
+    }
+}
+
+fn expand(input: Input) -> syn::Result<TokenStream2> {
+    let span = input.document.span();
+    let document =
+        syntax::parse(&input.document.value()).map_err(|error| syn::Error::new(span, error))?;
+
+    let mut dependencies = TokenStream2::new();
+    if let Some(schema) = &input.schema {
+        let path = schema_path(schema)?;
+        let source = std::fs::read_to_string(&path).map_err(|error| {
+            syn::Error::new(
+                schema.span(),
+                format!("Unable to read {}: {}", path.display(), error),
+            )
+        })?;
+        let definitions = syntax::parse(&source).map_err(|error| {
+            syn::Error::new(schema.span(), format!("{} in {}", error, path.display()))
+        })?;
+        let schema =
+            Schema::new(vec![definitions]).map_err(|errors| combine(schema.span(), errors))?;
+        schema
+            .validate_document(&document)
+            .map_err(|errors| combine(span, errors))?;
+
+        let path = path.display().to_string();
+        dependencies = quote!(
+            const _: &str = ::std::include_str!(#path);
+        );
+    }
+
+    let construct: TokenStream2 = syntax::__private::construct(&document)
+        .parse()
+        .map_err(|error| syn::Error::new(span, format!("{:?}", error)))?;
+    Ok(quote!({
+        #dependencies
+        #construct
+    }))
+}
+
+fn schema_path(schema: &LitStr) -> syn::Result<PathBuf> {
+    let directory = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|error| syn::Error::new(schema.span(), error))?;
+    Ok(PathBuf::from(directory).join(schema.value()))
+}
+
+fn combine(span: Span, errors: Vec<ValidationError>) -> syn::Error {
+    errors
+        .into_iter()
+        .map(|error| syn::Error::new(span, error))
+        .reduce(|mut combined, error| {
+            combined.combine(error);
+            combined
+        })
+        .unwrap_or_else(|| syn::Error::new(span, "Invalid document"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expand_str(input: &str) -> syn::Result<TokenStream2> {
+        expand(syn::parse_str(input)?)
+    }
+
+    fn schema_file(name: &str, source: &str) -> String {
+        let path = std::env::temp_dir().join(name);
+        std::fs::write(&path, source).unwrap();
+        path.display().to_string()
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        let error = expand_str(r#""type Person { name: }""#).unwrap_err();
+        assert!(error.to_string().contains("line 1, column 21"));
+    }
+
+    #[test]
+    fn expands_to_the_document() {
+        let code = expand_str(r##"r#"{ user(name: "Ada") { id } }"#,"##)
+            .unwrap()
+            .to_string();
+        assert!(code.contains(":: syntax :: document :: Document :: new"));
+        assert!(code.contains("\"Ada\""));
+        assert!(!code.contains("include_str"));
+    }
+
+    #[test]
+    fn validates_against_a_schema() {
+        let path = schema_file(
+            "syntax_macros_validates.graphql",
+            "type Query { user(name: String): User }\ntype User { id: ID }",
+        );
+        let valid = expand_str(&format!("schema = {:?}, \"{{ user {{ id }} }}\"", path));
+        assert!(valid.unwrap().to_string().contains("include_str"));
+
+        let invalid = expand_str(&format!("schema = {:?}, \"{{ user {{ email }} }}\"", path));
+        assert!(invalid.unwrap_err().to_string().contains("email"));
+    }
+
+    #[test]
+    fn reports_unusable_schemas() {
+        let missing = expand_str(r#"schema = "missing.graphql", "{ user }""#);
+        assert!(missing
+            .unwrap_err()
+            .to_string()
+            .starts_with("Unable to read"));
+
+        let path = schema_file(
+            "syntax_macros_unusable.graphql",
+            "type Query { user: Missing }",
+        );
+        let invalid = expand_str(&format!("schema = {:?}, \"{{ user }}\"", path));
+        assert!(invalid.unwrap_err().to_string().contains("Missing"));
+    }
+}
@@ -0,0 +1,549 @@
+//! Measures how expensive operations are to execute, so abusive queries can be rejected before
+//! they run.
+//!
+//! Three measures are taken of every operation, with fragments expanded in place:
+//! - the depth: how deeply fields are nested, a root field being at depth 1
+//! - the number of fields selected
+//! - the cost: the sum of the weights of the fields, where a field selected below a list is
+//!   counted once for every item the list is asked for
+//!
+//! A field weighs 1 unless its definition sets a weight with the `@cost` directive. The
+//! selections of a field with a `first` or `limit` argument count that many times. A variable
+//! passed to one of these counts as the value sent with the request, or else as its default,
+//! or else as the default of the argument. A count that is still unknown is the maximum list
+//! size of the [`Limits`], and is unbounded without one.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use syntax::analysis::{self, Limits};
+//! use syntax::schema::Schema;
+//!
+//! let schema = Schema::new(vec![syntax::parse(
+//!     r#"type Query { users(first: Int = 10): [User] @cost(weight: 2) }
+//! type User { name: String, friends(first: Int): [User] }"#,
+//! )
+//! .unwrap()])
+//! .unwrap();
+//! let query = syntax::parse(
+//!     "query Users($first: Int) { users { name friends(first: $first) { name } } }",
+//! )
+//! .unwrap();
+//! let mut variables = HashMap::new();
+//! variables.insert(String::from("first"), 5);
+//!
+//! let complexity = analysis::analyze(&schema, &query, &variables);
+//! assert_eq!(complexity.depth, 3);
+//! assert_eq!(complexity.fields, 4);
+//! assert_eq!(complexity.cost, 2 + 10 * (1 + 1 + 5 * 1));
+//! assert!(Limits::new()
+//!     .with_max_cost(50)
+//!     .check(&schema, &query, &variables)
+//!     .is_err());
+//! ```
+//!
+//! [`Limits`]: struct.Limits.html
+
+use crate::document::Document;
+use crate::error::ValidationError;
+use crate::nodes::*;
+use crate::schema::Schema;
+use std::collections::HashMap;
+
+/// The arguments that set how many items a list field returns.
+const MULTIPLIER_ARGUMENTS: [&str; 2] = ["first", "limit"];
+
+/// How expensive an operation is to execute.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Complexity {
+    /// How deeply fields are nested. Root fields are at depth 1.
+    pub depth: usize,
+    /// The number of fields selected, including those selected through fragments
+    pub fields: usize,
+    /// The weighted cost of the fields, multiplied by the sizes of the lists they are in
+    pub cost: u64,
+}
+
+impl Complexity {
+    /// Combines the measures of two selections made side by side.
+    fn add(&mut self, other: Complexity) {
+        self.depth = self.depth.max(other.depth);
+        self.fields = self.fields.saturating_add(other.fields);
+        self.cost = self.cost.saturating_add(other.cost);
+    }
+
+    /// Keeps the largest of each measure.
+    fn max(&mut self, other: Complexity) {
+        self.depth = self.depth.max(other.depth);
+        self.fields = self.fields.max(other.fields);
+        self.cost = self.cost.max(other.cost);
+    }
+}
+
+/// Measures every operation of a document and returns the largest of each measure, since any
+/// of the operations can be executed.
+///
+/// `variables` holds the integer values of the variables sent with the document, by name.
+/// Other values do not affect how complex an operation is.
+pub fn analyze(
+    schema: &Schema,
+    document: &Document,
+    variables: &HashMap<String, u64>,
+) -> Complexity {
+    let analyzer = Analyzer::new(schema, document, variables, None);
+    let mut complexity = Complexity::default();
+    for operation in operations(document) {
+        complexity.max(analyzer.operation(operation));
+    }
+    complexity
+}
+
+/// The most complex operation a server accepts. Every limit is unset by default.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    max_depth: Option<usize>,
+    max_fields: Option<usize>,
+    max_cost: Option<u64>,
+    max_list_size: Option<u64>,
+}
+
+impl Limits {
+    /// Creates limits that accept any operation.
+    pub fn new() -> Limits {
+        Limits::default()
+    }
+
+    /// Limits how deeply fields can be nested.
+    pub fn with_max_depth(mut self, depth: usize) -> Limits {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Limits how many fields an operation can select.
+    pub fn with_max_fields(mut self, fields: usize) -> Limits {
+        self.max_fields = Some(fields);
+        self
+    }
+
+    /// Limits the cost of an operation.
+    pub fn with_max_cost(mut self, cost: u64) -> Limits {
+        self.max_cost = Some(cost);
+        self
+    }
+
+    /// Sets the most items a list field returns, which a `first` or `limit` argument counts as
+    /// when its value is not known.
+    pub fn with_max_list_size(mut self, size: u64) -> Limits {
+        self.max_list_size = Some(size);
+        self
+    }
+
+    /// Measures every operation of the document and reports each limit an operation goes over,
+    /// pointing at the operation. `variables` are the integer variables sent with the document,
+    /// as for [`analyze`].
+    ///
+    /// [`analyze`]: fn.analyze.html
+    pub fn check(
+        &self,
+        schema: &Schema,
+        document: &Document,
+        variables: &HashMap<String, u64>,
+    ) -> Result<(), Vec<ValidationError>> {
+        let analyzer = Analyzer::new(schema, document, variables, self.max_list_size);
+        let mut errors = Vec::new();
+        for operation in operations(document) {
+            let complexity = analyzer.operation(operation);
+            let name = operation
+                .name()
+                .map_or("<anonymous>", |name| name.value.as_str());
+            let location = operation.span().start;
+            if let Some(max_depth) = self.max_depth.filter(|max| complexity.depth > *max) {
+                errors.push(
+                    ValidationError::new(&format!(
+                        "Operation {} has a depth of {}, more than the maximum depth of {}",
+                        name, complexity.depth, max_depth
+                    ))
+                    .with_location(location),
+                );
+            }
+            if let Some(max_fields) = self.max_fields.filter(|max| complexity.fields > *max) {
+                errors.push(
+                    ValidationError::new(&format!(
+                        "Operation {} selects {} fields, more than the maximum of {}",
+                        name, complexity.fields, max_fields
+                    ))
+                    .with_location(location),
+                );
+            }
+            if let Some(max_cost) = self.max_cost.filter(|max| complexity.cost > *max) {
+                errors.push(
+                    ValidationError::new(&format!(
+                        "Operation {} has a cost of {}, more than the maximum cost of {}",
+                        name, complexity.cost, max_cost
+                    ))
+                    .with_location(location),
+                );
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+fn operations(document: &Document) -> impl Iterator<Item = &OperationTypeNode> {
+    document
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            DefinitionNode::Executable(ExecutableDefinitionNode::Operation(operation)) => {
+                Some(operation)
+            }
+            _ => None,
+        })
+}
+
+/// An integer literal as a count. Negative counts are 0 and counts too large for a `u64`
+/// saturate rather than being ignored.
+fn as_count(value: &ValueNode) -> Option<u64> {
+    match value {
+        ValueNode::Int(int) if int.raw.starts_with('-') => Some(0),
+        ValueNode::Int(int) => Some(int.raw.parse().unwrap_or(u64::MAX)),
+        _ => None,
+    }
+}
+
+/// The weight set by a `@cost` directive on a field definition.
+fn cost_weight(definition: &FieldDefinitionNode) -> Option<u64> {
+    definition
+        .directives
+        .iter()
+        .flatten()
+        .find(|directive| directive.name.value == "cost")?
+        .arguments
+        .iter()
+        .flatten()
+        .find(|argument| argument.name.value == "weight")
+        .and_then(|argument| as_count(&argument.value))
+}
+
+/// Analysis runs before a document is validated, so unknown fields, types and fragments are
+/// tolerated: unknown fields weigh 1 and unknown fragments select nothing.
+struct Analyzer<'a> {
+    schema: &'a Schema,
+    fragments: HashMap<&'a str, &'a FragmentDefinitionNode>,
+    variables: &'a HashMap<String, u64>,
+    max_list_size: Option<u64>,
+}
+
+impl<'a> Analyzer<'a> {
+    fn new(
+        schema: &'a Schema,
+        document: &'a Document,
+        variables: &'a HashMap<String, u64>,
+        max_list_size: Option<u64>,
+    ) -> Analyzer<'a> {
+        let fragments = document
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(fragment)) => {
+                    Some((fragment.name.value.as_str(), fragment))
+                }
+                _ => None,
+            })
+            .collect();
+        Analyzer {
+            schema,
+            fragments,
+            variables,
+            max_list_size,
+        }
+    }
+
+    fn operation(&self, operation: &'a OperationTypeNode) -> Complexity {
+        let root = match operation {
+            OperationTypeNode::Query(_) => self.schema.query_type(),
+            OperationTypeNode::Mutation(_) => self.schema.mutation_type(),
+            OperationTypeNode::Subscription(_) => self.schema.subscription_type(),
+        };
+        self.selections(
+            root.map(|root| root.name.value.as_str()),
+            operation.selections(),
+            operation.variables(),
+            &mut Vec::new(),
+        )
+    }
+
+    /// Measures a selection set. `fragment_path` holds the fragments being expanded, so a
+    /// fragment that spreads itself is only expanded once.
+    fn selections(
+        &self,
+        parent: Option<&'a str>,
+        selections: &'a [Selection],
+        variables: &'a [VariableDefinitionNode],
+        fragment_path: &mut Vec<&'a str>,
+    ) -> Complexity {
+        let mut complexity = Complexity::default();
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    complexity.add(self.field(parent, field, variables, fragment_path))
+                }
+                Selection::Fragment(FragmentSpread::Node(spread)) => {
+                    let name = spread.name.value.as_str();
+                    let fragment = match self.fragments.get(name) {
+                        Some(fragment) if !fragment_path.contains(&name) => fragment,
+                        _ => continue,
+                    };
+                    fragment_path.push(name);
+                    complexity.add(self.selections(
+                        Some(&fragment.node_type.name.value),
+                        &fragment.selections,
+                        variables,
+                        fragment_path,
+                    ));
+                    fragment_path.pop();
+                }
+                Selection::Fragment(FragmentSpread::Inline(inline)) => {
+                    let type_condition = inline
+                        .node_type
+                        .as_ref()
+                        .map(|named_type| named_type.name.value.as_str());
+                    complexity.add(self.selections(
+                        type_condition.or(parent),
+                        &inline.selections,
+                        variables,
+                        fragment_path,
+                    ));
+                }
+            }
+        }
+        complexity
+    }
+
+    fn field(
+        &self,
+        parent: Option<&'a str>,
+        field: &'a FieldNode,
+        variables: &'a [VariableDefinitionNode],
+        fragment_path: &mut Vec<&'a str>,
+    ) -> Complexity {
+        let definition = parent.and_then(|parent| self.schema.field(parent, &field.name.value));
+        let children = match &field.selections {
+            Some(selections) => {
+                let field_type = definition
+                    .and_then(|definition| self.schema.named_type(&definition.field_type))
+                    .map(|field_type| field_type.name().value.as_str());
+                self.selections(field_type, selections, variables, fragment_path)
+            }
+            None => Complexity::default(),
+        };
+        let weight = definition.and_then(cost_weight).unwrap_or(1);
+        let multiplier = self.multiplier(field, definition, variables);
+        Complexity {
+            depth: children.depth + 1,
+            fields: children.fields.saturating_add(1),
+            cost: weight.saturating_add(multiplier.saturating_mul(children.cost)),
+        }
+    }
+
+    /// How many items a field is asked for. A variable counts as the value sent for it, or as
+    /// its default value, and an argument left out counts as the default value of its
+    /// definition. A variable without any of these counts as the maximum list size. Fields
+    /// without a `first` or `limit` count once.
+    fn multiplier(
+        &self,
+        field: &FieldNode,
+        definition: Option<&FieldDefinitionNode>,
+        variables: &[VariableDefinitionNode],
+    ) -> u64 {
+        for name in &MULTIPLIER_ARGUMENTS {
+            let argument = field
+                .arguments
+                .iter()
+                .flatten()
+                .find(|argument| argument.name.value == *name);
+            let argument_default = || {
+                definition
+                    .and_then(|definition| definition.arguments.as_ref())
+                    .and_then(|arguments| {
+                        arguments
+                            .iter()
+                            .find(|argument| argument.name.value == *name)
+                    })
+                    .and_then(|argument| argument.default_value.as_ref())
+                    .and_then(as_count)
+            };
+            let count = match argument.map(|argument| &argument.value) {
+                Some(ValueNode::Variable(variable)) => {
+                    let name = &variable.name.value;
+                    let count = self.variables.get(name).copied().or_else(|| {
+                        variables
+                            .iter()
+                            .find(|definition| definition.variable.name.value == *name)
+                            .and_then(|definition| definition.default_value.as_ref())
+                            .and_then(as_count)
+                            .or_else(argument_default)
+                    });
+                    Some(count.unwrap_or_else(|| self.max_list_size.unwrap_or(u64::MAX)))
+                }
+                Some(value) => as_count(value),
+                None => argument_default(),
+            };
+            if let Some(count) = count {
+                return count;
+            }
+        }
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const SCHEMA: &str = r#"type Query {
+  user(id: ID!): User
+  users(first: Int = 20): [User!]!
+  search(text: String!, limit: Int): [User!]! @cost(weight: 10)
+}
+type User {
+  name: String
+  avatar: String @cost(weight: 5)
+  friends(first: Int): [User!]!
+}"#;
+
+    fn analyze_query(query: &str) -> Complexity {
+        analyze_with_variables(query, &[])
+    }
+
+    fn analyze_with_variables(query: &str, variables: &[(&str, u64)]) -> Complexity {
+        let schema = Schema::new(vec![parse(SCHEMA).unwrap()]).unwrap();
+        let variables = variables
+            .iter()
+            .map(|(name, value)| (name.to_string(), *value))
+            .collect();
+        analyze(&schema, &parse(query).unwrap(), &variables)
+    }
+
+    #[test]
+    fn measures_depth_and_fields() {
+        assert_eq!(
+            analyze_query("{ user(id: 1) { name friends { name friends { name } } } }"),
+            Complexity {
+                depth: 4,
+                fields: 6,
+                cost: 6,
+            }
+        );
+    }
+
+    #[test]
+    fn weighs_fields_and_multiplies_lists() {
+        // users defaults to 20 items, each with a name and an avatar weighing 5
+        assert_eq!(analyze_query("{ users { name avatar } }").cost, 1 + 20 * 6);
+        assert_eq!(
+            analyze_query("{ users(first: 2) { friends(first: 3) { avatar } } }").cost,
+            1 + 2 * (1 + 3 * 5)
+        );
+        assert_eq!(
+            analyze_query("{ users(first: 99999999999999999999999) { name } }").cost,
+            u64::MAX
+        );
+        assert_eq!(analyze_query("{ users(first: -5) { name } }").cost, 1);
+    }
+
+    #[test]
+    fn counts_variables() {
+        let query = r#"query Search($limit: Int = 4, $first: Int) {
+  search(text: "a", limit: $limit) { friends(first: $first) { name } }
+}"#;
+        assert_eq!(
+            analyze_with_variables(query, &[("first", 3)]).cost,
+            10 + 4 * (1 + 3)
+        );
+        assert_eq!(
+            analyze_with_variables(query, &[("limit", 2), ("first", 3)]).cost,
+            10 + 2 * (1 + 3)
+        );
+        // Without a value or a default, the number of friends is unbounded
+        assert_eq!(analyze_query(query).cost, u64::MAX);
+        // A variable without a value or a default of its own takes the argument's default
+        assert_eq!(
+            analyze_query("query Users($first: Int) { users(first: $first) { name } }").cost,
+            1 + 20
+        );
+
+        let schema = Schema::new(vec![parse(SCHEMA).unwrap()]).unwrap();
+        let limits = Limits::new().with_max_list_size(10).with_max_cost(50);
+        let errors = limits
+            .check(&schema, &parse(query).unwrap(), &HashMap::new())
+            .unwrap_err();
+        assert_eq!(
+            errors[0].message,
+            "Operation Search has a cost of 54, more than the maximum cost of 50"
+        );
+    }
+
+    #[test]
+    fn expands_fragments() {
+        assert_eq!(
+            analyze_query(
+                r#"{
+  user(id: 1) { ...Friends ... on User { avatar } }
+}
+fragment Friends on User { friends(first: 10) { ...Name } }
+fragment Name on User { name ...Friends }"#
+            ),
+            Complexity {
+                depth: 3,
+                fields: 4,
+                cost: 1 + (1 + 10) + 5,
+            }
+        );
+    }
+
+    #[test]
+    fn measures_the_most_complex_operation() {
+        assert_eq!(
+            analyze_query("query A { users { name } } query B { user(id: 1) { a: name b: name } }"),
+            Complexity {
+                depth: 2,
+                fields: 3,
+                cost: 21,
+            }
+        );
+    }
+
+    #[test]
+    fn reports_operations_over_the_limits() {
+        let schema = Schema::new(vec![parse(SCHEMA).unwrap()]).unwrap();
+        let document = parse(
+            "query Small { user(id: 1) { name } }\nquery Large { users { friends { name } } }",
+        )
+        .unwrap();
+        let limits = Limits::new().with_max_depth(3).with_max_fields(3);
+        assert!(limits.check(&schema, &document, &HashMap::new()).is_ok());
+
+        let errors = limits
+            .with_max_depth(1)
+            .with_max_cost(30)
+            .check(&schema, &document, &HashMap::new())
+            .unwrap_err();
+        let messages: Vec<&str> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Operation Small has a depth of 2, more than the maximum depth of 1",
+                "Operation Large has a depth of 3, more than the maximum depth of 1",
+                "Operation Large has a cost of 41, more than the maximum cost of 30",
+            ]
+        );
+        assert_eq!(errors[1].location.unwrap().line, 2);
+    }
+}
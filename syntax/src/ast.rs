@@ -1,14 +1,33 @@
 use crate::document::Document;
 use crate::error::{ParseError, ParseResult};
 use crate::lexer::Lexer;
+use crate::nodes::enum_type_extension::EnumTypeExtensionNode;
+use crate::nodes::input_type_extension::InputTypeExtensionNode;
+use crate::nodes::interface_type_extension::InterfaceTypeExtensionNode;
 use crate::nodes::object_type_extension::ObjectTypeExtensionNode;
+use crate::nodes::scalar_type_extension::ScalarTypeExtensionNode;
+use crate::nodes::schema_extension::SchemaExtensionNode;
+use crate::nodes::union_type_extension::UnionTypeExtensionNode;
 use crate::nodes::*;
-use crate::token::{Location, Token};
+use crate::token::{Location, Span, Token, TokenKind};
+use std::convert::TryFrom;
 use std::iter::{Iterator, Peekable};
 use std::sync::Arc;
 
+#[allow(clippy::upper_case_acronyms)]
 pub struct AST<'i> {
+    input: &'i str,
     lexer: Peekable<Lexer<'i>>,
+    /// The end of the last consumed token, used to close node spans.
+    last_end: Location,
+    /// How many braces, parentheses and brackets are open. Used to find the next definition
+    /// boundary when recovering from an error.
+    depth: usize,
+    /// Whether the keyword of the definition being parsed has been consumed.
+    keyword_seen: bool,
+    /// The first definition keyword at the start of a line consumed after that keyword. A
+    /// definition that fails is assumed to have been left unclosed, and parsing resumes there.
+    resume: Option<Location>,
 }
 
 use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
@@ -27,7 +46,14 @@ impl<'i> Debug for AST<'i> {
 impl<'i> AST<'i> {
     pub fn new(input: &'i str) -> ParseResult<AST<'i>> {
         let lexer = Lexer::new(input).peekable();
-        Ok(AST { lexer })
+        Ok(AST {
+            input,
+            lexer,
+            last_end: Location::ignored(),
+            depth: 0,
+            keyword_seen: false,
+            resume: None,
+        })
     }
 
     pub fn parse(&'i mut self) -> ParseResult<Document> {
@@ -35,6 +61,72 @@ impl<'i> AST<'i> {
         Ok(Document::new(definitions))
     }
 
+    /// Parses every definition it can. After a syntax error the tokens up to the start of the
+    /// next top-level definition are skipped, so one mistake does not hide the ones after it.
+    /// A lexical error ends the document, as the lexer cannot continue past it.
+    pub fn parse_with_recovery(&mut self) -> (Document, Vec<ParseError>) {
+        let mut definitions: Vec<DefinitionNode> = Vec::new();
+        let mut errors: Vec<ParseError> = Vec::new();
+        if let Err(error) = self.expect_token(Token::Start) {
+            errors.push(error);
+        } else if self.expect_optional_token(&Token::End).is_some() {
+            errors.push(ParseError::DocumentEmpty);
+        } else {
+            while let Some(Ok(tok)) = self.lexer.peek() {
+                if let Token::End = tok {
+                    break;
+                }
+                let last_end = self.last_end;
+                self.depth = 0;
+                self.keyword_seen = false;
+                self.resume = None;
+                match self.parse_definition() {
+                    Ok(definition) => definitions.push(definition),
+                    Err(error) => {
+                        errors.push(error);
+                        if let Some(location) = self.resume.take() {
+                            self.lexer =
+                                Lexer::resume(self.input, location.absolute_position).peekable();
+                            continue;
+                        }
+                        // Always make progress, even if the definition failed on its first token
+                        if self.last_end == last_end {
+                            self.skip_token(&mut errors);
+                        }
+                        self.synchronize(&mut errors);
+                    }
+                }
+            }
+            if let Some(Err(_)) = self.lexer.peek() {
+                self.skip_token(&mut errors);
+            }
+        }
+        (Document::new(definitions), errors)
+    }
+
+    /// Skips tokens until the next one that starts a top-level definition. A definition keyword
+    /// at the start of a line is taken as one even inside an unclosed body.
+    fn synchronize(&mut self, errors: &mut Vec<ParseError>) {
+        while let Some(Ok(tok)) = self.lexer.peek() {
+            if let Token::End = tok {
+                break;
+            }
+            if (self.depth == 0 && starts_definition(tok)) || starts_line_definition(tok) {
+                break;
+            }
+            self.skip_token(errors);
+        }
+    }
+
+    /// Consumes the next token, recording it if it is a lexical error.
+    fn skip_token(&mut self, errors: &mut Vec<ParseError>) {
+        match self.lexer.next() {
+            Some(Ok(tok)) => self.consume(&tok),
+            Some(Err(lex_error)) => errors.push(ParseError::LexError(lex_error)),
+            None => {}
+        }
+    }
+
     fn parse_description(&mut self) -> ParseResult<Description> {
         match self.unwrap_peeked_token()? {
             Token::BlockStr(_, _) | Token::Str(_, _) => {
@@ -46,6 +138,7 @@ impl<'i> AST<'i> {
     }
 
     fn parse_input_value(&mut self) -> ParseResult<InputValueDefinitionNode> {
+        let start = self.peek_location()?;
         let description = self.parse_description()?;
         let name_tok = self.unwrap_next_token()?;
         self.expect_token(Token::Colon(Location::ignored()))?;
@@ -55,6 +148,7 @@ impl<'i> AST<'i> {
         let mut input_value = InputValueDefinitionNode::new(name_tok, type_node, description)?;
         input_value.with_default_value(default_value);
         input_value.with_directives(directives);
+        input_value.span = self.span_from(start);
         Ok(input_value)
     }
 
@@ -69,8 +163,9 @@ impl<'i> AST<'i> {
                 let mut args: ArgumentDefinitions = Vec::new();
                 loop {
                     args.push(self.parse_input_value()?);
-                    if let Some(_) =
-                        self.expect_optional_token(&Token::CloseParen(Location::ignored()))
+                    if self
+                        .expect_optional_token(&Token::CloseParen(Location::ignored()))
+                        .is_some()
                     {
                         break;
                     }
@@ -82,12 +177,14 @@ impl<'i> AST<'i> {
     }
 
     fn parse_argument(&mut self) -> ParseResult<Argument> {
+        let start = self.peek_location()?;
         let name = self.unwrap_next_token()?;
         self.expect_token(Token::Colon(Location::ignored()))?;
         let value = self.parse_value()?;
         Ok(Argument {
             name: NameNode::new(name)?,
             value,
+            span: self.span_from(start),
         })
     }
 
@@ -113,20 +210,18 @@ impl<'i> AST<'i> {
     }
 
     fn parse_directive(&mut self) -> ParseResult<DirectiveNode> {
-        self.expect_token(Token::At(Location::ignored()))?;
+        let at = self.expect_token(Token::At(Location::ignored()))?;
         let name = self.unwrap_next_token()?;
         let arguments = self.parse_arguments()?;
-        Ok(DirectiveNode::new(name, arguments)?)
+        let mut directive = DirectiveNode::new(name, arguments)?;
+        directive.span = self.span_from(at.location());
+        Ok(directive)
     }
 
     fn parse_directives(&mut self) -> ParseResult<Option<Vec<DirectiveNode>>> {
         let mut directives: Vec<DirectiveNode> = Vec::new();
-        loop {
-            if let Token::At(_) = self.unwrap_peeked_token()? {
-                directives.push(self.parse_directive()?);
-            } else {
-                break;
-            }
+        while let Token::At(_) = self.unwrap_peeked_token()? {
+            directives.push(self.parse_directive()?);
         }
         if !directives.is_empty() {
             Ok(Some(directives))
@@ -137,13 +232,13 @@ impl<'i> AST<'i> {
 
     fn parse_definitions(&'i mut self) -> ParseResult<Vec<DefinitionNode>> {
         self.expect_token(Token::Start)?;
-        if let Some(_) = self.expect_optional_token(&Token::End) {
+        if self.expect_optional_token(&Token::End).is_some() {
             Err(ParseError::DocumentEmpty)
         } else {
             let mut nodes: Vec<DefinitionNode> = Vec::new();
             loop {
                 nodes.push(self.parse_definition()?);
-                if let Some(_) = self.expect_optional_token(&Token::End) {
+                if self.expect_optional_token(&Token::End).is_some() {
                     break;
                 }
             }
@@ -164,10 +259,17 @@ impl<'i> AST<'i> {
                 "schema" => Ok(DefinitionNode::TypeSystem(
                     TypeSystemDefinitionNode::Schema(self.parse_schema(description)?),
                 )),
+                "directive" => Ok(DefinitionNode::TypeSystem(
+                    TypeSystemDefinitionNode::Directive(
+                        self.parse_directive_definition(description)?,
+                    ),
+                )),
                 "extend" => Ok(DefinitionNode::Extension(
                     self.parse_type_extension(description)?,
                 )),
-                "query" | "fragment" => Ok(DefinitionNode::Executable(self.parse_executable()?)),
+                "query" | "mutation" | "subscription" | "fragment" => {
+                    Ok(DefinitionNode::Executable(self.parse_executable()?))
+                }
                 name => Err(ParseError::UnexpectedKeyword {
                     expected: "A valid GraphQL keyword".into(),
                     received: name.into(),
@@ -176,7 +278,7 @@ impl<'i> AST<'i> {
             },
             Token::OpenBrace(_) => Ok(DefinitionNode::Executable(self.parse_executable()?)),
             _ => Err(ParseError::UnexpectedToken {
-                expected: "Token<Name> or Token<OpenBrace>".into(),
+                expected: vec![TokenKind::Name, TokenKind::OpenBrace],
                 received: tok.to_string(),
                 location: tok.location(),
             }),
@@ -185,29 +287,32 @@ impl<'i> AST<'i> {
 
     fn parse_type(&mut self, description: Description) -> ParseResult<TypeDefinitionNode> {
         let tok = self.unwrap_next_token()?;
+        let start = definition_start(&description, &tok);
         if let Token::Name(_, val) = tok {
             match val {
                 "type" => Ok(TypeDefinitionNode::Object(
-                    self.parse_object_type(description)?,
+                    self.parse_object_type(description, start)?,
+                )),
+                "enum" => Ok(TypeDefinitionNode::Enum(
+                    self.parse_enum_type(description, start)?,
                 )),
-                "enum" => Ok(TypeDefinitionNode::Enum(self.parse_enum_type(description)?)),
                 "union" => Ok(TypeDefinitionNode::Union(
-                    self.parse_union_type(description)?,
+                    self.parse_union_type(description, start)?,
                 )),
                 "interface" => Ok(TypeDefinitionNode::Interface(
-                    self.parse_interface_type(description)?,
+                    self.parse_interface_type(description, start)?,
                 )),
                 "input" => Ok(TypeDefinitionNode::Input(
-                    self.parse_input_type(description)?,
+                    self.parse_input_type(description, start)?,
                 )),
                 "scalar" => Ok(TypeDefinitionNode::Scalar(
-                    self.parse_scalar_type(description)?,
+                    self.parse_scalar_type(description, start)?,
                 )),
                 _ => Err(ParseError::BadValue),
             }
         } else {
             Err(ParseError::UnexpectedToken {
-                expected: "Token::Name".into(),
+                expected: vec![TokenKind::Name],
                 received: tok.to_string(),
                 location: tok.location(),
             })
@@ -218,13 +323,39 @@ impl<'i> AST<'i> {
         &mut self,
         description: Description,
     ) -> ParseResult<TypeSystemExtensionNode> {
-        self.unwrap_next_token()?; // Discard "extend"
+        let extend = self.unwrap_next_token()?;
+        let start = definition_start(&description, &extend);
         match self.unwrap_next_token()? {
             Token::Name(_, "type") => Ok(TypeSystemExtensionNode::Object(
-                self.parse_object_type_extension(description)?,
+                self.parse_object_type_extension(description, start)?,
+            )),
+            Token::Name(_, "interface") => Ok(TypeSystemExtensionNode::Interface(
+                self.parse_interface_type_extension(description, start)?,
+            )),
+            Token::Name(_, "union") => Ok(TypeSystemExtensionNode::Union(
+                self.parse_union_type_extension(description, start)?,
+            )),
+            Token::Name(_, "enum") => Ok(TypeSystemExtensionNode::Enum(
+                self.parse_enum_type_extension(description, start)?,
             )),
+            Token::Name(_, "input") => Ok(TypeSystemExtensionNode::Input(
+                self.parse_input_type_extension(description, start)?,
+            )),
+            Token::Name(_, "scalar") => Ok(TypeSystemExtensionNode::Scalar(
+                self.parse_scalar_type_extension(description, start)?,
+            )),
+            Token::Name(_, "schema") => Ok(TypeSystemExtensionNode::Schema(
+                self.parse_schema_extension(description, start)?,
+            )),
+            Token::Name(location, keyword) => Err(ParseError::UnexpectedKeyword {
+                expected:
+                    "One of `type`, `interface`, `union`, `enum`, `input`, `scalar` or `schema`"
+                        .into(),
+                received: keyword.into(),
+                location,
+            }),
             tok => Err(ParseError::UnexpectedToken {
-                expected: String::from("Token::Name"),
+                expected: vec![TokenKind::Name],
                 received: tok.to_string().to_owned(),
                 location: tok.location(),
             }),
@@ -234,6 +365,7 @@ impl<'i> AST<'i> {
     fn parse_object_type(
         &mut self,
         description: Description,
+        start: Location,
     ) -> ParseResult<ObjectTypeDefinitionNode> {
         let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
         let interfaces = self.parse_object_interfaces()?;
@@ -243,12 +375,14 @@ impl<'i> AST<'i> {
         let mut obj = ObjectTypeDefinitionNode::new(name_tok, description, fields)?;
         obj.with_interfaces(interfaces);
         obj.with_directives(directives);
+        obj.span = self.span_from(start);
         Ok(obj)
     }
 
     fn parse_object_type_extension(
         &mut self,
         description: Description,
+        start: Location,
     ) -> ParseResult<ObjectTypeExtensionNode> {
         let name_tok = self.unwrap_next_token()?;
         let interfaces = self.parse_object_interfaces()?;
@@ -263,46 +397,180 @@ impl<'i> AST<'i> {
             type_extension.with_fields(fields);
         }
 
+        type_extension.span = self.span_from(start);
+        Ok(type_extension)
+    }
+
+    fn parse_interface_type_extension(
+        &mut self,
+        description: Description,
+        start: Location,
+    ) -> ParseResult<InterfaceTypeExtensionNode> {
+        let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        let interfaces = self.parse_object_interfaces()?;
+        let directives = self.parse_directives()?;
+
+        let mut type_extension = InterfaceTypeExtensionNode::new(name_tok, description)?;
+        type_extension.with_interfaces(interfaces);
+        type_extension.with_directives(directives);
+
+        if let Token::OpenBrace(_) = self.unwrap_peeked_token()? {
+            let fields = self.parse_fields()?;
+            type_extension.with_fields(fields);
+        }
+
+        type_extension.span = self.span_from(start);
+        Ok(type_extension)
+    }
+
+    fn parse_union_type_extension(
+        &mut self,
+        description: Description,
+        start: Location,
+    ) -> ParseResult<UnionTypeExtensionNode> {
+        let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        let directives = self.parse_directives()?;
+
+        let mut type_extension = UnionTypeExtensionNode::new(name_tok, description)?;
+        type_extension.with_directives(directives);
+
+        if self
+            .expect_optional_token(&Token::Equals(Location::ignored()))
+            .is_some()
+        {
+            let types = self.parse_union_types()?;
+            type_extension.with_types(types);
+        }
+
+        type_extension.span = self.span_from(start);
+        Ok(type_extension)
+    }
+
+    fn parse_enum_type_extension(
+        &mut self,
+        description: Description,
+        start: Location,
+    ) -> ParseResult<EnumTypeExtensionNode> {
+        let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        let directives = self.parse_directives()?;
+
+        let mut type_extension = EnumTypeExtensionNode::new(name_tok, description)?;
+        type_extension.with_directives(directives);
+
+        if let Token::OpenBrace(_) = self.unwrap_peeked_token()? {
+            let values = self.parse_enum_values()?;
+            type_extension.with_values(values);
+        }
+
+        type_extension.span = self.span_from(start);
         Ok(type_extension)
     }
 
+    fn parse_input_type_extension(
+        &mut self,
+        description: Description,
+        start: Location,
+    ) -> ParseResult<InputTypeExtensionNode> {
+        let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        let directives = self.parse_directives()?;
+
+        let mut type_extension = InputTypeExtensionNode::new(name_tok, description)?;
+        type_extension.with_directives(directives);
+
+        if let Token::OpenBrace(_) = self.unwrap_peeked_token()? {
+            let fields = self.parse_input_fields()?;
+            type_extension.with_fields(fields);
+        }
+
+        type_extension.span = self.span_from(start);
+        Ok(type_extension)
+    }
+
+    fn parse_scalar_type_extension(
+        &mut self,
+        description: Description,
+        start: Location,
+    ) -> ParseResult<ScalarTypeExtensionNode> {
+        let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        let directives = self.parse_directives()?;
+
+        let mut type_extension = ScalarTypeExtensionNode::new(name_tok, description)?;
+        type_extension.with_directives(directives);
+        type_extension.span = self.span_from(start);
+        Ok(type_extension)
+    }
+
+    fn parse_schema_extension(
+        &mut self,
+        description: Description,
+        start: Location,
+    ) -> ParseResult<SchemaExtensionNode> {
+        let directives = self.parse_directives()?;
+
+        let mut schema_extension = SchemaExtensionNode::new(description);
+        schema_extension.with_directives(directives);
+
+        if let Token::OpenBrace(_) = self.unwrap_peeked_token()? {
+            let operations = self.parse_schema_operation_types()?;
+            schema_extension.with_operations(operations);
+        }
+
+        schema_extension.span = self.span_from(start);
+        Ok(schema_extension)
+    }
+
     fn parse_interface_type(
         &mut self,
         description: Description,
+        start: Location,
     ) -> ParseResult<InterfaceTypeDefinitionNode> {
         let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        let interfaces = self.parse_object_interfaces()?;
         let directives = self.parse_directives()?;
         let fields = self.parse_fields()?;
 
         let mut interface = InterfaceTypeDefinitionNode::new(name_tok, description)?;
+        interface.with_interfaces(interfaces);
         interface.with_directives(directives);
         interface.with_fields(fields);
+        interface.span = self.span_from(start);
         Ok(interface)
     }
 
     fn parse_input_type(
         &mut self,
         description: Description,
+        start: Location,
     ) -> ParseResult<InputTypeDefinitionNode> {
         let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
-        let mut input_type = InputTypeDefinitionNode::new(name_tok, description)?;
+        let directives = self.parse_directives()?;
         let fields = self.parse_input_fields()?;
+
+        let mut input_type = InputTypeDefinitionNode::new(name_tok, description)?;
+        input_type.with_directives(directives);
         input_type.with_fields(fields);
+        input_type.span = self.span_from(start);
         Ok(input_type)
     }
 
     fn parse_scalar_type(
         &mut self,
         description: Description,
+        start: Location,
     ) -> ParseResult<ScalarTypeDefinitionNode> {
         let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
         let directives = self.parse_directives()?;
         let mut scalar_type = ScalarTypeDefinitionNode::new(name_tok, description)?;
         scalar_type.with_directives(directives);
+        scalar_type.span = self.span_from(start);
         Ok(scalar_type)
     }
 
-    fn parse_enum_type(&mut self, description: Description) -> ParseResult<EnumTypeDefinitionNode> {
+    fn parse_enum_type(
+        &mut self,
+        description: Description,
+        start: Location,
+    ) -> ParseResult<EnumTypeDefinitionNode> {
         let name_tok = self.expect_token(Token::Name(Location::ignored(), "enum"))?;
         if name_tok == Token::Name(Location::ignored(), "true")
             || name_tok == Token::Name(Location::ignored(), "false")
@@ -312,40 +580,41 @@ impl<'i> AST<'i> {
         }
         let directives = self.parse_directives()?;
         let values = self.parse_enum_values()?;
-        Ok(EnumTypeDefinitionNode::new(
-            name_tok,
-            description,
-            directives,
-            values,
-        )?)
+        let mut enum_type = EnumTypeDefinitionNode::new(name_tok, description, directives, values)?;
+        enum_type.span = self.span_from(start);
+        Ok(enum_type)
     }
 
     fn parse_union_type(
         &mut self,
         description: Description,
+        start: Location,
     ) -> ParseResult<UnionTypeDefinitionNode> {
         let name_tok = self.expect_token(Token::Name(Location::ignored(), "union"))?;
         let directives = self.parse_directives()?;
         self.expect_token(Token::Equals(Location::ignored()))?;
         let types = self.parse_union_types()?;
-        Ok(UnionTypeDefinitionNode::new(
-            name_tok,
-            description,
-            directives,
-            types,
-        )?)
+        let mut union_type =
+            UnionTypeDefinitionNode::new(name_tok, description, directives, types)?;
+        union_type.span = self.span_from(start);
+        Ok(union_type)
     }
 
     fn parse_object_interfaces(&mut self) -> ParseResult<Option<Vec<NamedTypeNode>>> {
         if let Some(name_tok) = self.expect_optional_token(&Token::Name(Location::ignored(), "")) {
             match name_tok {
                 Token::Name(_, "implements") => {
+                    // A leading `&` is allowed before the first interface
+                    self.expect_optional_token(&Token::Amp(Location::ignored()));
                     let mut interface_names: Vec<NamedTypeNode> = Vec::new();
                     loop {
                         let interface_name =
                             self.expect_token(Token::Name(Location::ignored(), ""))?;
                         interface_names.push(NamedTypeNode::new(interface_name)?);
-                        if let None = self.expect_optional_token(&Token::Amp(Location::ignored())) {
+                        if self
+                            .expect_optional_token(&Token::Amp(Location::ignored()))
+                            .is_none()
+                        {
                             break;
                         }
                     }
@@ -357,7 +626,7 @@ impl<'i> AST<'i> {
                     location: name_tok.location(),
                 }),
                 tok => Err(ParseError::UnexpectedToken {
-                    expected: String::from("Token<Name>"),
+                    expected: vec![TokenKind::Name],
                     received: tok.to_string(),
                     location: tok.location(),
                 }),
@@ -371,7 +640,10 @@ impl<'i> AST<'i> {
         let mut fields: Vec<FieldDefinitionNode> = Vec::new();
         self.expect_token(Token::OpenBrace(Location::ignored()))?;
         loop {
-            if let Some(_) = self.expect_optional_token(&Token::CloseBrace(Location::ignored())) {
+            if self
+                .expect_optional_token(&Token::CloseBrace(Location::ignored()))
+                .is_some()
+            {
                 break;
             }
             fields.push(self.parse_field()?);
@@ -380,26 +652,35 @@ impl<'i> AST<'i> {
     }
 
     fn parse_field(&mut self) -> ParseResult<FieldDefinitionNode> {
+        let start = self.peek_location()?;
         let description = self.parse_description()?;
         let name = self.expect_token(Token::Name(Location::ignored(), ""))?;
         let arguments = self.parse_arguments_definition()?;
-        println!("arguments, {:?}", arguments);
         self.expect_token(Token::Colon(Location::ignored()))?;
         let field_type = self.parse_field_type()?;
-        FieldDefinitionNode::new(name, field_type, description, arguments)
+        let directives = self.parse_directives()?;
+        let mut field = FieldDefinitionNode::new(name, field_type, description, arguments)?;
+        field.with_directives(directives);
+        field.span = self.span_from(start);
+        Ok(field)
     }
 
     fn parse_field_type(&mut self) -> ParseResult<TypeNode> {
         let mut field_type: TypeNode;
-        if let Some(_) = self.expect_optional_token(&Token::OpenSquare(Location::ignored())) {
-            field_type = TypeNode::List(ListTypeNode::new(self.parse_field_type()?));
+        if let Some(open) = self.expect_optional_token(&Token::OpenSquare(Location::ignored())) {
+            let mut list_type = ListTypeNode::new(self.parse_field_type()?);
             self.expect_token(Token::CloseSquare(Location::ignored()))?;
+            list_type.span = self.span_from(open.location());
+            field_type = TypeNode::List(list_type);
         } else {
             field_type = TypeNode::Named(NamedTypeNode::new(
                 self.expect_token(Token::Name(Location::ignored(), ""))?,
             )?);
         }
-        if let Some(_) = self.expect_optional_token(&Token::Bang(Location::ignored())) {
+        if self
+            .expect_optional_token(&Token::Bang(Location::ignored()))
+            .is_some()
+        {
             field_type = TypeNode::NonNull(Arc::new(field_type));
         }
         Ok(field_type)
@@ -409,7 +690,10 @@ impl<'i> AST<'i> {
         let mut fields: Vec<InputValueDefinitionNode> = Vec::new();
         let tok = self.expect_token(Token::OpenBrace(Location::ignored()))?;
         loop {
-            if let Some(_) = self.expect_optional_token(&Token::CloseBrace(Location::ignored())) {
+            if self
+                .expect_optional_token(&Token::CloseBrace(Location::ignored()))
+                .is_some()
+            {
                 break;
             }
             fields.push(self.parse_input_value()?);
@@ -425,13 +709,19 @@ impl<'i> AST<'i> {
         let mut values: Vec<EnumValueDefinitionNode> = Vec::new();
         self.expect_token(Token::OpenBrace(Location::ignored()))?;
         loop {
-            if let Some(_) = self.expect_optional_token(&Token::CloseBrace(Location::ignored())) {
+            if self
+                .expect_optional_token(&Token::CloseBrace(Location::ignored()))
+                .is_some()
+            {
                 break;
             }
+            let start = self.peek_location()?;
             let description = self.parse_description()?;
             let name = self.expect_token(Token::Name(Location::ignored(), ""))?;
             let directives = self.parse_directives()?;
-            values.push(EnumValueDefinitionNode::new(name, description, directives)?);
+            let mut value = EnumValueDefinitionNode::new(name, description, directives)?;
+            value.span = self.span_from(start);
+            values.push(value);
         }
         Ok(values)
     }
@@ -441,12 +731,11 @@ impl<'i> AST<'i> {
         // First Pipe is truely optional
         self.expect_optional_token(&Token::Pipe(Location::ignored()));
         types.push(NamedTypeNode::new(self.unwrap_next_token()?)?);
-        loop {
-            if let Some(_) = self.expect_optional_token(&Token::Pipe(Location::ignored())) {
-                types.push(NamedTypeNode::new(self.unwrap_next_token()?)?);
-            } else {
-                break;
-            }
+        while self
+            .expect_optional_token(&Token::Pipe(Location::ignored()))
+            .is_some()
+        {
+            types.push(NamedTypeNode::new(self.unwrap_next_token()?)?);
         }
         Ok(types)
     }
@@ -460,25 +749,33 @@ impl<'i> AST<'i> {
 
     fn parse_value(&mut self) -> ParseResult<ValueNode> {
         let tok = self.unwrap_peeked_token()?;
+        let span = Span::from_token(tok);
         match *tok {
             Token::Name(_, value) => {
                 self.unwrap_next_token()?;
                 match value {
-                    "true" => Ok(ValueNode::Bool(BooleanValueNode { value: true })),
-                    "false" => Ok(ValueNode::Bool(BooleanValueNode { value: false })),
+                    "true" => Ok(ValueNode::Bool(BooleanValueNode { value: true, span })),
+                    "false" => Ok(ValueNode::Bool(BooleanValueNode { value: false, span })),
                     "null" => Ok(ValueNode::Null),
                     _ => Ok(ValueNode::Enum(EnumValueNode {
                         value: value.to_owned(),
+                        span,
                     })),
                 }
             }
             Token::Int(_, value) => {
                 self.unwrap_next_token()?;
-                Ok(ValueNode::Int(IntValueNode { value }))
+                Ok(ValueNode::Int(IntValueNode {
+                    raw: value.to_owned(),
+                    span,
+                }))
             }
             Token::Float(_, value) => {
                 self.unwrap_next_token()?;
-                Ok(ValueNode::Float(FloatValueNode { value }))
+                Ok(ValueNode::Float(FloatValueNode {
+                    raw: value.to_owned(),
+                    span,
+                }))
             }
             Token::Str(_, _) | Token::BlockStr(_, _) => {
                 let str_tok = self.unwrap_next_token()?;
@@ -497,9 +794,15 @@ impl<'i> AST<'i> {
                 Ok(ValueNode::Object(obj_value))
             }
             _ => Err(ParseError::UnexpectedToken {
-                expected: String::from(
-                    "One of (Name, Int, Float, Str, Dollar, OpenSquare, OpenBrace)",
-                ),
+                expected: vec![
+                    TokenKind::Name,
+                    TokenKind::Int,
+                    TokenKind::Float,
+                    TokenKind::Str,
+                    TokenKind::Dollar,
+                    TokenKind::OpenSquare,
+                    TokenKind::OpenBrace,
+                ],
                 received: tok.to_owned().to_string(),
                 location: tok.location(),
             }),
@@ -507,53 +810,120 @@ impl<'i> AST<'i> {
     }
 
     fn parse_list_value(&mut self) -> ParseResult<ListValueNode> {
-        self.expect_token(Token::OpenSquare(Location::ignored()))?;
+        let open = self.expect_token(Token::OpenSquare(Location::ignored()))?;
         let mut values: Vec<ValueNode> = Vec::new();
         loop {
-            if let Some(_) = self.expect_optional_token(&Token::CloseSquare(Location::ignored())) {
+            if self
+                .expect_optional_token(&Token::CloseSquare(Location::ignored()))
+                .is_some()
+            {
                 break;
             }
             values.push(self.parse_value()?);
         }
-        Ok(ListValueNode { values })
+        Ok(ListValueNode {
+            values,
+            span: self.span_from(open.location()),
+        })
     }
 
     fn parse_object_value(&mut self) -> ParseResult<ObjectValueNode> {
-        self.expect_token(Token::OpenBrace(Location::ignored()))?;
+        let open = self.expect_token(Token::OpenBrace(Location::ignored()))?;
         let mut fields: Vec<ObjectFieldNode> = Vec::new();
         loop {
-            if let Some(_) = self.expect_optional_token(&Token::CloseBrace(Location::ignored())) {
+            if self
+                .expect_optional_token(&Token::CloseBrace(Location::ignored()))
+                .is_some()
+            {
                 break;
             }
             let name = self.unwrap_next_token()?;
             self.expect_token(Token::Colon(Location::ignored()))?;
             let value = self.parse_value()?;
             fields.push(ObjectFieldNode {
+                span: self.span_from(name.location()),
                 name: NameNode::new(name)?,
                 value,
             });
         }
-        Ok(ObjectValueNode { fields })
+        Ok(ObjectValueNode {
+            fields,
+            span: self.span_from(open.location()),
+        })
     }
 
     fn parse_variable(&mut self) -> ParseResult<VariableNode> {
-        self.expect_token(Token::Dollar(Location::ignored()))?;
+        let dollar = self.expect_token(Token::Dollar(Location::ignored()))?;
         let name = self.unwrap_next_token()?;
         Ok(VariableNode {
             name: NameNode::new(name)?,
+            span: self.span_from(dollar.location()),
         })
     }
 
+    fn parse_directive_definition(
+        &mut self,
+        description: Description,
+    ) -> ParseResult<DirectiveDefinitionNode> {
+        let directive_keyword = self.unwrap_next_token()?;
+        let start = definition_start(&description, &directive_keyword);
+        self.expect_token(Token::At(Location::ignored()))?;
+        let name_tok = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        let arguments = self.parse_arguments_definition()?;
+
+        let mut repeatable = false;
+        let mut keyword = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        if let Token::Name(_, "repeatable") = keyword {
+            repeatable = true;
+            keyword = self.expect_token(Token::Name(Location::ignored(), ""))?;
+        }
+        if let Token::Name(location, received) = keyword {
+            if received != "on" {
+                return Err(ParseError::UnexpectedKeyword {
+                    expected: "on".into(),
+                    received: received.into(),
+                    location,
+                });
+            }
+        }
+        let locations = self.parse_directive_locations()?;
+
+        let mut directive = DirectiveDefinitionNode::new(name_tok, description)?;
+        directive.with_arguments(arguments);
+        directive.with_repeatable(repeatable);
+        directive.with_locations(locations);
+        directive.span = self.span_from(start);
+        Ok(directive)
+    }
+
+    fn parse_directive_locations(&mut self) -> ParseResult<Vec<DirectiveLocation>> {
+        let mut locations: Vec<DirectiveLocation> = Vec::new();
+        // First Pipe is truely optional
+        self.expect_optional_token(&Token::Pipe(Location::ignored()));
+        locations.push(DirectiveLocation::try_from(self.unwrap_next_token()?)?);
+        while self
+            .expect_optional_token(&Token::Pipe(Location::ignored()))
+            .is_some()
+        {
+            locations.push(DirectiveLocation::try_from(self.unwrap_next_token()?)?);
+        }
+        Ok(locations)
+    }
+
     fn parse_schema(&mut self, description: Description) -> ParseResult<SchemaDefinitionNode> {
         match self.unwrap_next_token()? {
             Token::Name(location, keyword) => match keyword {
                 "schema" => {
+                    let start = description
+                        .as_ref()
+                        .map_or(location, |description| description.span.start);
                     let directives = self.parse_directives()?;
                     let operations = self.parse_schema_operation_types()?;
                     Ok(SchemaDefinitionNode {
                         description,
                         directives,
                         operations,
+                        span: self.span_from(start),
                     })
                 }
                 _ => Err(ParseError::UnexpectedKeyword {
@@ -563,7 +933,7 @@ impl<'i> AST<'i> {
                 }),
             },
             tok => Err(ParseError::UnexpectedToken {
-                expected: "Token<Name> with value of `schema`".into(),
+                expected: vec![TokenKind::Name],
                 received: tok.to_string(),
                 location: tok.location(),
             }),
@@ -574,15 +944,20 @@ impl<'i> AST<'i> {
         self.expect_token(Token::OpenBrace(Location::ignored()))?;
         let mut operations = Vec::new();
         loop {
-            if let Some(_) = self.expect_optional_token(&Token::CloseBrace(Location::ignored())) {
+            if self
+                .expect_optional_token(&Token::CloseBrace(Location::ignored()))
+                .is_some()
+            {
                 break;
             }
 
+            let start = self.peek_location()?;
             let operation = self.parse_schema_operation()?;
             self.expect_token(Token::Colon(Location::ignored()))?;
             operations.push(OperationTypeDefinitionNode {
                 operation,
                 node_type: NamedTypeNode::new(self.unwrap_next_token()?)?,
+                span: self.span_from(start),
             })
         }
         Ok(operations)
@@ -602,7 +977,7 @@ impl<'i> AST<'i> {
                 }),
             },
             tok => Err(ParseError::UnexpectedToken {
-                expected: "Token<Name>".into(),
+                expected: vec![TokenKind::Name],
                 received: tok.to_string(),
                 location: tok.location(),
             }),
@@ -613,12 +988,14 @@ impl<'i> AST<'i> {
         let tok = self.unwrap_peeked_token()?;
         match tok {
             Token::Name(location, val) => match *val {
-                "query" /* | "mutation" | "subscription" */ => Ok(ExecutableDefinitionNode::Operation(self.parse_operation_type()?)),
-                "fragment" =>
-                    Ok(ExecutableDefinitionNode::Fragment(self.parse_fragment_definition()?))
-                ,
+                "query" | "mutation" | "subscription" => Ok(ExecutableDefinitionNode::Operation(
+                    self.parse_operation_type()?,
+                )),
+                "fragment" => Ok(ExecutableDefinitionNode::Fragment(
+                    self.parse_fragment_definition()?,
+                )),
                 keyword => Err(ParseError::UnexpectedKeyword {
-                    expected: "One of `query` or `fragment`".into(),
+                    expected: "One of `query`, `mutation`, `subscription` or `fragment`".into(),
                     received: keyword.into(),
                     location: *location,
                 }),
@@ -627,9 +1004,7 @@ impl<'i> AST<'i> {
                 OperationTypeNode::Query(self.parse_anonymous_query()?),
             )),
             tok => Err(ParseError::UnexpectedToken {
-                expected: String::from(
-                    "One of 'query', 'mutation', 'subscription', 'fragment', or anonymous query",
-                ),
+                expected: vec![TokenKind::Name, TokenKind::OpenBrace],
                 received: tok.to_string(),
                 location: tok.location(),
             }),
@@ -640,38 +1015,85 @@ impl<'i> AST<'i> {
         let keyword = self.unwrap_next_token()?;
         if let Token::Name(loc, name) = keyword {
             match name {
-                "query" => Ok(OperationTypeNode::Query(self.parse_query()?)),
+                "query" => Ok(OperationTypeNode::Query(self.parse_query(loc)?)),
+                "mutation" => Ok(OperationTypeNode::Mutation(self.parse_mutation(loc)?)),
+                "subscription" => Ok(OperationTypeNode::Subscription(
+                    self.parse_subscription(loc)?,
+                )),
                 _ => Err(ParseError::UnexpectedKeyword {
-                    expected: "One of 'query'".into(),
-                    received: "name".into(),
+                    expected: "One of 'query', 'mutation' or 'subscription'".into(),
+                    received: name.into(),
                     location: loc,
                 }),
             }
         } else {
             Err(ParseError::UnexpectedToken {
-                expected: "Token<Name>".into(),
+                expected: vec![TokenKind::Name],
                 received: keyword.to_string(),
                 location: keyword.location(),
             })
         }
     }
 
-    fn parse_query(&mut self) -> ParseResult<QueryDefinitionNode> {
-        let name = self.unwrap_next_token()?;
+    fn parse_optional_name(&mut self) -> ParseResult<Option<NameNode>> {
+        match self.expect_optional_token(&Token::Name(Location::ignored(), "")) {
+            Some(tok) => Ok(Some(NameNode::new(tok)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn parse_query(&mut self, start: Location) -> ParseResult<QueryDefinitionNode> {
+        let name = self.parse_optional_name()?;
         let variables = self.parse_variables()?;
+        let directives = self.parse_directives()?;
         let selections = self.parse_selection_set()?;
         Ok(QueryDefinitionNode {
-            name: Some(NameNode::new(name)?),
+            name,
+            variables,
+            directives,
+            selections,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_mutation(&mut self, start: Location) -> ParseResult<MutationDefinitionNode> {
+        let name = self.parse_optional_name()?;
+        let variables = self.parse_variables()?;
+        let directives = self.parse_directives()?;
+        let selections = self.parse_selection_set()?;
+        Ok(MutationDefinitionNode {
+            name,
             variables,
+            directives,
             selections,
+            span: self.span_from(start),
+        })
+    }
+
+    fn parse_subscription(&mut self, start: Location) -> ParseResult<SubscriptionDefinitionNode> {
+        let name = self.parse_optional_name()?;
+        let variables = self.parse_variables()?;
+        let directives = self.parse_directives()?;
+        let selections = self.parse_selection_set()?;
+        Ok(SubscriptionDefinitionNode {
+            name,
+            variables,
+            directives,
+            selections,
+            span: self.span_from(start),
         })
     }
 
     fn parse_variables(&mut self) -> ParseResult<Option<Variables>> {
         let mut variables = Vec::new();
-        if let Some(_) = self.expect_optional_token(&Token::OpenParen(Location::ignored())) {
+        if self
+            .expect_optional_token(&Token::OpenParen(Location::ignored()))
+            .is_some()
+        {
             loop {
-                if let Some(_) = self.expect_optional_token(&Token::CloseParen(Location::ignored()))
+                if self
+                    .expect_optional_token(&Token::CloseParen(Location::ignored()))
+                    .is_some()
                 {
                     break;
                 }
@@ -686,6 +1108,7 @@ impl<'i> AST<'i> {
     }
 
     fn parse_variable_definition(&mut self) -> ParseResult<VariableDefinitionNode> {
+        let start = self.peek_location()?;
         let variable = self.parse_variable()?;
         self.expect_token(Token::Colon(Location::ignored()))?;
         let variable_type = self.parse_field_type()?;
@@ -693,20 +1116,28 @@ impl<'i> AST<'i> {
             variable,
             variable_type,
             default_value: None,
+            span: Span::ignored(),
         };
-        if let Some(_) = self.expect_optional_token(&Token::Equals(Location::ignored())) {
+        if self
+            .expect_optional_token(&Token::Equals(Location::ignored()))
+            .is_some()
+        {
             let value = self.parse_value()?;
             var.default_value = Some(value);
         }
+        var.span = self.span_from(start);
         Ok(var)
     }
 
     fn parse_anonymous_query(&mut self) -> ParseResult<QueryDefinitionNode> {
+        let start = self.peek_location()?;
         let selections = self.parse_selection_set()?;
         Ok(QueryDefinitionNode {
             name: None,
             variables: None,
+            directives: None,
             selections,
+            span: self.span_from(start),
         })
     }
 
@@ -714,7 +1145,10 @@ impl<'i> AST<'i> {
         self.expect_token(Token::OpenBrace(Location::ignored()))?;
         let mut selections = Vec::new();
         loop {
-            if let Some(_) = self.expect_optional_token(&Token::CloseBrace(Location::ignored())) {
+            if self
+                .expect_optional_token(&Token::CloseBrace(Location::ignored()))
+                .is_some()
+            {
                 break;
             }
             selections.push(self.parse_selection()?);
@@ -734,7 +1168,11 @@ impl<'i> AST<'i> {
         let mut field: FieldNode;
 
         let name = self.unwrap_next_token()?;
-        if let Some(_) = self.expect_optional_token(&Token::Colon(Location::ignored())) {
+        let start = name.location();
+        if self
+            .expect_optional_token(&Token::Colon(Location::ignored()))
+            .is_some()
+        {
             let root = self.unwrap_next_token()?;
             field = FieldNode::new(root)?;
             field.with_alias(name)?;
@@ -753,6 +1191,7 @@ impl<'i> AST<'i> {
             field.with_selections(selections);
         }
 
+        field.span = self.span_from(start);
         Ok(field)
     }
 
@@ -764,9 +1203,10 @@ impl<'i> AST<'i> {
                     let name = self.unwrap_next_token()?;
                     let _on = self.unwrap_next_token()?;
                     let node_type = self.unwrap_next_token()?;
-                    let frag_def = FragmentDefinitionNode::new(name, node_type)?
+                    let mut frag_def = FragmentDefinitionNode::new(name, node_type)?
                         .with_directives(self.parse_directives()?)
                         .with_selections(self.parse_selection_set()?);
+                    frag_def.span = self.span_from(loc);
 
                     Ok(frag_def)
                 }
@@ -778,7 +1218,7 @@ impl<'i> AST<'i> {
             }
         } else {
             Err(ParseError::UnexpectedToken {
-                expected: "Token<Name>".into(),
+                expected: vec![TokenKind::Name],
                 received: keyword.to_string(),
                 location: keyword.location(),
             })
@@ -786,33 +1226,41 @@ impl<'i> AST<'i> {
     }
 
     fn parse_fragment_spread(&mut self) -> ParseResult<FragmentSpread> {
-        self.expect_token(Token::Spread(Location::ignored()))?;
+        let start = self
+            .expect_token(Token::Spread(Location::ignored()))?
+            .location();
         match self.unwrap_peeked_token()? {
-            &Token::Name(_, "on") => {
-                Ok(FragmentSpread::Inline(self.parse_inline_fragment_spread()?))
-            }
-            &Token::At(_) => Ok(FragmentSpread::Inline(
-                self.parse_anonymous_inline_fragmen_spread()?,
+            &Token::Name(_, "on") => Ok(FragmentSpread::Inline(
+                self.parse_inline_fragment_spread(start)?,
+            )),
+            &Token::At(_) | &Token::OpenBrace(_) => Ok(FragmentSpread::Inline(
+                self.parse_anonymous_inline_fragmen_spread(start)?,
+            )),
+            &Token::Name(_, _) => Ok(FragmentSpread::Node(
+                self.parse_fragment_spread_node(start)?,
             )),
-            &Token::Name(_, _) => Ok(FragmentSpread::Node(self.parse_fragment_spread_node()?)),
             tok => Err(ParseError::UnexpectedToken {
                 location: tok.location(),
-                expected: "One of Token::Name or Token::At".into(),
+                expected: vec![TokenKind::Name, TokenKind::At, TokenKind::OpenBrace],
                 received: tok.to_string(),
             }),
         }
     }
 
-    fn parse_fragment_spread_node(&mut self) -> ParseResult<FragmentSpreadNode> {
+    fn parse_fragment_spread_node(&mut self, start: Location) -> ParseResult<FragmentSpreadNode> {
         let name = self.unwrap_next_token()?;
         let directives = self.parse_directives()?;
         Ok(FragmentSpreadNode {
             name: NameNode::new(name)?,
             directives,
+            span: self.span_from(start),
         })
     }
 
-    fn parse_inline_fragment_spread(&mut self) -> ParseResult<InlineFragmentSpreadNode> {
+    fn parse_inline_fragment_spread(
+        &mut self,
+        start: Location,
+    ) -> ParseResult<InlineFragmentSpreadNode> {
         let _on_tok = self.unwrap_next_token()?;
         let name = self.unwrap_next_token()?;
         let directives = self.parse_directives()?;
@@ -821,16 +1269,21 @@ impl<'i> AST<'i> {
             node_type: Some(NamedTypeNode::new(name)?),
             directives,
             selections,
+            span: self.span_from(start),
         })
     }
 
-    fn parse_anonymous_inline_fragmen_spread(&mut self) -> ParseResult<InlineFragmentSpreadNode> {
+    fn parse_anonymous_inline_fragmen_spread(
+        &mut self,
+        start: Location,
+    ) -> ParseResult<InlineFragmentSpreadNode> {
         let directives = self.parse_directives()?;
         let selections = self.parse_selection_set()?;
         Ok(InlineFragmentSpreadNode {
             node_type: None,
             directives,
             selections,
+            span: self.span_from(start),
         })
     }
 
@@ -838,11 +1291,12 @@ impl<'i> AST<'i> {
         if let Some(next) = self.lexer.next() {
             match next {
                 Ok(actual) => {
+                    self.consume(&actual);
                     if actual.is_same_type(&tok) {
                         Ok(actual)
                     } else {
                         Err(ParseError::UnexpectedToken {
-                            expected: tok.to_string(),
+                            expected: vec![tok.kind()],
                             received: actual.to_string(),
                             location: actual.location(),
                         })
@@ -860,7 +1314,9 @@ impl<'i> AST<'i> {
             match next {
                 Ok(actual) => {
                     if actual.is_same_type(tok) {
-                        Some(self.lexer.next().unwrap().unwrap())
+                        let actual = self.lexer.next().unwrap().unwrap();
+                        self.consume(&actual);
+                        Some(actual)
                     } else {
                         None
                     }
@@ -885,18 +1341,80 @@ impl<'i> AST<'i> {
     fn unwrap_next_token(&mut self) -> ParseResult<Token<'i>> {
         match self.lexer.next() {
             Some(res) => match res {
-                Ok(tok) => Ok(tok),
+                Ok(tok) => {
+                    self.consume(&tok);
+                    Ok(tok)
+                }
                 Err(lex_error) => Err(ParseError::LexError(lex_error)),
             },
             None => Err(ParseError::EOF),
         }
     }
+
+    /// Records the end of a consumed token so the enclosing node's span can be closed.
+    fn consume(&mut self, tok: &Token<'i>) {
+        match tok {
+            Token::Start | Token::End => return,
+            Token::OpenBrace(_) | Token::OpenParen(_) | Token::OpenSquare(_) => self.depth += 1,
+            Token::CloseBrace(_) | Token::CloseParen(_) | Token::CloseSquare(_) => {
+                self.depth = self.depth.saturating_sub(1)
+            }
+            Token::Name(..) if !self.keyword_seen => self.keyword_seen = true,
+            Token::Name(..) if self.resume.is_none() && starts_line_definition(tok) => {
+                self.resume = Some(tok.location())
+            }
+            _ => {}
+        }
+        self.last_end = tok.end_location();
+    }
+
+    fn peek_location(&mut self) -> ParseResult<Location> {
+        Ok(self.unwrap_peeked_token()?.location())
+    }
+
+    /// A span from `start` to the end of the last consumed token.
+    fn span_from(&self, start: Location) -> Span {
+        Span::new(start, self.last_end)
+    }
 }
 
-// struct Location<'a> {
-//     start: Token<'a>,
-//     end: Token<'a>,
-// }
+/// Definitions start at their description when they have one, otherwise at their keyword.
+fn definition_start(description: &Description, keyword: &Token) -> Location {
+    description
+        .as_ref()
+        .map_or(keyword.location(), |description| description.span.start)
+}
+
+/// Whether a token can begin a definition at the top level of a document.
+fn starts_definition(tok: &Token) -> bool {
+    match tok {
+        Token::Name(_, keyword) => matches!(
+            *keyword,
+            "type"
+                | "enum"
+                | "union"
+                | "interface"
+                | "input"
+                | "scalar"
+                | "schema"
+                | "directive"
+                | "extend"
+                | "query"
+                | "mutation"
+                | "subscription"
+                | "fragment"
+        ),
+        // An anonymous query's `{` is not a boundary, as it cannot be told apart from the body of
+        // the definition that failed. The shorthand is only valid for a lone operation anyway.
+        Token::Str(_, _) | Token::BlockStr(_, _) => true,
+        _ => false,
+    }
+}
+
+/// A definition keyword in the first column, which is almost always the start of a definition.
+fn starts_line_definition(tok: &Token) -> bool {
+    matches!(tok, Token::Name(..)) && tok.location().column == 1 && starts_definition(tok)
+}
 
 #[cfg(test)]
 mod tests {
@@ -915,19 +1433,19 @@ mod tests {
         let value = ast.parse_value();
         println!("IntValue: {:?}", value);
         assert!(value.is_ok());
-        assert_eq!(value.unwrap(), ValueNode::Int(IntValueNode { value: 42 }));
+        assert_eq!(value.unwrap(), ValueNode::Int(IntValueNode::from(42)));
     }
 
     #[test]
     fn it_parses_float_value() {
-        let mut ast = AST::new("3.1415926").unwrap();
+        let mut ast = AST::new("42.4242").unwrap();
         ast.expect_token(Token::Start).unwrap();
         let value = ast.parse_value();
         println!("FloatValue: {:?}", value);
         assert!(value.is_ok());
         assert_eq!(
             value.unwrap(),
-            ValueNode::Float(FloatValueNode { value: 3.1415926 })
+            ValueNode::Float(FloatValueNode::from(42.4242))
         );
     }
 
@@ -968,13 +1486,19 @@ mod tests {
         assert!(value.is_ok());
         assert_eq!(
             value.unwrap(),
-            ValueNode::Bool(BooleanValueNode { value: true })
+            ValueNode::Bool(BooleanValueNode {
+                value: true,
+                span: Span::ignored(),
+            })
         );
         let value = ast.parse_value();
         assert!(value.is_ok());
         assert_eq!(
             value.unwrap(),
-            ValueNode::Bool(BooleanValueNode { value: false })
+            ValueNode::Bool(BooleanValueNode {
+                value: false,
+                span: Span::ignored(),
+            })
         );
     }
 
@@ -997,9 +1521,16 @@ mod tests {
             value.unwrap(),
             ValueNode::List(ListValueNode {
                 values: vec![
-                    ValueNode::Bool(BooleanValueNode { value: true }),
-                    ValueNode::Bool(BooleanValueNode { value: false }),
-                ]
+                    ValueNode::Bool(BooleanValueNode {
+                        value: true,
+                        span: Span::ignored(),
+                    }),
+                    ValueNode::Bool(BooleanValueNode {
+                        value: false,
+                        span: Span::ignored(),
+                    }),
+                ],
+                span: Span::ignored(),
             })
         );
         let value = ast.parse_value();
@@ -1010,19 +1541,22 @@ mod tests {
                 values: vec![
                     ValueNode::List(ListValueNode {
                         values: vec![
-                            ValueNode::Int(IntValueNode { value: 1 }),
-                            ValueNode::Int(IntValueNode { value: 2 }),
-                            ValueNode::Int(IntValueNode { value: 3 }),
-                        ]
+                            ValueNode::Int(IntValueNode::from(1)),
+                            ValueNode::Int(IntValueNode::from(2)),
+                            ValueNode::Int(IntValueNode::from(3)),
+                        ],
+                        span: Span::ignored(),
                     }),
                     ValueNode::List(ListValueNode {
                         values: vec![
-                            ValueNode::Int(IntValueNode { value: 4 }),
-                            ValueNode::Int(IntValueNode { value: 5 }),
-                            ValueNode::Int(IntValueNode { value: 6 }),
-                        ]
+                            ValueNode::Int(IntValueNode::from(4)),
+                            ValueNode::Int(IntValueNode::from(5)),
+                            ValueNode::Int(IntValueNode::from(6)),
+                        ],
+                        span: Span::ignored(),
                     })
-                ]
+                ],
+                span: Span::ignored(),
             })
         )
     }
@@ -1035,7 +1569,10 @@ mod tests {
         assert!(value.is_ok());
         assert_eq!(
             value.unwrap(),
-            ValueNode::Object(ObjectValueNode { fields: vec![] })
+            ValueNode::Object(ObjectValueNode {
+                fields: vec![],
+                span: Span::ignored(),
+            })
         );
 
         let value = ast.parse_value();
@@ -1046,15 +1583,18 @@ mod tests {
                 fields: vec![
                     ObjectFieldNode {
                         name: NameNode::from("id"),
-                        value: ValueNode::Int(IntValueNode { value: 42 }),
+                        value: ValueNode::Int(IntValueNode::from(42)),
+                        span: Span::ignored(),
                     },
                     ObjectFieldNode {
                         name: NameNode::from("name"),
                         value: ValueNode::Str(
                             StringValueNode::new(Token::Str(Location::ignored(), "Obj")).unwrap()
                         ),
+                        span: Span::ignored(),
                     }
-                ]
+                ],
+                span: Span::ignored(),
             })
         )
     }
@@ -1068,7 +1608,8 @@ mod tests {
         assert_eq!(
             value.unwrap(),
             ValueNode::Variable(VariableNode {
-                name: NameNode::from("myVariable")
+                name: NameNode::from("myVariable"),
+                span: Span::ignored(),
             })
         );
     }
@@ -1084,6 +1625,7 @@ mod tests {
             vec![DirectiveNode {
                 name: NameNode::from("deprecated"),
                 arguments: None,
+                span: Span::ignored(),
             }]
         )
     }
@@ -1100,8 +1642,13 @@ mod tests {
                 name: NameNode::from("include"),
                 arguments: Some(vec![Argument {
                     name: NameNode::from("if"),
-                    value: ValueNode::Bool(BooleanValueNode { value: true })
+                    value: ValueNode::Bool(BooleanValueNode {
+                        value: true,
+                        span: Span::ignored(),
+                    }),
+                    span: Span::ignored(),
                 }]),
+                span: Span::ignored(),
             }]
         )
     }
@@ -1119,13 +1666,16 @@ mod tests {
                 arguments: Some(vec![
                     Argument {
                         name: NameNode::from("height"),
-                        value: ValueNode::Int(IntValueNode { value: 100 })
+                        value: ValueNode::Int(IntValueNode::from(100)),
+                        span: Span::ignored(),
                     },
                     Argument {
                         name: NameNode::from("width"),
-                        value: ValueNode::Int(IntValueNode { value: 50 })
+                        value: ValueNode::Int(IntValueNode::from(50)),
+                        span: Span::ignored(),
                     }
                 ]),
+                span: Span::ignored(),
             }]
         )
     }
@@ -1144,26 +1694,31 @@ mod tests {
                 name: NameNode::from("BadDirection"),
                 directives: Some(vec![DirectiveNode {
                     name: NameNode::from("depricated"),
-                    arguments: None
+                    arguments: None,
+                    span: Span::ignored(),
                 }]),
                 values: vec![
                     EnumValueDefinitionNode {
                         description: None,
                         name: NameNode::from("NORTH"),
                         directives: None,
+                        span: Span::ignored(),
                     },
                     EnumValueDefinitionNode {
                         description: None,
                         name: NameNode::from("SWEST"),
                         directives: Some(vec![DirectiveNode {
                             name: NameNode::from("badValue"),
-                            arguments: None
-                        }])
+                            arguments: None,
+                            span: Span::ignored(),
+                        }]),
+                        span: Span::ignored(),
                     },
                     EnumValueDefinitionNode {
                         description: None,
                         name: NameNode::from("EAST"),
                         directives: None,
+                        span: Span::ignored(),
                     },
                     EnumValueDefinitionNode {
                         description: None,
@@ -1172,11 +1727,18 @@ mod tests {
                             name: NameNode::from("badValue"),
                             arguments: Some(vec![Argument {
                                 name: NameNode::from("allow"),
-                                value: ValueNode::Bool(BooleanValueNode { value: true })
-                            }])
-                        }])
+                                value: ValueNode::Bool(BooleanValueNode {
+                                    value: true,
+                                    span: Span::ignored(),
+                                }),
+                                span: Span::ignored(),
+                            }]),
+                            span: Span::ignored(),
+                        }]),
+                        span: Span::ignored(),
                     },
-                ]
+                ],
+                span: Span::ignored(),
             })
         )
     }
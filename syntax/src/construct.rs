@@ -0,0 +1,273 @@
+//! Rust source that builds a parsed [`Document`], so a document parsed at compile time by the
+//! `gql!` macro of `syntax-macros` can be expanded into the code that constructs it.
+//!
+//! The generated code names every node by its path in the `syntax` crate, so it builds wherever
+//! `syntax` is a dependency.
+//!
+//! [`Document`]: ../document/struct.Document.html
+
+use crate::document::Document;
+use crate::nodes::enum_type_extension::*;
+use crate::nodes::input_type_extension::*;
+use crate::nodes::interface_type_extension::*;
+use crate::nodes::object_type_extension::*;
+use crate::nodes::scalar_type_extension::*;
+use crate::nodes::schema_extension::*;
+use crate::nodes::union_type_extension::*;
+use crate::nodes::*;
+use crate::token::{Location, Span};
+use std::sync::Arc;
+
+const PATH: &str = "::syntax::nodes::";
+const TOKEN_PATH: &str = "::syntax::token::";
+
+/// The source of an expression that evaluates to `document`.
+pub fn construct(document: &Document) -> String {
+    let mut code = String::from("::syntax::document::Document::new(");
+    document.definitions.construct(&mut code);
+    code.push(')');
+    code
+}
+
+trait Construct {
+    fn construct(&self, code: &mut String);
+}
+
+macro_rules! construct_structs {
+    ($($node:ident { $($field:ident),* })*) => {$(
+        impl Construct for $node {
+            fn construct(&self, code: &mut String) {
+                code.push_str(PATH);
+                code.push_str(concat!(stringify!($node), " { "));
+                $(
+                    code.push_str(concat!(stringify!($field), ": "));
+                    self.$field.construct(code);
+                    code.push_str(", ");
+                )*
+                code.push('}');
+            }
+        }
+    )*};
+}
+
+macro_rules! construct_enums {
+    ($($node:ident { $($variant:ident $(($inner:ident))?),* })*) => {$(
+        impl Construct for $node {
+            fn construct(&self, code: &mut String) {
+                code.push_str(PATH);
+                match self {
+                    $($node::$variant $(($inner))? => {
+                        code.push_str(concat!(stringify!($node), "::", stringify!($variant)));
+                        $(
+                            code.push('(');
+                            $inner.construct(code);
+                            code.push(')');
+                        )?
+                    })*
+                }
+            }
+        }
+    )*};
+}
+
+construct_structs! {
+    NameNode { value, span }
+    NamedTypeNode { name, span }
+    ListTypeNode { list_type, span }
+    VariableNode { name, span }
+    IntValueNode { raw, span }
+    FloatValueNode { raw, span }
+    BooleanValueNode { value, span }
+    EnumValueNode { value, span }
+    ListValueNode { values, span }
+    ObjectFieldNode { name, value, span }
+    ObjectValueNode { fields, span }
+    DirectiveNode { name, arguments, span }
+    InputValueDefinitionNode { description, name, input_type, default_value, directives, span }
+    VariableDefinitionNode { variable, variable_type, default_value, span }
+    Argument { name, value, span }
+    FieldDefinitionNode { description, name, arguments, field_type, directives, span }
+    EnumValueDefinitionNode { description, name, directives, span }
+    OperationTypeDefinitionNode { operation, node_type, span }
+    SchemaDefinitionNode { description, directives, operations, span }
+    ScalarTypeDefinitionNode { description, name, directives, span }
+    ObjectTypeDefinitionNode { description, name, interfaces, directives, fields, span }
+    InputTypeDefinitionNode { description, name, directives, fields, span }
+    InterfaceTypeDefinitionNode { description, name, interfaces, directives, fields, span }
+    EnumTypeDefinitionNode { description, name, directives, values, span }
+    UnionTypeDefinitionNode { description, name, directives, types, span }
+    DirectiveDefinitionNode { description, name, arguments, repeatable, locations, span }
+    FieldNode { name, alias, arguments, directives, selections, span }
+    FragmentSpreadNode { name, directives, span }
+    InlineFragmentSpreadNode { node_type, directives, selections, span }
+    FragmentDefinitionNode { name, node_type, directives, selections, span }
+    QueryDefinitionNode { name, variables, directives, selections, span }
+    MutationDefinitionNode { name, variables, directives, selections, span }
+    SubscriptionDefinitionNode { name, variables, directives, selections, span }
+    SchemaExtensionNode { description, directives, operations, span }
+    ScalarTypeExtensionNode { description, name, directives, span }
+    ObjectTypeExtensionNode { description, name, interfaces, directives, fields, span }
+    InterfaceTypeExtensionNode { description, name, interfaces, directives, fields, span }
+    UnionTypeExtensionNode { description, name, directives, types, span }
+    EnumTypeExtensionNode { description, name, directives, values, span }
+    InputTypeExtensionNode { description, name, directives, fields, span }
+}
+
+construct_enums! {
+    TypeNode { Named(node), List(node), NonNull(node) }
+    ValueNode {
+        Variable(node),
+        Int(node),
+        Float(node),
+        Str(node),
+        Bool(node),
+        Null,
+        Enum(node),
+        List(node),
+        Object(node)
+    }
+    Operation { Query, Mutation, Subscription }
+    TypeDefinitionNode {
+        Scalar(node),
+        Object(node),
+        Interface(node),
+        Union(node),
+        Enum(node),
+        Input(node)
+    }
+    ExecutableDirectiveLocation {
+        Query,
+        Mutation,
+        Subscription,
+        Field,
+        FragmentDefinition,
+        FragmentSpread,
+        InlineFragment,
+        VariableDefinition
+    }
+    TypeSystemDirectiveLocation {
+        Schema,
+        Scalar,
+        Object,
+        FieldDefinition,
+        ArgumentDefinition,
+        Interface,
+        Union,
+        Enum,
+        EnumValue,
+        InputObject,
+        InputFieldDefinition
+    }
+    DirectiveLocation { Executable(node), TypeSystem(node) }
+    TypeSystemDefinitionNode { Schema(node), Type(node), Directive(node) }
+    TypeSystemExtensionNode {
+        Schema(node),
+        Scalar(node),
+        Object(node),
+        Interface(node),
+        Union(node),
+        Enum(node),
+        Input(node)
+    }
+    FragmentSpread { Node(node), Inline(node) }
+    Selection { Field(node), Fragment(node) }
+    OperationTypeNode { Query(node), Mutation(node), Subscription(node) }
+    ExecutableDefinitionNode { Operation(node), Fragment(node) }
+    DefinitionNode { Executable(node), TypeSystem(node), Extension(node) }
+}
+
+/// Whether a string was a block string is private to the node, so it is built with `from`.
+impl Construct for StringValueNode {
+    fn construct(&self, code: &mut String) {
+        code.push_str(&format!(
+            "{{ let mut string = {}StringValueNode::from({:?}, {}); string.span = ",
+            PATH,
+            self.value,
+            self.is_block()
+        ));
+        self.span.construct(code);
+        code.push_str("; string }");
+    }
+}
+
+impl Construct for Span {
+    fn construct(&self, code: &mut String) {
+        code.push_str(TOKEN_PATH);
+        code.push_str("Span::new(");
+        self.start.construct(code);
+        code.push_str(", ");
+        self.end.construct(code);
+        code.push(')');
+    }
+}
+
+impl Construct for Location {
+    fn construct(&self, code: &mut String) {
+        code.push_str(&format!(
+            "{}Location::new({}, {}, {})",
+            TOKEN_PATH, self.absolute_position, self.line, self.column
+        ));
+    }
+}
+
+impl Construct for String {
+    fn construct(&self, code: &mut String) {
+        code.push_str(&format!("::std::string::String::from({:?})", self));
+    }
+}
+
+impl Construct for bool {
+    fn construct(&self, code: &mut String) {
+        code.push_str(if *self { "true" } else { "false" });
+    }
+}
+
+impl<T: Construct> Construct for Option<T> {
+    fn construct(&self, code: &mut String) {
+        match self {
+            Some(value) => {
+                code.push_str("::std::option::Option::Some(");
+                value.construct(code);
+                code.push(')');
+            }
+            None => code.push_str("::std::option::Option::None"),
+        }
+    }
+}
+
+impl<T: Construct> Construct for Vec<T> {
+    fn construct(&self, code: &mut String) {
+        code.push_str("::std::vec![");
+        for value in self {
+            value.construct(code);
+            code.push_str(", ");
+        }
+        code.push(']');
+    }
+}
+
+impl<T: Construct> Construct for Arc<T> {
+    fn construct(&self, code: &mut String) {
+        code.push_str("::std::sync::Arc::new(");
+        self.as_ref().construct(code);
+        code.push(')');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[test]
+    fn constructs_nodes_by_path() {
+        let document = parse("{ user(name: \"Ada\\n\") }").unwrap();
+        let code = construct(&document);
+        assert!(code.starts_with(
+            "::syntax::document::Document::new(::std::vec![::syntax::nodes::DefinitionNode::\
+             Executable(::syntax::nodes::ExecutableDefinitionNode::Operation("
+        ));
+        assert!(code.contains("StringValueNode::from(\"Ada\\n\", false)"));
+        assert!(code.contains("Location::new(2, 1, 3)"));
+    }
+}
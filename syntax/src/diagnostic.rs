@@ -0,0 +1,453 @@
+//! Renders errors against the source text they came from.
+//!
+//! A [`Diagnostic`] turns a [`ParseError`], [`LexError`] or [`ValidationError`] into a short
+//! title, the location of the problem, what was expected and found there and a hint on how to
+//! fix it. Rendering it with the source prints the offending line with a caret under the
+//! problem.
+//!
+//! # Example
+//!
+//! ```
+//! use syntax::diagnostic::Diagnostic;
+//!
+//! let source = "type Person {\n  name String\n}";
+//! let error = syntax::parse(source).unwrap_err();
+//! let rendered = Diagnostic::from_parse_error(&error, source).render(source);
+//! assert_eq!(
+//!     rendered,
+//!     r#"error: Unexpected token
+//!  --> line 2, column 8
+//!   |
+//! 2 |   name String
+//!   |        ^^^^^^ expected `:`, found `String`
+//! "#
+//! );
+//! ```
+//!
+//! [`Diagnostic`]: struct.Diagnostic.html
+//! [`ParseError`]: ../error/enum.ParseError.html
+//! [`LexError`]: ../error/enum.LexError.html
+//! [`ValidationError`]: ../error/struct.ValidationError.html
+
+use crate::error::{describe_kinds, LexError, ParseError, ValidationError};
+use crate::token::Location;
+use std::fmt;
+
+const END_OF_DOCUMENT: &str = "end of document";
+const END_OF_DOCUMENT_HINT: &str =
+    "The document ended early. Check for an unclosed brace, bracket or parenthesis";
+
+/// A user facing description of an error in a GraphQL string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// A short summary of the problem
+    pub title: String,
+    /// Where the problem starts, when it is known
+    pub location: Option<Location>,
+    /// How many characters the problem covers on its line
+    pub length: usize,
+    /// What the parser expected to find
+    pub expected: Option<String>,
+    /// What the parser found instead
+    pub found: Option<String>,
+    /// A suggestion on how to fix the problem
+    pub hint: Option<String>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic with only a title.
+    pub fn new(title: &str) -> Diagnostic {
+        Diagnostic {
+            title: String::from(title),
+            location: None,
+            length: 1,
+            expected: None,
+            found: None,
+            hint: None,
+        }
+    }
+
+    /// Points the diagnostic at `length` characters starting at `location`.
+    pub fn with_location(mut self, location: Location, length: usize) -> Diagnostic {
+        // Tokens without a position in the string, like the end of the document, have line 0
+        if location.line > 0 {
+            self.location = Some(location);
+            self.length = length.max(1);
+        }
+        self
+    }
+
+    /// Describes what was expected at the location.
+    pub fn with_expected(mut self, expected: &str) -> Diagnostic {
+        self.expected = Some(String::from(expected));
+        self
+    }
+
+    /// Describes what was found at the location.
+    pub fn with_found(mut self, found: &str) -> Diagnostic {
+        self.found = Some(String::from(found));
+        self
+    }
+
+    /// Suggests how to fix the problem.
+    pub fn with_hint(mut self, hint: &str) -> Diagnostic {
+        self.hint = Some(String::from(hint));
+        self
+    }
+
+    /// Describes a [`ParseError`] raised while parsing `source`.
+    ///
+    /// [`ParseError`]: ../error/enum.ParseError.html
+    pub fn from_parse_error(error: &ParseError, source: &str) -> Diagnostic {
+        match error {
+            ParseError::BadValue => Diagnostic::new("Bad value")
+                .with_hint("Check the value against the GraphQL specification"),
+            ParseError::DocumentEmpty => Diagnostic::new("Empty document")
+                .with_hint("A document must contain at least one definition"),
+            ParseError::ArgumentEmpty(location) => Diagnostic::new("Empty arguments")
+                .at_lexeme(*location, source)
+                .with_expected("at least one argument")
+                .with_hint("Add an argument or remove the parentheses"),
+            ParseError::ObjectEmpty(location) => Diagnostic::new("Empty definition")
+                .at_lexeme(*location, source)
+                .with_hint("Add at least one field between the braces"),
+            ParseError::EOF => Diagnostic::new("Unexpected end of document")
+                .at_end(source)
+                .with_found(END_OF_DOCUMENT)
+                .with_hint(END_OF_DOCUMENT_HINT),
+            ParseError::LexError(lex_error) => Diagnostic::from_lex_error(lex_error, source),
+            ParseError::UnexpectedToken {
+                expected, location, ..
+            } => {
+                let diagnostic =
+                    Diagnostic::new("Unexpected token").with_expected(&describe_kinds(expected));
+                if location.line == 0 {
+                    diagnostic
+                        .at_end(source)
+                        .with_found(END_OF_DOCUMENT)
+                        .with_hint(END_OF_DOCUMENT_HINT)
+                } else {
+                    diagnostic.at_lexeme(*location, source)
+                }
+            }
+            ParseError::UnexpectedKeyword {
+                expected,
+                received,
+                location,
+            } => Diagnostic::new("Unexpected keyword")
+                .with_location(*location, received.chars().count())
+                .with_expected(expected)
+                .with_found(&format!("`{}`", received))
+                .with_hint("Check the keyword for typos"),
+            ParseError::NotImplemented => Diagnostic::new("Not implemented")
+                .with_hint("This part of GraphQL is not supported yet"),
+        }
+    }
+
+    /// Describes a [`LexError`] raised while lexing `source`.
+    ///
+    /// [`LexError`]: ../error/enum.LexError.html
+    pub fn from_lex_error(error: &LexError, source: &str) -> Diagnostic {
+        match error {
+            LexError::UnmatchedQuote(location) => {
+                // The lexer reports unmatched quotes at the column after the quote
+                let quote = Location::new(
+                    location.absolute_position,
+                    location.line,
+                    location.column.saturating_sub(1).max(1),
+                );
+                Diagnostic::new("Unmatched quote")
+                    .at_lexeme(quote, source)
+                    .with_hint("Close the string with a matching `\"`")
+            }
+            LexError::UnknownCharacter(location) => Diagnostic::new("Unknown character")
+                .at_lexeme(*location, source)
+                .with_hint("Remove the character or move it inside a string"),
+            LexError::UnexpectedCharacter(location) => Diagnostic::new("Unexpected character")
+                .at_lexeme(*location, source)
+                .with_hint("A spread is written as `...`"),
+            LexError::UnableToConvert(location, expected) => {
+                Diagnostic::new("Unable to convert value")
+                    .at_lexeme(*location, source)
+                    .with_expected(&format!("a valid {}", expected))
+            }
+            LexError::InvalidEscape(location) => {
+                let rest = source.get(location.absolute_position..).unwrap_or("");
+                let length = if rest.starts_with("\\u") { 6 } else { 2 };
+                let escape: String = rest.chars().take(length).collect();
+                Diagnostic::new("Invalid escape sequence")
+                    .with_location(*location, escape.chars().count())
+                    .with_found(&format!("`{}`", escape))
+                    .with_hint(
+                        "Valid escapes are \\\", \\\\, \\/, \\b, \\f, \\n, \\r, \\t and \\uXXXX",
+                    )
+            }
+            LexError::LineTerminatorInString(location) => {
+                Diagnostic::new("Line terminator in string")
+                    .with_location(*location, 1)
+                    .with_hint("Escape it as `\\n`, or use a block string for text on many lines")
+            }
+            LexError::EOF => Diagnostic::new("Unexpected end of document")
+                .at_end(source)
+                .with_found(END_OF_DOCUMENT)
+                .with_hint(END_OF_DOCUMENT_HINT),
+        }
+    }
+
+    /// Describes a [`ValidationError`]. Its location is underlined when it has one.
+    ///
+    /// [`ValidationError`]: ../error/struct.ValidationError.html
+    pub fn from_validation_error(error: &ValidationError) -> Diagnostic {
+        let diagnostic = Diagnostic::new(&error.message);
+        match error.location {
+            Some(location) => diagnostic.with_location(location, 1),
+            None => diagnostic,
+        }
+    }
+
+    /// Renders the diagnostic with the line of `source` it points at.
+    pub fn render(&self, source: &str) -> String {
+        let mut output = format!("error: {}\n", self.title);
+        let line = self
+            .location
+            .and_then(|location| Some((location, source.lines().nth(location.line - 1)?)));
+        match line {
+            Some((location, text)) => {
+                let number = location.line.to_string();
+                let gutter = " ".repeat(number.len());
+                output.push_str(&format!(
+                    "{}--> line {}, column {}\n",
+                    gutter, location.line, location.column
+                ));
+                output.push_str(&format!("{} |\n", gutter));
+                output.push_str(&format!("{} | {}\n", number, text));
+                // Keep tabs so the caret lines up with the text above it
+                let padding: String = text
+                    .chars()
+                    .take(location.column - 1)
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect();
+                output.push_str(&format!(
+                    "{} | {}{}",
+                    gutter,
+                    padding,
+                    "^".repeat(self.length)
+                ));
+                let label = self.label();
+                if !label.is_empty() {
+                    output.push(' ');
+                    output.push_str(&label);
+                }
+                output.push('\n');
+                if let Some(hint) = &self.hint {
+                    output.push_str(&format!("{} |\n{} = hint: {}\n", gutter, gutter, hint));
+                }
+            }
+            None => {
+                let label = self.label();
+                if !label.is_empty() {
+                    output.push_str(&format!("  = {}\n", label));
+                }
+                if let Some(hint) = &self.hint {
+                    output.push_str(&format!("  = hint: {}\n", hint));
+                }
+            }
+        }
+        output
+    }
+
+    fn label(&self) -> String {
+        match (&self.expected, &self.found) {
+            (Some(expected), Some(found)) => format!("expected {}, found {}", expected, found),
+            (Some(expected), None) => format!("expected {}", expected),
+            (None, Some(found)) => format!("found {}", found),
+            (None, None) => String::new(),
+        }
+    }
+
+    /// Points at the token starting at `location` and reports it as found.
+    fn at_lexeme(self, location: Location, source: &str) -> Diagnostic {
+        let lexeme = lexeme_at(source, location.absolute_position);
+        let diagnostic = self.with_location(location, lexeme.chars().count());
+        if lexeme.is_empty() {
+            diagnostic.with_found(END_OF_DOCUMENT)
+        } else {
+            diagnostic.with_found(&format!("`{}`", lexeme))
+        }
+    }
+
+    /// Points just past the last character of `source`.
+    fn at_end(self, source: &str) -> Diagnostic {
+        let line = source.lines().count().max(1);
+        let column = source.lines().last().map_or(0, |text| text.chars().count()) + 1;
+        self.with_location(Location::new(source.len(), line, column), 1)
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.title)?;
+        if let Some(location) = &self.location {
+            write!(f, " on line {}, column {}", location.line, location.column)?;
+        }
+        let label = self.label();
+        if !label.is_empty() {
+            write!(f, ": {}", label)?;
+        }
+        Ok(())
+    }
+}
+
+/// The text of the token that starts at `position`, limited to a single line.
+fn lexeme_at(source: &str, position: usize) -> &str {
+    let rest = match source.get(position..) {
+        Some(rest) => rest,
+        None => return "",
+    };
+    let first = match rest.chars().next() {
+        Some(first) => first,
+        None => return "",
+    };
+    let length = if rest.starts_with("\"\"\"") || rest.starts_with("...") {
+        3
+    } else if first == '"' {
+        rest[1..].find(['"', '\n']).map_or(rest.len(), |offset| {
+            // Include the closing quote, but not the newline of an unterminated string
+            if rest[1 + offset..].starts_with('"') {
+                offset + 2
+            } else {
+                offset + 1
+            }
+        })
+    } else if first.is_ascii_digit() || first == '-' {
+        rest.find(|c: char| !(c.is_ascii_alphanumeric() || "-+.".contains(c)))
+            .unwrap_or(rest.len())
+    } else if first.is_alphanumeric() || first == '_' {
+        rest.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .unwrap_or(rest.len())
+    } else {
+        first.len_utf8()
+    };
+    &rest[..length]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use crate::token::TokenKind;
+
+    fn render(source: &str) -> String {
+        Diagnostic::from_parse_error(&parse(source).unwrap_err(), source).render(source)
+    }
+
+    #[test]
+    fn renders_unexpected_keyword() {
+        assert_eq!(
+            render("scalar Date\ntypo Person { name: String }"),
+            r#"error: Unexpected keyword
+ --> line 2, column 1
+  |
+2 | typo Person { name: String }
+  | ^^^^ expected A valid GraphQL keyword, found `typo`
+  |
+  = hint: Check the keyword for typos
+"#
+        );
+    }
+
+    #[test]
+    fn renders_empty_object() {
+        assert_eq!(
+            render("type Empty {}"),
+            r#"error: Empty definition
+ --> line 1, column 6
+  |
+1 | type Empty {}
+  |      ^^^^^ found `Empty`
+  |
+  = hint: Add at least one field between the braces
+"#
+        );
+    }
+
+    #[test]
+    fn renders_end_of_document() {
+        assert_eq!(
+            render("type Person {\n  name: String\n"),
+            r#"error: Unexpected token
+ --> line 2, column 15
+  |
+2 |   name: String
+  |               ^ expected a name, found end of document
+  |
+  = hint: The document ended early. Check for an unclosed brace, bracket or parenthesis
+"#
+        );
+    }
+
+    #[test]
+    fn renders_lex_errors() {
+        assert_eq!(
+            render("type Person {\n  name: \"Str"),
+            r#"error: Unmatched quote
+ --> line 2, column 9
+  |
+2 |   name: "Str
+  |         ^^^^ found `"Str`
+  |
+  = hint: Close the string with a matching `"`
+"#
+        );
+        assert_eq!(
+            render("type Person {\n  name: \"Str\n}"),
+            r#"error: Line terminator in string
+ --> line 2, column 13
+  |
+2 |   name: "Str
+  |             ^
+  |
+  = hint: Escape it as `\n`, or use a block string for text on many lines
+"#
+        );
+    }
+
+    #[test]
+    fn renders_validation_errors_without_a_location() {
+        let error = ValidationError::new("Unknown type Foo");
+        assert_eq!(
+            Diagnostic::from_validation_error(&error).render(""),
+            "error: Unknown type Foo\n"
+        );
+    }
+
+    #[test]
+    fn describes_expected_token_kinds() {
+        assert_eq!(describe_kinds(&[TokenKind::Name]), "a name");
+        assert_eq!(
+            describe_kinds(&[TokenKind::Name, TokenKind::OpenBrace]),
+            "a name or `{`"
+        );
+        assert_eq!(
+            describe_kinds(&[TokenKind::Name, TokenKind::At, TokenKind::OpenBrace]),
+            "one of a name, `@` or `{`"
+        );
+        assert_eq!(
+            render("query { user(id: ) }").lines().nth(4),
+            Some(
+                "  |                  ^ expected one of a name, an integer, a float, a string, \
+                 `$`, `[` or `{`, found `)`"
+            )
+        );
+    }
+
+    #[test]
+    fn displays_on_one_line() {
+        let source = "type Person {\n  name String\n}";
+        let diagnostic = Diagnostic::from_parse_error(&parse(source).unwrap_err(), source);
+        assert_eq!(
+            diagnostic.to_string(),
+            "Unexpected token on line 2, column 8: expected `:`, found `String`"
+        );
+    }
+}
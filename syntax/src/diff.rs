@@ -0,0 +1,737 @@
+//! Compares two versions of a schema and classifies every change by how it affects clients
+//! written against the old version.
+//!
+//! - A breaking change makes some operations that were valid invalid, such as removing a type
+//!   or a field, or adding a required argument.
+//! - A dangerous change keeps every operation valid but can change how clients behave, such as
+//!   adding an enum value a client does not expect or changing the default of an argument.
+//! - A safe change cannot affect existing clients, such as adding a type or changing a
+//!   description.
+//!
+//! # Example
+//!
+//! ```
+//! use syntax::diff::{self, Criticality};
+//!
+//! let old = syntax::parse("type Query { user(id: ID): User }\ntype User { name: String }")
+//!     .unwrap();
+//! let new = syntax::parse("type Query { user(id: ID!): User }\ntype User { name: String! }")
+//!     .unwrap();
+//!
+//! let changes = diff::diff(&old, &new);
+//! assert_eq!(changes.len(), 2);
+//! assert_eq!(changes[0].criticality, Criticality::Breaking);
+//! assert_eq!(
+//!     changes[0].message,
+//!     "Argument id of Query.user changed type from ID to ID!"
+//! );
+//! assert_eq!(changes[1].criticality, Criticality::Safe);
+//! ```
+
+use crate::document::Document;
+use crate::nodes::*;
+use crate::printer;
+use std::fmt;
+
+/// How a change affects clients of the old schema.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Criticality {
+    /// Existing clients are not affected
+    Safe,
+    /// Existing operations stay valid, but clients may see values they do not expect
+    Dangerous,
+    /// Some existing operations become invalid
+    Breaking,
+}
+
+impl fmt::Display for Criticality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Criticality::Safe => write!(f, "safe"),
+            Criticality::Dangerous => write!(f, "dangerous"),
+            Criticality::Breaking => write!(f, "breaking"),
+        }
+    }
+}
+
+/// A single difference between two schemas.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Change {
+    /// How the change affects clients of the old schema
+    pub criticality: Criticality,
+    /// What changed, such as `Field name was removed from User`
+    pub message: String,
+}
+
+impl Change {
+    /// Whether the change makes some operations that were valid invalid.
+    pub fn is_breaking(&self) -> bool {
+        self.criticality == Criticality::Breaking
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} change: {}", self.criticality, self.message)
+    }
+}
+
+/// Lists the changes between two schemas. Definitions that changed are reported in the order
+/// of the old schema, followed by the definitions that were added in the order of the new
+/// schema.
+///
+/// Both documents should contain type system definitions only. Extensions are not applied, so
+/// merge them into their definitions with [`Document::merge`] first.
+///
+/// [`Document::merge`]: ../document/struct.Document.html#method.merge
+pub fn diff(old: &Document, new: &Document) -> Vec<Change> {
+    let old = Definitions::new(old);
+    let new = Definitions::new(new);
+    let mut differ = Differ {
+        changes: Vec::new(),
+    };
+
+    for (operation, name) in [
+        (Operation::Query, "query"),
+        (Operation::Mutation, "mutation"),
+        (Operation::Subscription, "subscription"),
+    ] {
+        match (old.root(&operation), new.root(&operation)) {
+            (Some(old_root), Some(new_root)) if old_root != new_root => differ.breaking(format!(
+                "Root {} type changed from {} to {}",
+                name, old_root, new_root
+            )),
+            (Some(old_root), None) => {
+                differ.breaking(format!("Root {} type {} was removed", name, old_root))
+            }
+            (None, Some(new_root)) => {
+                differ.safe(format!("Root {} type {} was added", name, new_root))
+            }
+            _ => (),
+        }
+    }
+
+    for old_type in &old.types {
+        let name = &old_type.name().value;
+        match new.get_type(name) {
+            Some(new_type) => differ.type_definition(old_type, new_type),
+            None => differ.breaking(format!("Type {} was removed", name)),
+        }
+    }
+    for new_type in &new.types {
+        if old.get_type(&new_type.name().value).is_none() {
+            differ.safe(format!("Type {} was added", new_type.name().value));
+        }
+    }
+
+    for old_directive in &old.directives {
+        let name = &old_directive.name.value;
+        match new.get_directive(name) {
+            Some(new_directive) => differ.directive(old_directive, new_directive),
+            None => differ.breaking(format!("Directive @{} was removed", name)),
+        }
+    }
+    for new_directive in &new.directives {
+        if old.get_directive(&new_directive.name.value).is_none() {
+            differ.safe(format!("Directive @{} was added", new_directive.name.value));
+        }
+    }
+
+    differ.changes
+}
+
+/// The type system definitions of one version of a schema.
+struct Definitions<'a> {
+    schema: Option<&'a SchemaDefinitionNode>,
+    types: Vec<&'a TypeDefinitionNode>,
+    directives: Vec<&'a DirectiveDefinitionNode>,
+}
+
+impl<'a> Definitions<'a> {
+    fn new(document: &'a Document) -> Self {
+        let mut definitions = Definitions {
+            schema: None,
+            types: Vec::new(),
+            directives: Vec::new(),
+        };
+        for definition in &document.definitions {
+            match definition {
+                DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Schema(schema)) => {
+                    definitions.schema = Some(schema)
+                }
+                DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(type_definition)) => {
+                    definitions.types.push(type_definition)
+                }
+                DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Directive(directive)) => {
+                    definitions.directives.push(directive)
+                }
+                _ => (),
+            }
+        }
+        definitions
+    }
+
+    fn get_type(&self, name: &str) -> Option<&'a TypeDefinitionNode> {
+        self.types
+            .iter()
+            .find(|type_definition| type_definition.name().value == name)
+            .copied()
+    }
+
+    fn get_directive(&self, name: &str) -> Option<&'a DirectiveDefinitionNode> {
+        self.directives
+            .iter()
+            .find(|directive| directive.name.value == name)
+            .copied()
+    }
+
+    /// The root type of an operation. Without a schema definition the root types are the
+    /// object types named `Query`, `Mutation` and `Subscription`.
+    fn root(&self, operation: &Operation) -> Option<&'a str> {
+        match self.schema {
+            Some(schema) => schema
+                .operations
+                .iter()
+                .find(|root| &root.operation == operation)
+                .map(|root| root.node_type.name.value.as_str()),
+            None => {
+                let name = match operation {
+                    Operation::Query => "Query",
+                    Operation::Mutation => "Mutation",
+                    Operation::Subscription => "Subscription",
+                };
+                match self.get_type(name) {
+                    Some(TypeDefinitionNode::Object(object)) => Some(object.name.value.as_str()),
+                    _ => None,
+                }
+            }
+        }
+    }
+}
+
+fn kind(type_definition: &TypeDefinitionNode) -> &'static str {
+    match type_definition {
+        TypeDefinitionNode::Scalar(_) => "a scalar",
+        TypeDefinitionNode::Object(_) => "an object",
+        TypeDefinitionNode::Interface(_) => "an interface",
+        TypeDefinitionNode::Union(_) => "a union",
+        TypeDefinitionNode::Enum(_) => "an enum",
+        TypeDefinitionNode::Input(_) => "an input object",
+    }
+}
+
+fn description(type_definition: &TypeDefinitionNode) -> &Description {
+    match type_definition {
+        TypeDefinitionNode::Scalar(scalar) => &scalar.description,
+        TypeDefinitionNode::Object(object) => &object.description,
+        TypeDefinitionNode::Interface(interface) => &interface.description,
+        TypeDefinitionNode::Union(union) => &union.description,
+        TypeDefinitionNode::Enum(enum_type) => &enum_type.description,
+        TypeDefinitionNode::Input(input) => &input.description,
+    }
+}
+
+fn names(types: &Option<Vec<NamedTypeNode>>) -> Vec<&str> {
+    types
+        .iter()
+        .flatten()
+        .map(|named_type| named_type.name.value.as_str())
+        .collect()
+}
+
+/// Whether every value a field of the old type returns is also a value of the new type as far
+/// as clients are concerned. A field may stop returning null, but cannot start.
+fn is_safe_output_change(old: &TypeNode, new: &TypeNode) -> bool {
+    match (old, new) {
+        (TypeNode::Named(old), TypeNode::Named(new)) => old.name.value == new.name.value,
+        (TypeNode::List(old), TypeNode::List(new)) => {
+            is_safe_output_change(&old.list_type, &new.list_type)
+        }
+        (TypeNode::NonNull(old), TypeNode::NonNull(new)) => is_safe_output_change(old, new),
+        (old, TypeNode::NonNull(new)) => is_safe_output_change(old, new),
+        _ => false,
+    }
+}
+
+/// Whether every value clients could send for the old input type is still accepted by the new
+/// one. An input may start accepting null, but cannot stop.
+fn is_safe_input_change(old: &TypeNode, new: &TypeNode) -> bool {
+    match (old, new) {
+        (TypeNode::Named(old), TypeNode::Named(new)) => old.name.value == new.name.value,
+        (TypeNode::List(old), TypeNode::List(new)) => {
+            is_safe_input_change(&old.list_type, &new.list_type)
+        }
+        (TypeNode::NonNull(old), TypeNode::NonNull(new)) => is_safe_input_change(old, new),
+        (TypeNode::NonNull(old), new) => is_safe_input_change(old, new),
+        _ => false,
+    }
+}
+
+fn is_required(input: &InputValueDefinitionNode) -> bool {
+    matches!(input.input_type, TypeNode::NonNull(_)) && input.default_value.is_none()
+}
+
+struct Differ {
+    changes: Vec<Change>,
+}
+
+impl Differ {
+    fn push(&mut self, criticality: Criticality, message: String) {
+        self.changes.push(Change {
+            criticality,
+            message,
+        });
+    }
+
+    fn breaking(&mut self, message: String) {
+        self.push(Criticality::Breaking, message)
+    }
+
+    fn dangerous(&mut self, message: String) {
+        self.push(Criticality::Dangerous, message)
+    }
+
+    fn safe(&mut self, message: String) {
+        self.push(Criticality::Safe, message)
+    }
+
+    fn description(&mut self, coordinate: &str, old: &Description, new: &Description) {
+        let old = old.as_ref().map(|description| description.value.as_str());
+        let new = new.as_ref().map(|description| description.value.as_str());
+        match (old, new) {
+            (None, Some(_)) => self.safe(format!("Description was added to {}", coordinate)),
+            (Some(_), None) => self.safe(format!("Description was removed from {}", coordinate)),
+            (Some(old), Some(new)) if old != new => {
+                self.safe(format!("Description of {} changed", coordinate))
+            }
+            _ => (),
+        }
+    }
+
+    fn type_definition(&mut self, old: &TypeDefinitionNode, new: &TypeDefinitionNode) {
+        let name = &old.name().value;
+        self.description(name, description(old), description(new));
+        match (old, new) {
+            (TypeDefinitionNode::Scalar(_), TypeDefinitionNode::Scalar(_)) => (),
+            (TypeDefinitionNode::Object(old), TypeDefinitionNode::Object(new)) => {
+                self.interfaces(name, &old.interfaces, &new.interfaces);
+                self.fields(name, &old.fields, &new.fields);
+            }
+            (TypeDefinitionNode::Interface(old), TypeDefinitionNode::Interface(new)) => {
+                self.interfaces(name, &old.interfaces, &new.interfaces);
+                self.fields(name, &old.fields, &new.fields);
+            }
+            (TypeDefinitionNode::Union(old), TypeDefinitionNode::Union(new)) => {
+                self.union_members(name, &old.types, &new.types)
+            }
+            (TypeDefinitionNode::Enum(old), TypeDefinitionNode::Enum(new)) => {
+                self.enum_values(name, &old.values, &new.values)
+            }
+            (TypeDefinitionNode::Input(old), TypeDefinitionNode::Input(new)) => {
+                self.input_fields(name, &old.fields, &new.fields)
+            }
+            (old, new) => self.breaking(format!(
+                "Type {} changed from {} to {}",
+                name,
+                kind(old),
+                kind(new)
+            )),
+        }
+    }
+
+    fn interfaces(
+        &mut self,
+        name: &str,
+        old: &Option<Vec<NamedTypeNode>>,
+        new: &Option<Vec<NamedTypeNode>>,
+    ) {
+        let (old, new) = (names(old), names(new));
+        for interface in old.iter().filter(|interface| !new.contains(interface)) {
+            self.breaking(format!("{} no longer implements {}", name, interface));
+        }
+        for interface in new.iter().filter(|interface| !old.contains(interface)) {
+            self.dangerous(format!("{} now implements {}", name, interface));
+        }
+    }
+
+    fn union_members(&mut self, name: &str, old: &[NamedTypeNode], new: &[NamedTypeNode]) {
+        let has = |members: &[NamedTypeNode], member: &NamedTypeNode| {
+            members
+                .iter()
+                .any(|other| other.name.value == member.name.value)
+        };
+        for member in old.iter().filter(|member| !has(new, member)) {
+            self.breaking(format!(
+                "Member {} was removed from union {}",
+                member.name.value, name
+            ));
+        }
+        for member in new.iter().filter(|member| !has(old, member)) {
+            self.dangerous(format!(
+                "Member {} was added to union {}",
+                member.name.value, name
+            ));
+        }
+    }
+
+    fn enum_values(
+        &mut self,
+        name: &str,
+        old: &[EnumValueDefinitionNode],
+        new: &[EnumValueDefinitionNode],
+    ) {
+        for old_value in old {
+            let value = &old_value.name.value;
+            match new.iter().find(|new_value| &new_value.name.value == value) {
+                Some(new_value) => self.description(
+                    &format!("{}.{}", name, value),
+                    &old_value.description,
+                    &new_value.description,
+                ),
+                None => self.breaking(format!("Value {} was removed from enum {}", value, name)),
+            }
+        }
+        for new_value in new {
+            if !old
+                .iter()
+                .any(|value| value.name.value == new_value.name.value)
+            {
+                self.dangerous(format!(
+                    "Value {} was added to enum {}",
+                    new_value.name.value, name
+                ));
+            }
+        }
+    }
+
+    fn fields(&mut self, name: &str, old: &[FieldDefinitionNode], new: &[FieldDefinitionNode]) {
+        for old_field in old {
+            let field_name = &old_field.name.value;
+            let coordinate = format!("{}.{}", name, field_name);
+            let new_field = match new.iter().find(|field| &field.name.value == field_name) {
+                Some(new_field) => new_field,
+                None => {
+                    self.breaking(format!("Field {} was removed from {}", field_name, name));
+                    continue;
+                }
+            };
+            self.description(&coordinate, &old_field.description, &new_field.description);
+            if old_field.field_type != new_field.field_type {
+                let message = format!(
+                    "{} changed type from {} to {}",
+                    coordinate,
+                    printer::print_type(&old_field.field_type),
+                    printer::print_type(&new_field.field_type)
+                );
+                if is_safe_output_change(&old_field.field_type, &new_field.field_type) {
+                    self.safe(message);
+                } else {
+                    self.breaking(message);
+                }
+            }
+            self.arguments(&coordinate, &old_field.arguments, &new_field.arguments);
+        }
+        for new_field in new {
+            if !old
+                .iter()
+                .any(|field| field.name.value == new_field.name.value)
+            {
+                self.safe(format!(
+                    "Field {} was added to {}",
+                    new_field.name.value, name
+                ));
+            }
+        }
+    }
+
+    /// Compares the arguments of a field or directive.
+    fn arguments(
+        &mut self,
+        coordinate: &str,
+        old: &Option<ArgumentDefinitions>,
+        new: &Option<ArgumentDefinitions>,
+    ) {
+        let old = old.as_deref().unwrap_or_default();
+        let new = new.as_deref().unwrap_or_default();
+        for old_argument in old {
+            let name = &old_argument.name.value;
+            match new.iter().find(|argument| &argument.name.value == name) {
+                Some(new_argument) => self.input_value(
+                    &format!("Argument {} of {}", name, coordinate),
+                    old_argument,
+                    new_argument,
+                ),
+                None => self.breaking(format!("Argument {} was removed from {}", name, coordinate)),
+            }
+        }
+        for new_argument in new {
+            let name = &new_argument.name.value;
+            if old.iter().any(|argument| &argument.name.value == name) {
+                continue;
+            }
+            if is_required(new_argument) {
+                self.breaking(format!(
+                    "Required argument {} was added to {}",
+                    name, coordinate
+                ));
+            } else {
+                self.dangerous(format!(
+                    "Optional argument {} was added to {}",
+                    name, coordinate
+                ));
+            }
+        }
+    }
+
+    fn input_fields(
+        &mut self,
+        name: &str,
+        old: &[InputValueDefinitionNode],
+        new: &[InputValueDefinitionNode],
+    ) {
+        for old_field in old {
+            let field_name = &old_field.name.value;
+            match new.iter().find(|field| &field.name.value == field_name) {
+                Some(new_field) => {
+                    self.input_value(&format!("{}.{}", name, field_name), old_field, new_field)
+                }
+                None => self.breaking(format!(
+                    "Input field {} was removed from {}",
+                    field_name, name
+                )),
+            }
+        }
+        for new_field in new {
+            let field_name = &new_field.name.value;
+            if old.iter().any(|field| &field.name.value == field_name) {
+                continue;
+            }
+            if is_required(new_field) {
+                self.breaking(format!(
+                    "Required input field {} was added to {}",
+                    field_name, name
+                ));
+            } else {
+                self.dangerous(format!(
+                    "Optional input field {} was added to {}",
+                    field_name, name
+                ));
+            }
+        }
+    }
+
+    /// Compares an argument or input field that is in both schemas.
+    fn input_value(
+        &mut self,
+        coordinate: &str,
+        old: &InputValueDefinitionNode,
+        new: &InputValueDefinitionNode,
+    ) {
+        self.description(coordinate, &old.description, &new.description);
+        if old.input_type != new.input_type {
+            let message = format!(
+                "{} changed type from {} to {}",
+                coordinate,
+                printer::print_type(&old.input_type),
+                printer::print_type(&new.input_type)
+            );
+            if is_safe_input_change(&old.input_type, &new.input_type) {
+                self.safe(message);
+            } else {
+                self.breaking(message);
+            }
+        }
+        match (&old.default_value, &new.default_value) {
+            (Some(old_default), Some(new_default)) if old_default != new_default => {
+                self.dangerous(format!(
+                    "{} changed default value from {} to {}",
+                    coordinate,
+                    printer::print_value(old_default),
+                    printer::print_value(new_default)
+                ))
+            }
+            (Some(old_default), None) => self.dangerous(format!(
+                "{} no longer defaults to {}",
+                coordinate,
+                printer::print_value(old_default)
+            )),
+            (None, Some(new_default)) => self.dangerous(format!(
+                "{} now defaults to {}",
+                coordinate,
+                printer::print_value(new_default)
+            )),
+            _ => (),
+        }
+    }
+
+    fn directive(&mut self, old: &DirectiveDefinitionNode, new: &DirectiveDefinitionNode) {
+        let coordinate = format!("@{}", old.name.value);
+        self.description(&coordinate, &old.description, &new.description);
+        if old.repeatable && !new.repeatable {
+            self.breaking(format!("{} is no longer repeatable", coordinate));
+        }
+        for location in &old.locations {
+            if !new.locations.contains(location) {
+                self.breaking(format!(
+                    "Location {} was removed from {}",
+                    printer::directive_location_name(location),
+                    coordinate
+                ));
+            }
+        }
+        self.arguments(&coordinate, &old.arguments, &new.arguments);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn changes(old: &str, new: &str) -> Vec<(Criticality, String)> {
+        diff(&parse(old).unwrap(), &parse(new).unwrap())
+            .into_iter()
+            .map(|change| (change.criticality, change.message))
+            .collect()
+    }
+
+    fn breaking(message: &str) -> (Criticality, String) {
+        (Criticality::Breaking, String::from(message))
+    }
+
+    fn dangerous(message: &str) -> (Criticality, String) {
+        (Criticality::Dangerous, String::from(message))
+    }
+
+    fn safe(message: &str) -> (Criticality, String) {
+        (Criticality::Safe, String::from(message))
+    }
+
+    #[test]
+    fn finds_no_changes_in_the_same_schema() {
+        let schema = r#""The root" type Query { user(id: ID! = 1): [User!] }
+type User { name: String }
+directive @auth(role: String) on FIELD_DEFINITION"#;
+        assert_eq!(changes(schema, schema), vec![]);
+    }
+
+    #[test]
+    fn classifies_type_changes() {
+        assert_eq!(
+            changes(
+                r#"type Query { user: User }
+type User { name: String }
+"A color" enum Color { RED GREEN }
+union Result = User | Query
+scalar Date
+input Filter { name: String }"#,
+                r#"type Query { user: User }
+type User { name: String }
+"The color of a user" enum Color { RED BLUE }
+union Result = User | Other
+type Other { id: ID }
+input Date { value: String }
+input Filter { name: String }"#
+            ),
+            vec![
+                safe("Description of Color changed"),
+                breaking("Value GREEN was removed from enum Color"),
+                dangerous("Value BLUE was added to enum Color"),
+                breaking("Member Query was removed from union Result"),
+                dangerous("Member Other was added to union Result"),
+                breaking("Type Date changed from a scalar to an input object"),
+                safe("Type Other was added"),
+            ]
+        );
+        assert_eq!(
+            changes(
+                "type Query { id: ID }\ntype User { id: ID }",
+                "type Query { id: ID }"
+            ),
+            vec![breaking("Type User was removed")]
+        );
+    }
+
+    #[test]
+    fn classifies_field_changes() {
+        assert_eq!(
+            changes(
+                r#"type Query implements Node { id: ID name: String friends: [Query] age: Int }
+interface Node { id: ID }"#,
+                r#"type Query implements Named {
+  id: ID!
+  name: [String]
+  friends: [Query!]!
+  "Years since birth"
+  email: String
+}
+interface Named { name: [String] }"#
+            ),
+            vec![
+                breaking("Query no longer implements Node"),
+                dangerous("Query now implements Named"),
+                safe("Query.id changed type from ID to ID!"),
+                breaking("Query.name changed type from String to [String]"),
+                safe("Query.friends changed type from [Query] to [Query!]!"),
+                breaking("Field age was removed from Query"),
+                safe("Field email was added to Query"),
+                breaking("Type Node was removed"),
+                safe("Type Named was added"),
+            ]
+        );
+    }
+
+    #[test]
+    fn classifies_argument_and_input_changes() {
+        assert_eq!(
+            changes(
+                r#"type Query { users(first: Int = 10, after: String!, filter: Filter): [ID] }
+input Filter { name: String, age: Int! }"#,
+                r#"type Query {
+  users(first: Int! = 20, after: String, filter: Filter!, sort: Int, by: String!): [ID]
+}
+input Filter { name: Int, age: Int, active: Boolean! }"#
+            ),
+            vec![
+                breaking("Argument first of Query.users changed type from Int to Int!"),
+                dangerous("Argument first of Query.users changed default value from 10 to 20"),
+                safe("Argument after of Query.users changed type from String! to String"),
+                breaking("Argument filter of Query.users changed type from Filter to Filter!"),
+                dangerous("Optional argument sort was added to Query.users"),
+                breaking("Required argument by was added to Query.users"),
+                breaking("Filter.name changed type from String to Int"),
+                safe("Filter.age changed type from Int! to Int"),
+                breaking("Required input field active was added to Filter"),
+            ]
+        );
+    }
+
+    #[test]
+    fn classifies_schema_and_directive_changes() {
+        assert_eq!(
+            changes(
+                r#"schema { query: Query mutation: Mutation }
+type Query { id: ID }
+type Mutation { id: ID }
+directive @auth(role: String) repeatable on FIELD_DEFINITION | OBJECT
+directive @old on FIELD"#,
+                r#"schema { query: Root }
+type Query { id: ID }
+type Root { id: ID }
+type Mutation { id: ID }
+directive @auth(role: String, scope: String!) on FIELD_DEFINITION
+directive @new on FIELD"#
+            ),
+            vec![
+                breaking("Root query type changed from Query to Root"),
+                breaking("Root mutation type Mutation was removed"),
+                safe("Type Root was added"),
+                breaking("@auth is no longer repeatable"),
+                breaking("Location OBJECT was removed from @auth"),
+                breaking("Required argument scope was added to @auth"),
+                breaking("Directive @old was removed"),
+                safe("Directive @new was added"),
+            ]
+        );
+    }
+}
@@ -1,15 +1,29 @@
 //! A parsed GraphQL [`Document`].
 //!
 //! [`Document`]: ../struct.Document.html
-use crate::nodes::DefinitionNode;
+use crate::error::ValidationError;
+use crate::nodes::enum_type_extension::EnumTypeExtensionNode;
+use crate::nodes::input_type_extension::InputTypeExtensionNode;
+use crate::nodes::interface_type_extension::InterfaceTypeExtensionNode;
+use crate::nodes::object_type_extension::ObjectTypeExtensionNode;
+use crate::nodes::scalar_type_extension::ScalarTypeExtensionNode;
+use crate::nodes::schema_extension::SchemaExtensionNode;
+use crate::nodes::union_type_extension::UnionTypeExtensionNode;
+use crate::nodes::*;
+use crate::token::Span;
+use crate::visitor::{walk_mut, VisitorMut};
 use log::debug;
+use std::sync::Arc;
 
 /// The Document is the root of a GraphQL schema and/or query. It contains a list of GraphQL
 /// definitions. These can be anything from types, enums, unions, etc. to a query.
 ///
 /// This struct will also provide validation methods and other ways to manipulate the GraphQL
 /// syntax tree.
-#[derive(Debug, PartialEq)]
+///
+/// With the `serde` feature enabled, documents can be serialised to and read from JSON shaped
+/// like the AST of graphql-js.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Document {
     /// A list of GraphQL definitions
     pub definitions: Vec<DefinitionNode>,
@@ -20,12 +34,125 @@ impl Document {
     pub fn new(definitions: Vec<DefinitionNode>) -> Document {
         Document { definitions }
     }
+
+    /// Whether the document only contains type system definitions and extensions, so it
+    /// describes a schema rather than operations to execute.
+    pub fn is_type_system(&self) -> bool {
+        !self.definitions.is_empty()
+            && self
+                .definitions
+                .iter()
+                .all(|definition| !matches!(definition, DefinitionNode::Executable(_)))
+    }
+
+    /// Merge type system documents into a single document, folding every `extend` definition
+    /// into the definition it extends. The fields, interfaces, directives, union members, enum
+    /// values and root operation types of an extension are added to its base definition, so
+    /// the merged document contains no extensions. The exception are extensions of the built-in
+    /// types, which have no definition to fold into until a [`Schema`] is built from the
+    /// document, and so are kept as they are.
+    ///
+    /// An extension of a missing type, of a type of another kind, or one that redefines
+    /// something its type already defines is reported, along with every other conflict.
+    ///
+    /// [`Schema`]: ../schema/struct.Schema.html
+    pub fn merge(documents: Vec<Document>) -> Result<Document, Vec<ValidationError>> {
+        crate::merge::merge(documents)
+    }
+
+    /// Applies a document of changes to a merged document, such as a schema that is updated
+    /// by clients. A type, directive or schema definition in `changes` replaces the one of the
+    /// same name in this document, and extensions in `changes` are folded in as
+    /// [`Document::merge`] does.
+    ///
+    /// [`Document::merge`]: #method.merge
+    pub fn update(self, changes: Document) -> Result<Document, Vec<ValidationError>> {
+        crate::merge::update(self, changes)
+    }
+
+    /// Forgets where every node was parsed from, for a document that is kept after its source
+    /// is gone. Errors found in the document then have no location, rather than one that points
+    /// into whatever source they are rendered with.
+    pub fn clear_locations(&mut self) {
+        walk_mut::document(&mut LocationClearer, self);
+    }
+}
+
+/// Replaces the span of every node with an ignored one.
+struct LocationClearer;
+
+/// Declares a hook per node type that clears the node's span.
+macro_rules! clear_spans {
+    ($($enter:ident $node:ty;)*) => {
+        impl VisitorMut for LocationClearer {
+            $(
+                fn $enter(&mut self, node: &mut $node) {
+                    node.span = Span::ignored();
+                }
+            )*
+
+            // Inner types shared with another tree are copied, so the walk can reach them
+            fn enter_type(&mut self, node: &mut TypeNode) {
+                if let TypeNode::NonNull(inner) = node {
+                    Arc::make_mut(inner);
+                }
+            }
+
+            fn enter_list_type(&mut self, node: &mut ListTypeNode) {
+                Arc::make_mut(&mut node.list_type);
+                node.span = Span::ignored();
+            }
+        }
+    };
+}
+
+clear_spans! {
+    enter_query_definition QueryDefinitionNode;
+    enter_mutation_definition MutationDefinitionNode;
+    enter_subscription_definition SubscriptionDefinitionNode;
+    enter_fragment_definition FragmentDefinitionNode;
+    enter_variable_definition VariableDefinitionNode;
+    enter_field FieldNode;
+    enter_fragment_spread_node FragmentSpreadNode;
+    enter_inline_fragment_spread InlineFragmentSpreadNode;
+    enter_argument Argument;
+    enter_directive DirectiveNode;
+    enter_variable VariableNode;
+    enter_int_value IntValueNode;
+    enter_float_value FloatValueNode;
+    enter_string_value StringValueNode;
+    enter_boolean_value BooleanValueNode;
+    enter_enum_value EnumValueNode;
+    enter_list_value ListValueNode;
+    enter_object_value ObjectValueNode;
+    enter_object_field ObjectFieldNode;
+    enter_named_type NamedTypeNode;
+    enter_name NameNode;
+    enter_schema_definition SchemaDefinitionNode;
+    enter_operation_type_definition OperationTypeDefinitionNode;
+    enter_directive_definition DirectiveDefinitionNode;
+    enter_scalar_type_definition ScalarTypeDefinitionNode;
+    enter_object_type_definition ObjectTypeDefinitionNode;
+    enter_interface_type_definition InterfaceTypeDefinitionNode;
+    enter_union_type_definition UnionTypeDefinitionNode;
+    enter_enum_type_definition EnumTypeDefinitionNode;
+    enter_input_type_definition InputTypeDefinitionNode;
+    enter_field_definition FieldDefinitionNode;
+    enter_input_value_definition InputValueDefinitionNode;
+    enter_enum_value_definition EnumValueDefinitionNode;
+    enter_schema_extension SchemaExtensionNode;
+    enter_scalar_type_extension ScalarTypeExtensionNode;
+    enter_object_type_extension ObjectTypeExtensionNode;
+    enter_interface_type_extension InterfaceTypeExtensionNode;
+    enter_union_type_extension UnionTypeExtensionNode;
+    enter_enum_type_extension EnumTypeExtensionNode;
+    enter_input_type_extension InputTypeExtensionNode;
 }
 
 use std::fmt;
 impl fmt::Display for Document {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Document<{} definitions>", self.definitions.len())
+        write!(f, "{}", crate::printer::print(self))
     }
 }
 
@@ -115,7 +242,7 @@ scalar Float
 A signed, 64-bit, fractional number.
 For more information see [f64 docs](https://doc.rust-lang.org/std/primitive.f64.html).
 """
-scalar Float
+scalar Double
 
 """TinyString
 A small string made up of 255 bytes.
@@ -152,6 +279,30 @@ Used as a unique identifier.
 """
 scalar ID
 
+"""skip
+Skips the field, fragment spread or inline fragment when the argument is true.
+"""
+directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+
+"""include
+Only includes the field, fragment spread or inline fragment when the argument is true.
+"""
+directive @include(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+
+"""deprecated
+Marks a field or enum value as no longer supported.
+"""
+directive @deprecated(reason: String = "No longer supported") on FIELD_DEFINITION | ENUM_VALUE
+
+"""specifiedBy
+Links a custom scalar to the specification of how it behaves.
+"""
+directive @specifiedBy(url: String!) on SCALAR
+
+"""cost
+Sets the weight of a field when measuring the cost of an operation. Fields weigh 1 by default.
+"""
+directive @cost(weight: Int!) on FIELD_DEFINITION
 
 """Schema
 The root of any interaction with the database.
@@ -184,6 +335,32 @@ schema {{
         ));
 
         debug!("Debugging: {:?}", doc);
-        doc.expect("Default schema is invalid")
+        let mut doc = doc.expect("Default schema is invalid");
+        doc.definitions.extend(
+            gql!(crate::introspection::TYPES)
+                .expect("Introspection types are invalid")
+                .definitions,
+        );
+        doc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse;
+    use crate::schema::Schema;
+
+    #[test]
+    fn clears_locations() {
+        let source = "type Query {\n  user: [User!]\n  name: Strin\n}";
+        let errors = Schema::new(vec![parse(source).unwrap()]).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| error.location.unwrap().line > 0));
+
+        let mut document = parse(source).unwrap();
+        document.clear_locations();
+        let errors = Schema::new(vec![document]).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert!(errors.iter().all(|error| error.location.unwrap().line == 0));
     }
 }
@@ -36,7 +36,7 @@
 //! ```
 //!
 
-use crate::token::Location;
+use crate::token::{Location, TokenKind};
 use std::fmt;
 
 fn format_location_message(message: &'static str, location: &Location) -> String {
@@ -72,6 +72,19 @@ fn format_expected_received_message(
     )
 }
 
+/// Describes the kinds of token that were expected, such as "a name or `{`".
+pub(crate) fn describe_kinds(kinds: &[TokenKind]) -> String {
+    match kinds {
+        [] => String::new(),
+        [kind] => kind.to_string(),
+        [first, second] => format!("{} or {}", first, second),
+        [rest @ .., last] => {
+            let rest: Vec<String> = rest.iter().map(TokenKind::to_string).collect();
+            format!("one of {} or {}", rest.join(", "), last)
+        }
+    }
+}
+
 /// Represents a symantic issue in the GraphQL string.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum LexError {
@@ -83,15 +96,21 @@ pub enum LexError {
     UnexpectedCharacter(Location),
     /// An issue occured while trying to turn the string value into some other type
     UnableToConvert(Location, &'static str),
+    /// A string contains an escape sequence that is not defined in the GraphQL spec
+    InvalidEscape(Location),
+    /// A single line string contains an unescaped line terminator
+    LineTerminatorInString(Location),
     /// The end of the file was encountered unexpectedly
     EOF,
 }
 
-const EOF_MESSAGE: &'static str = "Parse Error: Encountered End of File unexpectedly";
-const UNMATCHED_QUOTE_MESSAGE: &'static str = "Parse Error: Unmatched quote found on";
-const UNKNOWN_CHARACTER_MESSAGE: &'static str = "Parse Error: Unknown character found on";
-const UNEXPECTED_CHARACTER_MESSAGE: &'static str = "Parse Error: Unexpected character found on";
-const UNABLE_TO_CONVERT_MESSAGE: &'static str = "Parse Error: Unable to convert value at";
+const EOF_MESSAGE: &str = "Parse Error: Encountered End of File unexpectedly";
+const UNMATCHED_QUOTE_MESSAGE: &str = "Parse Error: Unmatched quote found on";
+const UNKNOWN_CHARACTER_MESSAGE: &str = "Parse Error: Unknown character found on";
+const UNEXPECTED_CHARACTER_MESSAGE: &str = "Parse Error: Unexpected character found on";
+const UNABLE_TO_CONVERT_MESSAGE: &str = "Parse Error: Unable to convert value at";
+const INVALID_ESCAPE_MESSAGE: &str = "Parse Error: Invalid escape sequence on";
+const LINE_TERMINATOR_IN_STRING_MESSAGE: &str = "Parse Error: Line terminator in string on";
 
 impl LexError {
     fn get_message(&self) -> String {
@@ -109,6 +128,12 @@ impl LexError {
             LexError::UnableToConvert(location, expected) => {
                 format_expected_value_message(UNABLE_TO_CONVERT_MESSAGE, location, expected)
             }
+            LexError::InvalidEscape(location) => {
+                format_location_message(INVALID_ESCAPE_MESSAGE, location)
+            }
+            LexError::LineTerminatorInString(location) => {
+                format_location_message(LINE_TERMINATOR_IN_STRING_MESSAGE, location)
+            }
         }
     }
 }
@@ -144,8 +169,8 @@ pub enum ParseError {
     /// The last token lexed was not the token that is defined
     /// in the GraphQL spec
     UnexpectedToken {
-        /// The token that was expected
-        expected: String,
+        /// The kinds of token that could have come next
+        expected: Vec<TokenKind>,
         /// The token received from the string
         received: String,
         /// The [`location`] of the unexpected token
@@ -171,17 +196,16 @@ pub enum ParseError {
     NotImplemented,
 }
 
-const NOT_IMPLEMENTED_MESSAGE: &'static str =
+const NOT_IMPLEMENTED_MESSAGE: &str =
     "Parse Error: One or more operations/types specified is not implemented";
-const BAD_VALUE_MESSAGE: &'static str =
+const BAD_VALUE_MESSAGE: &str =
     "Parse Error: Bad value received. Please check input and try again.";
-const DOCUMENT_EMPTY_MESSAGE: &'static str =
-    "Parse Error: Document is empty. Cannot parse an empty value";
-const ARGUMENT_EMPTY_MESSAGE: &'static str = "Parse Error: Argument empty on";
-const OBJECT_EMPTY_MESSAGE: &'static str = "Parse Error: Object empty on";
+const DOCUMENT_EMPTY_MESSAGE: &str = "Parse Error: Document is empty. Cannot parse an empty value";
+const ARGUMENT_EMPTY_MESSAGE: &str = "Parse Error: Argument empty on";
+const OBJECT_EMPTY_MESSAGE: &str = "Parse Error: Object empty on";
 
-const EXPECTED_TOKEN_MESSAGE: &'static str = "Parse Error: Unexpected token on";
-const EXPECTED_KEYWORD_MESSAGE: &'static str = "Parse Error: Unexpected keyword on";
+const EXPECTED_TOKEN_MESSAGE: &str = "Parse Error: Unexpected token on";
+const EXPECTED_KEYWORD_MESSAGE: &str = "Parse Error: Unexpected keyword on";
 
 impl ParseError {
     fn get_message(&self) -> String {
@@ -204,7 +228,7 @@ impl ParseError {
             } => format_expected_received_message(
                 EXPECTED_TOKEN_MESSAGE,
                 location,
-                expected,
+                &describe_kinds(expected),
                 received,
             ),
             ParseError::UnexpectedKeyword {
@@ -237,16 +261,24 @@ pub type ParseResult<T> = Result<T, ParseError>;
 /// # Example
 ///
 /// ```
-/// use syntax::parse;
-/// use syntax::document::Document;
+/// use syntax::error::ValidationError;
+/// use syntax::token::Location;
+///
+/// let error = ValidationError::new("Unknown type Foo").with_location(Location::new(12, 2, 7));
+/// assert_eq!(error.to_string(), "Validation Error: Unknown type Foo on line 2, column 7");
 /// ```
 #[derive(Debug)]
 pub struct ValidationError {
     /// A description of the logical error encountered while validating
     /// the GraphQL Document.
     pub message: String,
+    /// The [`location`] of the node that failed validation, when it is known.
+    /// [`location`]: ../token/struct.Location.html
+    pub location: Option<Location>,
 }
 
+const VALIDATION_ERROR_MESSAGE: &str = "Validation Error:";
+
 impl ValidationError {
     /// Returns a ValidationError with a message of the issue.
     ///
@@ -254,6 +286,26 @@ impl ValidationError {
     pub fn new(message: &str) -> ValidationError {
         ValidationError {
             message: String::from(message),
+            location: None,
+        }
+    }
+
+    /// Attaches the location of the offending node to the error.
+    pub fn with_location(mut self, location: Location) -> ValidationError {
+        self.location = Some(location);
+        self
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.location {
+            Some(location) => write!(
+                f,
+                "{} {} on line {}, column {}",
+                VALIDATION_ERROR_MESSAGE, self.message, location.line, location.column
+            ),
+            None => write!(f, "{} {}", VALIDATION_ERROR_MESSAGE, self.message),
         }
     }
 }
@@ -319,7 +371,7 @@ mod tests {
         let expected = Token::Name(Location::new(42, 4, 2), "val");
         let received = Token::Str(location, "Content of value");
         let error = ParseError::UnexpectedToken {
-            expected: expected.to_string(),
+            expected: vec![expected.kind(), TokenKind::OpenBrace],
             received: received.to_string(),
             location: received.location(),
         };
@@ -327,11 +379,7 @@ mod tests {
             error.to_string(),
             format!(
                 "{} line {}, column {}: Expected \"{}\", but found \"{}\"",
-                EXPECTED_TOKEN_MESSAGE,
-                location.line,
-                location.column,
-                expected.to_string(),
-                received.to_string()
+                EXPECTED_TOKEN_MESSAGE, location.line, location.column, "a name or `{`", received
             )
         );
     }
@@ -353,4 +401,22 @@ mod tests {
             )
         );
     }
+
+    #[test]
+    fn creates_validation_error_message() {
+        let error = ValidationError::new("Invalid Object Foo");
+        assert_eq!(
+            error.to_string(),
+            format!("{} Invalid Object Foo", VALIDATION_ERROR_MESSAGE)
+        );
+        let location = Location::new(42, 4, 2);
+        let error = error.with_location(location);
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "{} Invalid Object Foo on line {}, column {}",
+                VALIDATION_ERROR_MESSAGE, location.line, location.column
+            )
+        );
+    }
 }
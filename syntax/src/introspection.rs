@@ -0,0 +1,921 @@
+//! The [introspection](https://spec.graphql.org/October2021/#sec-Introspection) system, which
+//! lets clients query the types and directives of a [`Schema`].
+//!
+//! Every schema contains the introspection types (`__Schema`, `__Type`, `__Field`, ...). Every
+//! object, interface and union has a `__typename` field, and the query type has `__schema` and
+//! `__type(name:)` fields. [`execute`] answers operations that only select those fields.
+//!
+//! # Example
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use syntax::introspection;
+//! use syntax::schema::Schema;
+//!
+//! let schema = Schema::new(vec![syntax::parse("type Query { name: String }").unwrap()]).unwrap();
+//! let query = syntax::parse(r#"{ __type(name: "Query") { kind fields { name } } }"#).unwrap();
+//! let data = introspection::execute(&schema, &query, None, &HashMap::new())
+//!     .unwrap()
+//!     .unwrap();
+//! assert_eq!(
+//!     data.to_string(),
+//!     r#"{"__type":{"kind":"OBJECT","fields":[{"name":"name"}]}}"#
+//! );
+//! ```
+//!
+//! [`Schema`]: ../schema/struct.Schema.html
+//! [`execute`]: fn.execute.html
+
+use crate::document::Document;
+use crate::error::ValidationError;
+use crate::nodes::*;
+use crate::printer::{directive_location_name, print_type, print_value};
+use crate::schema::Schema;
+use std::collections::HashMap;
+use std::fmt;
+
+/// The introspection types, included in every schema through the default document.
+pub(crate) const TYPES: &str = r#""""__Schema
+A GraphQL schema: every type and directive it defines and the types each operation starts from.
+"""
+type __Schema {
+  description: String
+  types: [__Type!]!
+  queryType: __Type!
+  mutationType: __Type
+  subscriptionType: __Type
+  directives: [__Directive!]!
+}
+
+"""__Type
+Any type of the schema, including the list and non-null wrappers of another type. Which fields
+are set depends on the kind of the type.
+"""
+type __Type {
+  kind: __TypeKind!
+  name: String
+  description: String
+  fields(includeDeprecated: Boolean = false): [__Field!]
+  interfaces: [__Type!]
+  possibleTypes: [__Type!]
+  enumValues(includeDeprecated: Boolean = false): [__EnumValue!]
+  inputFields: [__InputValue!]
+  ofType: __Type
+  specifiedByURL: String
+}
+
+"""__TypeKind
+The kinds of types a __Type can describe.
+"""
+enum __TypeKind {
+  SCALAR
+  OBJECT
+  INTERFACE
+  UNION
+  ENUM
+  INPUT_OBJECT
+  LIST
+  NON_NULL
+}
+
+"""__Field
+A field of an object or interface type.
+"""
+type __Field {
+  name: String!
+  description: String
+  args: [__InputValue!]!
+  type: __Type!
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+"""__InputValue
+An argument or input field, with its default value printed as GraphQL.
+"""
+type __InputValue {
+  name: String!
+  description: String
+  type: __Type!
+  defaultValue: String
+}
+
+"""__EnumValue
+A value of an enum type.
+"""
+type __EnumValue {
+  name: String!
+  description: String
+  isDeprecated: Boolean!
+  deprecationReason: String
+}
+
+"""__Directive
+A directive of the schema, with the locations it can be used on.
+"""
+type __Directive {
+  name: String!
+  description: String
+  locations: [__DirectiveLocation!]!
+  args: [__InputValue!]!
+  isRepeatable: Boolean!
+}
+
+"""__DirectiveLocation
+The locations a directive can be used on.
+"""
+enum __DirectiveLocation {
+  QUERY
+  MUTATION
+  SUBSCRIPTION
+  FIELD
+  FRAGMENT_DEFINITION
+  FRAGMENT_SPREAD
+  INLINE_FRAGMENT
+  VARIABLE_DEFINITION
+  SCHEMA
+  SCALAR
+  OBJECT
+  FIELD_DEFINITION
+  ARGUMENT_DEFINITION
+  INTERFACE
+  UNION
+  ENUM
+  ENUM_VALUE
+  INPUT_OBJECT
+  INPUT_FIELD_DEFINITION
+}
+"#;
+
+/// The fields every schema answers without defining them.
+const META_FIELDS: &str = r#"type __Meta {
+  __typename: String!
+  __schema: __Schema!
+  __type(name: String!): __Type
+}"#;
+
+/// Whether a type is one of the built-in introspection types, which are allowed to use the
+/// reserved `__` prefix.
+pub(crate) fn is_introspection_type(name: &str) -> bool {
+    matches!(
+        name,
+        "__Schema"
+            | "__Type"
+            | "__TypeKind"
+            | "__Field"
+            | "__InputValue"
+            | "__EnumValue"
+            | "__Directive"
+            | "__DirectiveLocation"
+    )
+}
+
+/// The definitions of `__typename`, `__schema` and `__type`.
+pub(crate) fn meta_fields() -> Vec<FieldDefinitionNode> {
+    let document = crate::parse(META_FIELDS).expect("Meta fields are invalid");
+    match document.definitions.into_iter().next() {
+        Some(DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+            TypeDefinitionNode::Object(object),
+        ))) => object.fields,
+        _ => unreachable!("Meta fields are defined by an object type"),
+    }
+}
+
+/// The result of an introspection query, which prints as JSON.
+#[derive(Debug, PartialEq)]
+pub enum Value {
+    /// A missing value
+    Null,
+    /// A Boolean
+    Boolean(bool),
+    /// A string, also used for enum values
+    String(String),
+    /// A list of values
+    List(Vec<Value>),
+    /// The fields of an object, in the order they were selected
+    Object(Vec<(String, Value)>),
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, string: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in string.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Null => f.write_str("null"),
+            Value::Boolean(boolean) => write!(f, "{}", boolean),
+            Value::String(string) => write_string(f, string),
+            Value::List(values) => {
+                f.write_str("[")?;
+                for (index, value) in values.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                f.write_str("]")
+            }
+            Value::Object(fields) => {
+                f.write_str("{")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index > 0 {
+                        f.write_str(",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+/// Answers an operation that only selects introspection fields, returning its data.
+///
+/// The operation is picked by `operation_name`, or is the only operation of the document when
+/// no name is given. The document is expected to be valid for the schema. `variables` holds the
+/// values sent with the document, by name, and a variable without one takes its default value.
+/// Returns None when no single operation is picked, or when the operation selects fields that
+/// are not answered by introspection, and an error when a variable of a non-null type has no
+/// value.
+pub fn execute(
+    schema: &Schema,
+    document: &Document,
+    operation_name: Option<&str>,
+    variables: &HashMap<String, ValueNode>,
+) -> Result<Option<Value>, ValidationError> {
+    let mut operations = Vec::new();
+    let mut fragments = HashMap::new();
+    for definition in &document.definitions {
+        match definition {
+            DefinitionNode::Executable(ExecutableDefinitionNode::Operation(operation)) => {
+                operations.push(operation)
+            }
+            DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(fragment)) => {
+                fragments.insert(fragment.name.value.as_str(), fragment);
+            }
+            _ => (),
+        }
+    }
+    let operation = match operation_name {
+        Some(name) => operations.into_iter().find(|operation| {
+            operation.name().map(|operation| operation.value.as_str()) == Some(name)
+        }),
+        None if operations.len() == 1 => Some(operations[0]),
+        None => None,
+    };
+    let operation = match operation {
+        Some(operation) => operation,
+        None => return Ok(None),
+    };
+    let root = match operation {
+        OperationTypeNode::Query(_) => schema.query_type(),
+        OperationTypeNode::Mutation(_) => schema.mutation_type(),
+        OperationTypeNode::Subscription(_) => schema.subscription_type(),
+    };
+    let root = match root {
+        Some(root) => root,
+        None => return Ok(None),
+    };
+
+    let mut values = HashMap::new();
+    for definition in operation.variables() {
+        let name = definition.variable.name.value.as_str();
+        let value = variables.get(name).or(definition.default_value.as_ref());
+        let missing = matches!(value, None | Some(ValueNode::Null));
+        if missing && matches!(definition.variable_type, TypeNode::NonNull(_)) {
+            return Err(ValidationError::new(
+                format!(
+                    "Variable ${} of required type {} was not provided",
+                    name,
+                    print_type(&definition.variable_type)
+                )
+                .as_str(),
+            )
+            .with_location(definition.span.start));
+        }
+        values.insert(name, value);
+    }
+
+    let executor = Executor {
+        schema,
+        fragments,
+        variables: values,
+    };
+    let selections: Vec<&Selection> = operation.selections().iter().collect();
+    let mut fields = Vec::new();
+    executor.collect_fields(&root.name.value, &selections, &mut fields, &mut Vec::new());
+    let only_introspection = fields.iter().all(|(_, fields)| {
+        matches!(
+            fields[0].name.value.as_str(),
+            "__typename" | "__schema" | "__type"
+        )
+    });
+    if only_introspection {
+        Ok(Some(executor.execute_selections(
+            &Object::Root(&root.name.value),
+            &selections,
+        )))
+    } else {
+        Ok(None)
+    }
+}
+
+/// The objects of the introspection system.
+enum Object<'a> {
+    /// The root type of the operation
+    Root(&'a str),
+    Schema,
+    Type(&'a TypeDefinitionNode),
+    /// A list or non-null type
+    WrappingType(&'a TypeNode),
+    Field(&'a FieldDefinitionNode),
+    InputValue(&'a InputValueDefinitionNode),
+    EnumValue(&'a EnumValueDefinitionNode),
+    Directive(&'a DirectiveDefinitionNode),
+}
+
+impl<'a> Object<'a> {
+    fn type_name(&self) -> &'a str {
+        match self {
+            Object::Root(name) => name,
+            Object::Schema => "__Schema",
+            Object::Type(_) | Object::WrappingType(_) => "__Type",
+            Object::Field(_) => "__Field",
+            Object::InputValue(_) => "__InputValue",
+            Object::EnumValue(_) => "__EnumValue",
+            Object::Directive(_) => "__Directive",
+        }
+    }
+}
+
+/// A field's value before its selections are applied.
+enum Resolved<'a> {
+    Leaf(Value),
+    Object(Object<'a>),
+    List(Vec<Resolved<'a>>),
+}
+
+fn string(value: &str) -> Resolved<'_> {
+    Resolved::Leaf(Value::String(String::from(value)))
+}
+
+fn description(description: &Description) -> Resolved<'_> {
+    match description {
+        Some(description) => string(&description.value),
+        None => Resolved::Leaf(Value::Null),
+    }
+}
+
+/// The reason given by a `@deprecated` directive, if there is one.
+fn deprecation_reason(directives: &Option<Directives>) -> Option<&str> {
+    let directive = directives
+        .iter()
+        .flatten()
+        .find(|directive| directive.name.value == "deprecated")?;
+    let reason = directive
+        .arguments
+        .iter()
+        .flatten()
+        .find_map(|argument| match &argument.value {
+            ValueNode::Str(reason) if argument.name.value == "reason" => {
+                Some(reason.value.as_str())
+            }
+            _ => None,
+        });
+    Some(reason.unwrap_or("No longer supported"))
+}
+
+fn type_kind(type_definition: &TypeDefinitionNode) -> &'static str {
+    match type_definition {
+        TypeDefinitionNode::Scalar(_) => "SCALAR",
+        TypeDefinitionNode::Object(_) => "OBJECT",
+        TypeDefinitionNode::Interface(_) => "INTERFACE",
+        TypeDefinitionNode::Union(_) => "UNION",
+        TypeDefinitionNode::Enum(_) => "ENUM",
+        TypeDefinitionNode::Input(_) => "INPUT_OBJECT",
+    }
+}
+
+struct Executor<'a> {
+    schema: &'a Schema,
+    fragments: HashMap<&'a str, &'a FragmentDefinitionNode>,
+    variables: HashMap<&'a str, Option<&'a ValueNode>>,
+}
+
+impl<'a> Executor<'a> {
+    /// Looks up a literal argument, or the value of the variable it is given.
+    fn argument(&self, field: &'a FieldNode, name: &str) -> Option<&'a ValueNode> {
+        let value = &field
+            .arguments
+            .iter()
+            .flatten()
+            .find(|argument| argument.name.value == name)?
+            .value;
+        match value {
+            ValueNode::Variable(variable) => *self.variables.get(variable.name.value.as_str())?,
+            value => Some(value),
+        }
+    }
+
+    fn include_deprecated(&self, field: &'a FieldNode) -> bool {
+        matches!(
+            self.argument(field, "includeDeprecated"),
+            Some(ValueNode::Bool(boolean)) if boolean.value
+        )
+    }
+
+    /// Applies `@skip` and `@include`.
+    fn is_included(&self, directives: &'a Option<Directives>) -> bool {
+        directives.iter().flatten().all(|directive| {
+            let condition = directive
+                .arguments
+                .iter()
+                .flatten()
+                .find(|argument| argument.name.value == "if")
+                .and_then(|argument| match &argument.value {
+                    ValueNode::Variable(variable) => self
+                        .variables
+                        .get(variable.name.value.as_str())
+                        .copied()
+                        .flatten(),
+                    value => Some(value),
+                });
+            let condition = matches!(condition, Some(ValueNode::Bool(boolean)) if boolean.value);
+            match directive.name.value.as_str() {
+                "skip" => !condition,
+                "include" => condition,
+                _ => true,
+            }
+        })
+    }
+
+    fn applies_to(&self, type_condition: &str, type_name: &str) -> bool {
+        type_condition == type_name || self.schema.is_possible_type(type_condition, type_name)
+    }
+
+    /// Groups the selected fields by response key, in the order they are first selected.
+    fn collect_fields(
+        &self,
+        type_name: &str,
+        selections: &[&'a Selection],
+        fields: &mut Vec<(&'a str, Vec<&'a FieldNode>)>,
+        visited_fragments: &mut Vec<&'a str>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    if !self.is_included(&field.directives) {
+                        continue;
+                    }
+                    let key = field.alias.as_ref().unwrap_or(&field.name).value.as_str();
+                    match fields.iter_mut().find(|(existing, _)| *existing == key) {
+                        Some((_, fields)) => fields.push(field),
+                        None => fields.push((key, vec![field])),
+                    }
+                }
+                Selection::Fragment(FragmentSpread::Node(spread)) => {
+                    let name = spread.name.value.as_str();
+                    if !self.is_included(&spread.directives) || visited_fragments.contains(&name) {
+                        continue;
+                    }
+                    visited_fragments.push(name);
+                    if let Some(fragment) = self.fragments.get(name) {
+                        if self.applies_to(&fragment.node_type.name.value, type_name) {
+                            let selections: Vec<&Selection> = fragment.selections.iter().collect();
+                            self.collect_fields(type_name, &selections, fields, visited_fragments);
+                        }
+                    }
+                }
+                Selection::Fragment(FragmentSpread::Inline(inline)) => {
+                    let applies = inline.node_type.as_ref().is_none_or(|named_type| {
+                        self.applies_to(&named_type.name.value, type_name)
+                    });
+                    if self.is_included(&inline.directives) && applies {
+                        let selections: Vec<&Selection> = inline.selections.iter().collect();
+                        self.collect_fields(type_name, &selections, fields, visited_fragments);
+                    }
+                }
+            }
+        }
+    }
+
+    fn execute_selections(&self, object: &Object<'a>, selections: &[&'a Selection]) -> Value {
+        let mut fields = Vec::new();
+        self.collect_fields(object.type_name(), selections, &mut fields, &mut Vec::new());
+        Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, fields)| {
+                    let value = match self.resolve(object, fields[0]) {
+                        Some(resolved) => self.complete(resolved, &fields),
+                        None => Value::Null,
+                    };
+                    (String::from(key), value)
+                })
+                .collect(),
+        )
+    }
+
+    /// Applies the selections of every field with the same response key to a resolved value.
+    fn complete(&self, resolved: Resolved<'a>, fields: &[&'a FieldNode]) -> Value {
+        match resolved {
+            Resolved::Leaf(value) => value,
+            Resolved::Object(object) => {
+                let selections: Vec<&Selection> = fields
+                    .iter()
+                    .flat_map(|field| field.selections.iter().flatten())
+                    .collect();
+                self.execute_selections(&object, &selections)
+            }
+            Resolved::List(items) => Value::List(
+                items
+                    .into_iter()
+                    .map(|item| self.complete(item, fields))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn type_of(&self, type_node: &'a TypeNode) -> Option<Resolved<'a>> {
+        match type_node {
+            TypeNode::Named(named_type) => self
+                .schema
+                .get_type(&named_type.name.value)
+                .map(|type_definition| Resolved::Object(Object::Type(type_definition))),
+            wrapping_type => Some(Resolved::Object(Object::WrappingType(wrapping_type))),
+        }
+    }
+
+    fn types_named(&self, named_types: &'a Option<Vec<NamedTypeNode>>) -> Resolved<'a> {
+        Resolved::List(
+            named_types
+                .iter()
+                .flatten()
+                .filter_map(|named_type| self.schema.get_type(&named_type.name.value))
+                .map(|type_definition| Resolved::Object(Object::Type(type_definition)))
+                .collect(),
+        )
+    }
+
+    fn input_values(arguments: &'a [InputValueDefinitionNode]) -> Resolved<'a> {
+        Resolved::List(
+            arguments
+                .iter()
+                .map(|argument| Resolved::Object(Object::InputValue(argument)))
+                .collect(),
+        )
+    }
+
+    /// Resolves a field of an introspection object. None stands for null.
+    fn resolve(&self, object: &Object<'a>, field: &'a FieldNode) -> Option<Resolved<'a>> {
+        let name = field.name.value.as_str();
+        if name == "__typename" {
+            return Some(string(object.type_name()));
+        }
+        match object {
+            Object::Root(_) => match name {
+                "__schema" => Some(Resolved::Object(Object::Schema)),
+                "__type" => match self.argument(field, "name")? {
+                    ValueNode::Str(type_name) => self
+                        .schema
+                        .get_type(&type_name.value)
+                        .map(|type_definition| Resolved::Object(Object::Type(type_definition))),
+                    _ => None,
+                },
+                _ => None,
+            },
+            Object::Schema => {
+                let root = |root: Option<&'a ObjectTypeDefinitionNode>| {
+                    self.schema
+                        .get_type(&root?.name.value)
+                        .map(|root| Resolved::Object(Object::Type(root)))
+                };
+                match name {
+                    "types" => Some(Resolved::List(
+                        self.schema
+                            .types()
+                            .map(|type_definition| Resolved::Object(Object::Type(type_definition)))
+                            .collect(),
+                    )),
+                    "queryType" => root(self.schema.query_type()),
+                    "mutationType" => root(self.schema.mutation_type()),
+                    "subscriptionType" => root(self.schema.subscription_type()),
+                    "directives" => Some(Resolved::List(
+                        self.schema
+                            .directives()
+                            .map(|directive| Resolved::Object(Object::Directive(directive)))
+                            .collect(),
+                    )),
+                    _ => None,
+                }
+            }
+            Object::Type(type_definition) => self.resolve_type(type_definition, field),
+            Object::WrappingType(type_node) => match (name, type_node) {
+                ("kind", TypeNode::NonNull(_)) => Some(string("NON_NULL")),
+                ("kind", _) => Some(string("LIST")),
+                ("ofType", TypeNode::NonNull(inner)) => self.type_of(inner),
+                ("ofType", TypeNode::List(list)) => self.type_of(&list.list_type),
+                _ => None,
+            },
+            Object::Field(definition) => match name {
+                "name" => Some(string(&definition.name.value)),
+                "description" => Some(description(&definition.description)),
+                "args" => Some(Self::input_values(
+                    definition.arguments.as_deref().unwrap_or(&[]),
+                )),
+                "type" => self.type_of(&definition.field_type),
+                "isDeprecated" => Some(Resolved::Leaf(Value::Boolean(
+                    deprecation_reason(&definition.directives).is_some(),
+                ))),
+                "deprecationReason" => deprecation_reason(&definition.directives).map(string),
+                _ => None,
+            },
+            Object::InputValue(definition) => match name {
+                "name" => Some(string(&definition.name.value)),
+                "description" => Some(description(&definition.description)),
+                "type" => self.type_of(&definition.input_type),
+                "defaultValue" => definition
+                    .default_value
+                    .as_ref()
+                    .map(|value| Resolved::Leaf(Value::String(print_value(value)))),
+                _ => None,
+            },
+            Object::EnumValue(definition) => match name {
+                "name" => Some(string(&definition.name.value)),
+                "description" => Some(description(&definition.description)),
+                "isDeprecated" => Some(Resolved::Leaf(Value::Boolean(
+                    deprecation_reason(&definition.directives).is_some(),
+                ))),
+                "deprecationReason" => deprecation_reason(&definition.directives).map(string),
+                _ => None,
+            },
+            Object::Directive(definition) => match name {
+                "name" => Some(string(&definition.name.value)),
+                "description" => Some(description(&definition.description)),
+                "locations" => Some(Resolved::List(
+                    definition
+                        .locations
+                        .iter()
+                        .map(|location| string(directive_location_name(location)))
+                        .collect(),
+                )),
+                "args" => Some(Self::input_values(
+                    definition.arguments.as_deref().unwrap_or(&[]),
+                )),
+                "isRepeatable" => Some(Resolved::Leaf(Value::Boolean(definition.repeatable))),
+                _ => None,
+            },
+        }
+    }
+
+    fn resolve_type(
+        &self,
+        type_definition: &'a TypeDefinitionNode,
+        field: &'a FieldNode,
+    ) -> Option<Resolved<'a>> {
+        match (field.name.value.as_str(), type_definition) {
+            ("kind", type_definition) => Some(string(type_kind(type_definition))),
+            ("name", type_definition) => Some(string(&type_definition.name().value)),
+            ("description", TypeDefinitionNode::Scalar(scalar)) => {
+                Some(description(&scalar.description))
+            }
+            ("description", TypeDefinitionNode::Object(object)) => {
+                Some(description(&object.description))
+            }
+            ("description", TypeDefinitionNode::Interface(interface)) => {
+                Some(description(&interface.description))
+            }
+            ("description", TypeDefinitionNode::Union(union)) => {
+                Some(description(&union.description))
+            }
+            ("description", TypeDefinitionNode::Enum(enum_type)) => {
+                Some(description(&enum_type.description))
+            }
+            ("description", TypeDefinitionNode::Input(input)) => {
+                Some(description(&input.description))
+            }
+            ("fields", TypeDefinitionNode::Object(_))
+            | ("fields", TypeDefinitionNode::Interface(_)) => {
+                let include_deprecated = self.include_deprecated(field);
+                Some(Resolved::List(
+                    self.schema
+                        .fields_of(&type_definition.name().value)?
+                        .iter()
+                        .filter(|definition| {
+                            include_deprecated
+                                || deprecation_reason(&definition.directives).is_none()
+                        })
+                        .map(|definition| Resolved::Object(Object::Field(definition)))
+                        .collect(),
+                ))
+            }
+            ("interfaces", TypeDefinitionNode::Object(object)) => {
+                Some(self.types_named(&object.interfaces))
+            }
+            ("interfaces", TypeDefinitionNode::Interface(interface)) => {
+                Some(self.types_named(&interface.interfaces))
+            }
+            ("possibleTypes", TypeDefinitionNode::Interface(_))
+            | ("possibleTypes", TypeDefinitionNode::Union(_)) => Some(Resolved::List(
+                self.schema
+                    .possible_types(&type_definition.name().value)
+                    .iter()
+                    .filter_map(|object| self.schema.get_type(&object.name.value))
+                    .map(|object| Resolved::Object(Object::Type(object)))
+                    .collect(),
+            )),
+            ("enumValues", TypeDefinitionNode::Enum(enum_type)) => {
+                let include_deprecated = self.include_deprecated(field);
+                Some(Resolved::List(
+                    enum_type
+                        .values
+                        .iter()
+                        .filter(|value| {
+                            include_deprecated || deprecation_reason(&value.directives).is_none()
+                        })
+                        .map(|value| Resolved::Object(Object::EnumValue(value)))
+                        .collect(),
+                ))
+            }
+            ("inputFields", TypeDefinitionNode::Input(input)) => {
+                Some(Self::input_values(&input.fields))
+            }
+            ("specifiedByURL", TypeDefinitionNode::Scalar(scalar)) => scalar
+                .directives
+                .iter()
+                .flatten()
+                .find(|directive| directive.name.value == "specifiedBy")?
+                .arguments
+                .iter()
+                .flatten()
+                .find_map(|argument| match &argument.value {
+                    ValueNode::Str(url) if argument.name.value == "url" => Some(string(&url.value)),
+                    _ => None,
+                }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use crate::token::Location;
+
+    const SCHEMA: &str = r#"type Query {
+  hero: Character
+  search(text: String!, first: Int = 10): [Result!]!
+}
+"""A character of the saga"""
+interface Character {
+  name: String
+  nickname: String @deprecated(reason: "Use name")
+}
+type Human implements Character {
+  name: String
+  nickname: String @deprecated(reason: "Use name")
+}
+union Result = Human
+enum Side { LIGHT DARK GREY @deprecated }
+scalar Url @specifiedBy(url: "https://url.spec.whatwg.org")"#;
+
+    fn introspect(query: &str) -> Option<String> {
+        let schema = Schema::new(vec![parse(SCHEMA).unwrap()]).unwrap();
+        let document = parse(query).unwrap();
+        assert!(schema.validate_document(&document).is_ok());
+        execute(&schema, &document, None, &HashMap::new())
+            .unwrap()
+            .map(|data| data.to_string())
+    }
+
+    #[test]
+    fn answers_type_queries() {
+        assert_eq!(
+            introspect(
+                r#"{
+  character: __type(name: "Character") {
+    kind name description
+    fields(includeDeprecated: true) { name isDeprecated deprecationReason }
+    possibleTypes { name }
+  }
+  side: __type(name: "Side") { enumValues { name } }
+  url: __type(name: "Url") { specifiedByURL }
+  missing: __type(name: "Missing") { name }
+}"#
+            ),
+            Some(String::from(concat!(
+                r#"{"character":{"kind":"INTERFACE","name":"Character","#,
+                r#""description":"A character of the saga","#,
+                r#""fields":[{"name":"name","isDeprecated":false,"deprecationReason":null},"#,
+                r#"{"name":"nickname","isDeprecated":true,"deprecationReason":"Use name"}],"#,
+                r#""possibleTypes":[{"name":"Human"}]},"side":{"enumValues":[{"name":"LIGHT"},"#,
+                r#"{"name":"DARK"}]},"url":{"specifiedByURL":"https://url.spec.whatwg.org"},"#,
+                r#""missing":null}"#,
+            )))
+        );
+    }
+
+    #[test]
+    fn answers_schema_queries_with_fragments() {
+        assert_eq!(
+            introspect(
+                r#"query IntrospectionQuery($withArgs: Boolean = true) {
+  __typename
+  __schema {
+    queryType { name }
+    mutationType { name }
+    query: queryType {
+      fields {
+        name
+        args @include(if: $withArgs) { ...InputValue }
+        type { ...TypeRef }
+      }
+    }
+  }
+}
+fragment InputValue on __InputValue { name defaultValue type { ...TypeRef } }
+fragment TypeRef on __Type { kind name ofType { kind name ofType { kind name } } }"#
+            ),
+            Some(String::from(concat!(
+                r#"{"__typename":"Query","__schema":{"queryType":{"name":"Query"},"#,
+                r#""mutationType":null,"query":{"fields":[{"name":"hero","args":[],"#,
+                r#""type":{"kind":"INTERFACE","name":"Character","ofType":null}},"#,
+                r#"{"name":"search","args":[{"name":"text","defaultValue":null,"#,
+                r#""type":{"kind":"NON_NULL","name":null,"ofType":{"kind":"SCALAR","#,
+                r#""name":"String","ofType":null}}},{"name":"first","defaultValue":"10","#,
+                r#""type":{"kind":"SCALAR","name":"Int","ofType":null}}],"#,
+                r#""type":{"kind":"NON_NULL","#,
+                r#""name":null,"ofType":{"kind":"LIST","name":null,"ofType":{"kind":"NON_NULL","#,
+                r#""name":null}}}}]}}}"#,
+            )))
+        );
+    }
+
+    #[test]
+    fn uses_variable_values() {
+        let schema = Schema::new(vec![parse(SCHEMA).unwrap()]).unwrap();
+        let document = parse("query($name: String!) { __type(name: $name) { name } }").unwrap();
+        let mut variables = HashMap::new();
+        variables.insert(
+            String::from("name"),
+            ValueNode::Str(StringValueNode::from("Human", false)),
+        );
+        assert_eq!(
+            execute(&schema, &document, None, &variables)
+                .unwrap()
+                .map(|data| data.to_string()),
+            Some(String::from(r#"{"__type":{"name":"Human"}}"#))
+        );
+
+        let error = execute(&schema, &document, None, &HashMap::new()).unwrap_err();
+        assert_eq!(
+            error.message,
+            "Variable $name of required type String! was not provided"
+        );
+        assert_eq!(error.location, Some(Location::new(6, 1, 7)));
+    }
+
+    #[test]
+    fn lists_introspection_types_and_directives() {
+        let schema = Schema::new(vec![parse("type Query { name: String }").unwrap()]).unwrap();
+        let document =
+            parse("{ __schema { types { name } directives { name locations } } }").unwrap();
+        let data = execute(&schema, &document, None, &HashMap::new())
+            .unwrap()
+            .unwrap()
+            .to_string();
+        assert!(data.contains(r#"{"name":"__Schema"}"#));
+        assert!(data.contains(r#"{"name":"__DirectiveLocation"}"#));
+        assert!(data.contains(
+            r#"{"name":"skip","locations":["FIELD","FRAGMENT_SPREAD","INLINE_FRAGMENT"]}"#
+        ));
+    }
+
+    #[test]
+    fn leaves_other_fields_to_the_database() {
+        assert_eq!(introspect("{ __typename hero { name } }"), None);
+        assert_eq!(
+            Value::Object(vec![(
+                String::from("text"),
+                Value::String(String::from("\"quoted\"\n"))
+            )])
+            .to_string(),
+            r#"{"text":"\"quoted\"\n"}"#
+        );
+    }
+}
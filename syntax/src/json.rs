@@ -0,0 +1,1516 @@
+//! Serialises documents to JSON shaped like the AST of graphql-js, and reads them back.
+//!
+//! Every node is an object with a `kind`, such as `ObjectTypeDefinition` or `Field`, and the
+//! fields graphql-js gives that kind of node. Lists are always present, even when empty, while
+//! missing optional nodes are left out. The span a node was parsed from is kept under `loc`,
+//! with the offset, line and column of its start and end.
+//!
+//! The nodes are converted to and from an intermediate [`Node`] rather than deriving the
+//! traits on the syntax tree itself, because graphql-js nests nodes differently, for example
+//! wrapping selections in a `SelectionSet` and folding queries, mutations and subscriptions
+//! into a single `OperationDefinition`.
+
+use crate::document::Document;
+use crate::nodes::enum_type_extension::EnumTypeExtensionNode;
+use crate::nodes::input_type_extension::InputTypeExtensionNode;
+use crate::nodes::interface_type_extension::InterfaceTypeExtensionNode;
+use crate::nodes::object_type_extension::ObjectTypeExtensionNode;
+use crate::nodes::scalar_type_extension::ScalarTypeExtensionNode;
+use crate::nodes::schema_extension::SchemaExtensionNode;
+use crate::nodes::union_type_extension::UnionTypeExtensionNode;
+use crate::nodes::*;
+use crate::token::{Location, Span, Token};
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::convert::TryFrom;
+use std::sync::Arc;
+
+type Result<T> = std::result::Result<T, String>;
+
+impl Serialize for Document {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        document(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Document {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        into_document(Node::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct Node {
+    #[serde(flatten)]
+    kind: Kind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    loc: Option<Span>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OperationKind {
+    Query,
+    Mutation,
+    Subscription,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all_fields = "camelCase")]
+enum Kind {
+    Document {
+        definitions: Vec<Node>,
+    },
+    Name {
+        value: String,
+    },
+
+    // Executable definitions
+    OperationDefinition {
+        operation: OperationKind,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<Box<Node>>,
+        #[serde(default)]
+        variable_definitions: Vec<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        selection_set: Box<Node>,
+    },
+    VariableDefinition {
+        variable: Box<Node>,
+        #[serde(rename = "type")]
+        variable_type: Box<Node>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default_value: Option<Box<Node>>,
+    },
+    SelectionSet {
+        selections: Vec<Node>,
+    },
+    Field {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        alias: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        arguments: Vec<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        selection_set: Option<Box<Node>>,
+    },
+    Argument {
+        name: Box<Node>,
+        value: Box<Node>,
+    },
+    FragmentSpread {
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+    },
+    InlineFragment {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        type_condition: Option<Box<Node>>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        selection_set: Box<Node>,
+    },
+    FragmentDefinition {
+        name: Box<Node>,
+        type_condition: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        selection_set: Box<Node>,
+    },
+
+    // Values
+    Variable {
+        name: Box<Node>,
+    },
+    IntValue {
+        value: String,
+    },
+    FloatValue {
+        value: String,
+    },
+    StringValue {
+        value: String,
+        #[serde(default)]
+        block: bool,
+    },
+    BooleanValue {
+        value: bool,
+    },
+    NullValue,
+    EnumValue {
+        value: String,
+    },
+    ListValue {
+        values: Vec<Node>,
+    },
+    ObjectValue {
+        fields: Vec<Node>,
+    },
+    ObjectField {
+        name: Box<Node>,
+        value: Box<Node>,
+    },
+    Directive {
+        name: Box<Node>,
+        #[serde(default)]
+        arguments: Vec<Node>,
+    },
+
+    // Types
+    NamedType {
+        name: Box<Node>,
+    },
+    ListType {
+        #[serde(rename = "type")]
+        list_type: Box<Node>,
+    },
+    NonNullType {
+        #[serde(rename = "type")]
+        non_null_type: Box<Node>,
+    },
+
+    // Type system definitions
+    SchemaDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        operation_types: Vec<Node>,
+    },
+    OperationTypeDefinition {
+        operation: OperationKind,
+        #[serde(rename = "type")]
+        node_type: Box<Node>,
+    },
+    ScalarTypeDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+    },
+    ObjectTypeDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        interfaces: Vec<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        fields: Vec<Node>,
+    },
+    FieldDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        arguments: Vec<Node>,
+        #[serde(rename = "type")]
+        field_type: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+    },
+    InputValueDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(rename = "type")]
+        input_type: Box<Node>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        default_value: Option<Box<Node>>,
+        #[serde(default)]
+        directives: Vec<Node>,
+    },
+    InterfaceTypeDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        interfaces: Vec<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        fields: Vec<Node>,
+    },
+    UnionTypeDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        types: Vec<Node>,
+    },
+    EnumTypeDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        values: Vec<Node>,
+    },
+    EnumValueDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+    },
+    InputObjectTypeDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        fields: Vec<Node>,
+    },
+    DirectiveDefinition {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        arguments: Vec<Node>,
+        #[serde(default)]
+        repeatable: bool,
+        locations: Vec<Node>,
+    },
+
+    // Type system extensions
+    SchemaExtension {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        #[serde(default)]
+        operation_types: Vec<Node>,
+    },
+    ScalarTypeExtension {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+    },
+    ObjectTypeExtension {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        interfaces: Vec<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        #[serde(default)]
+        fields: Vec<Node>,
+    },
+    InterfaceTypeExtension {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        interfaces: Vec<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        #[serde(default)]
+        fields: Vec<Node>,
+    },
+    UnionTypeExtension {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        #[serde(default)]
+        types: Vec<Node>,
+    },
+    EnumTypeExtension {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        #[serde(default)]
+        values: Vec<Node>,
+    },
+    InputObjectTypeExtension {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<Box<Node>>,
+        name: Box<Node>,
+        #[serde(default)]
+        directives: Vec<Node>,
+        #[serde(default)]
+        fields: Vec<Node>,
+    },
+}
+
+// Conversions from the syntax tree
+
+/// Spans of nodes that were not parsed from a string are left out.
+fn node(kind: Kind, span: &Span) -> Node {
+    Node {
+        kind,
+        loc: Some(*span).filter(|span| !span.is_identical(&Span::ignored())),
+    }
+}
+
+fn list<T>(items: &[T], convert: fn(&T) -> Node) -> Vec<Node> {
+    items.iter().map(convert).collect()
+}
+
+fn optional_list<T>(items: &Option<Vec<T>>, convert: fn(&T) -> Node) -> Vec<Node> {
+    items.iter().flatten().map(convert).collect()
+}
+
+fn operation_kind(operation: &Operation) -> OperationKind {
+    match operation {
+        Operation::Query => OperationKind::Query,
+        Operation::Mutation => OperationKind::Mutation,
+        Operation::Subscription => OperationKind::Subscription,
+    }
+}
+
+fn document(document: &Document) -> Node {
+    Node {
+        kind: Kind::Document {
+            definitions: list(&document.definitions, definition),
+        },
+        loc: None,
+    }
+}
+
+fn name(name: &NameNode) -> Node {
+    node(
+        Kind::Name {
+            value: name.value.clone(),
+        },
+        &name.span,
+    )
+}
+
+fn boxed_name(value: &NameNode) -> Box<Node> {
+    Box::new(name(value))
+}
+
+fn string(string: &StringValueNode) -> Node {
+    node(
+        Kind::StringValue {
+            value: string.value.clone(),
+            block: string.is_block(),
+        },
+        &string.span,
+    )
+}
+
+fn description(description: &Description) -> Option<Box<Node>> {
+    description
+        .as_ref()
+        .map(|description| Box::new(string(description)))
+}
+
+fn named_type(named_type: &NamedTypeNode) -> Node {
+    node(
+        Kind::NamedType {
+            name: boxed_name(&named_type.name),
+        },
+        &named_type.span,
+    )
+}
+
+fn type_node(type_node: &TypeNode) -> Node {
+    match type_node {
+        TypeNode::Named(named) => named_type(named),
+        TypeNode::List(list) => node(
+            Kind::ListType {
+                list_type: Box::new(self::type_node(&list.list_type)),
+            },
+            &list.span,
+        ),
+        TypeNode::NonNull(inner) => Node {
+            kind: Kind::NonNullType {
+                non_null_type: Box::new(self::type_node(inner)),
+            },
+            loc: None,
+        },
+    }
+}
+
+fn value(value: &ValueNode) -> Node {
+    match value {
+        ValueNode::Variable(variable) => self::variable(variable),
+        ValueNode::Int(int) => node(
+            Kind::IntValue {
+                value: int.raw.clone(),
+            },
+            &int.span,
+        ),
+        ValueNode::Float(float) => node(
+            Kind::FloatValue {
+                value: float.raw.clone(),
+            },
+            &float.span,
+        ),
+        ValueNode::Str(string) => self::string(string),
+        ValueNode::Bool(boolean) => node(
+            Kind::BooleanValue {
+                value: boolean.value,
+            },
+            &boolean.span,
+        ),
+        ValueNode::Null => Node {
+            kind: Kind::NullValue,
+            loc: None,
+        },
+        ValueNode::Enum(enum_value) => node(
+            Kind::EnumValue {
+                value: enum_value.value.clone(),
+            },
+            &enum_value.span,
+        ),
+        ValueNode::List(list_value) => node(
+            Kind::ListValue {
+                values: list(&list_value.values, self::value),
+            },
+            &list_value.span,
+        ),
+        ValueNode::Object(object) => node(
+            Kind::ObjectValue {
+                fields: list(&object.fields, |field| {
+                    node(
+                        Kind::ObjectField {
+                            name: boxed_name(&field.name),
+                            value: Box::new(self::value(&field.value)),
+                        },
+                        &field.span,
+                    )
+                }),
+            },
+            &object.span,
+        ),
+    }
+}
+
+fn variable(variable: &VariableNode) -> Node {
+    node(
+        Kind::Variable {
+            name: boxed_name(&variable.name),
+        },
+        &variable.span,
+    )
+}
+
+fn argument(argument: &Argument) -> Node {
+    node(
+        Kind::Argument {
+            name: boxed_name(&argument.name),
+            value: Box::new(value(&argument.value)),
+        },
+        &argument.span,
+    )
+}
+
+fn directive(directive: &DirectiveNode) -> Node {
+    node(
+        Kind::Directive {
+            name: boxed_name(&directive.name),
+            arguments: optional_list(&directive.arguments, argument),
+        },
+        &directive.span,
+    )
+}
+
+fn input_value(input: &InputValueDefinitionNode) -> Node {
+    node(
+        Kind::InputValueDefinition {
+            description: description(&input.description),
+            name: boxed_name(&input.name),
+            input_type: Box::new(type_node(&input.input_type)),
+            default_value: input
+                .default_value
+                .as_ref()
+                .map(|default| Box::new(value(default))),
+            directives: optional_list(&input.directives, directive),
+        },
+        &input.span,
+    )
+}
+
+fn field_definition(field: &FieldDefinitionNode) -> Node {
+    node(
+        Kind::FieldDefinition {
+            description: description(&field.description),
+            name: boxed_name(&field.name),
+            arguments: optional_list(&field.arguments, input_value),
+            field_type: Box::new(type_node(&field.field_type)),
+            directives: optional_list(&field.directives, directive),
+        },
+        &field.span,
+    )
+}
+
+fn enum_value(value: &EnumValueDefinitionNode) -> Node {
+    node(
+        Kind::EnumValueDefinition {
+            description: description(&value.description),
+            name: boxed_name(&value.name),
+            directives: optional_list(&value.directives, directive),
+        },
+        &value.span,
+    )
+}
+
+fn operation_type(operation: &OperationTypeDefinitionNode) -> Node {
+    node(
+        Kind::OperationTypeDefinition {
+            operation: operation_kind(&operation.operation),
+            node_type: Box::new(named_type(&operation.node_type)),
+        },
+        &operation.span,
+    )
+}
+
+fn directive_location(location: &DirectiveLocation) -> Node {
+    Node {
+        kind: Kind::Name {
+            value: String::from(crate::printer::directive_location_name(location)),
+        },
+        loc: None,
+    }
+}
+
+fn selection_set(selections: &[Selection]) -> Box<Node> {
+    Box::new(Node {
+        kind: Kind::SelectionSet {
+            selections: list(selections, selection),
+        },
+        loc: None,
+    })
+}
+
+fn selection(selection: &Selection) -> Node {
+    match selection {
+        Selection::Field(field) => node(
+            Kind::Field {
+                alias: field.alias.as_ref().map(boxed_name),
+                name: boxed_name(&field.name),
+                arguments: optional_list(&field.arguments, argument),
+                directives: optional_list(&field.directives, directive),
+                selection_set: field.selections.as_deref().map(selection_set),
+            },
+            &field.span,
+        ),
+        Selection::Fragment(FragmentSpread::Node(spread)) => node(
+            Kind::FragmentSpread {
+                name: boxed_name(&spread.name),
+                directives: optional_list(&spread.directives, directive),
+            },
+            &spread.span,
+        ),
+        Selection::Fragment(FragmentSpread::Inline(inline)) => node(
+            Kind::InlineFragment {
+                type_condition: inline
+                    .node_type
+                    .as_ref()
+                    .map(|condition| Box::new(named_type(condition))),
+                directives: optional_list(&inline.directives, directive),
+                selection_set: selection_set(&inline.selections),
+            },
+            &inline.span,
+        ),
+    }
+}
+
+fn variable_definition(definition: &VariableDefinitionNode) -> Node {
+    node(
+        Kind::VariableDefinition {
+            variable: Box::new(variable(&definition.variable)),
+            variable_type: Box::new(type_node(&definition.variable_type)),
+            default_value: definition
+                .default_value
+                .as_ref()
+                .map(|default| Box::new(value(default))),
+        },
+        &definition.span,
+    )
+}
+
+fn operation(operation: &OperationTypeNode) -> Node {
+    let kind = match operation {
+        OperationTypeNode::Query(_) => OperationKind::Query,
+        OperationTypeNode::Mutation(_) => OperationKind::Mutation,
+        OperationTypeNode::Subscription(_) => OperationKind::Subscription,
+    };
+    node(
+        Kind::OperationDefinition {
+            operation: kind,
+            name: operation.name().map(boxed_name),
+            variable_definitions: list(operation.variables(), variable_definition),
+            directives: optional_list(operation.directives(), directive),
+            selection_set: selection_set(operation.selections()),
+        },
+        operation.span(),
+    )
+}
+
+fn type_definition(definition: &TypeDefinitionNode) -> Node {
+    let kind = match definition {
+        TypeDefinitionNode::Scalar(scalar) => Kind::ScalarTypeDefinition {
+            description: description(&scalar.description),
+            name: boxed_name(&scalar.name),
+            directives: optional_list(&scalar.directives, directive),
+        },
+        TypeDefinitionNode::Object(object) => Kind::ObjectTypeDefinition {
+            description: description(&object.description),
+            name: boxed_name(&object.name),
+            interfaces: optional_list(&object.interfaces, named_type),
+            directives: optional_list(&object.directives, directive),
+            fields: list(&object.fields, field_definition),
+        },
+        TypeDefinitionNode::Interface(interface) => Kind::InterfaceTypeDefinition {
+            description: description(&interface.description),
+            name: boxed_name(&interface.name),
+            interfaces: optional_list(&interface.interfaces, named_type),
+            directives: optional_list(&interface.directives, directive),
+            fields: list(&interface.fields, field_definition),
+        },
+        TypeDefinitionNode::Union(union) => Kind::UnionTypeDefinition {
+            description: description(&union.description),
+            name: boxed_name(&union.name),
+            directives: optional_list(&union.directives, directive),
+            types: list(&union.types, named_type),
+        },
+        TypeDefinitionNode::Enum(enum_type) => Kind::EnumTypeDefinition {
+            description: description(&enum_type.description),
+            name: boxed_name(&enum_type.name),
+            directives: optional_list(&enum_type.directives, directive),
+            values: list(&enum_type.values, enum_value),
+        },
+        TypeDefinitionNode::Input(input) => Kind::InputObjectTypeDefinition {
+            description: description(&input.description),
+            name: boxed_name(&input.name),
+            directives: optional_list(&input.directives, directive),
+            fields: list(&input.fields, input_value),
+        },
+    };
+    node(kind, definition.span())
+}
+
+fn extension(extension: &TypeSystemExtensionNode) -> Node {
+    match extension {
+        TypeSystemExtensionNode::Schema(schema) => node(
+            Kind::SchemaExtension {
+                description: description(&schema.description),
+                directives: optional_list(&schema.directives, directive),
+                operation_types: optional_list(&schema.operations, operation_type),
+            },
+            &schema.span,
+        ),
+        TypeSystemExtensionNode::Scalar(scalar) => node(
+            Kind::ScalarTypeExtension {
+                description: description(&scalar.description),
+                name: boxed_name(&scalar.name),
+                directives: optional_list(&scalar.directives, directive),
+            },
+            &scalar.span,
+        ),
+        TypeSystemExtensionNode::Object(object) => node(
+            Kind::ObjectTypeExtension {
+                description: description(&object.description),
+                name: boxed_name(&object.name),
+                interfaces: optional_list(&object.interfaces, named_type),
+                directives: optional_list(&object.directives, directive),
+                fields: optional_list(&object.fields, field_definition),
+            },
+            &object.span,
+        ),
+        TypeSystemExtensionNode::Interface(interface) => node(
+            Kind::InterfaceTypeExtension {
+                description: description(&interface.description),
+                name: boxed_name(&interface.name),
+                interfaces: optional_list(&interface.interfaces, named_type),
+                directives: optional_list(&interface.directives, directive),
+                fields: optional_list(&interface.fields, field_definition),
+            },
+            &interface.span,
+        ),
+        TypeSystemExtensionNode::Union(union) => node(
+            Kind::UnionTypeExtension {
+                description: description(&union.description),
+                name: boxed_name(&union.name),
+                directives: optional_list(&union.directives, directive),
+                types: optional_list(&union.types, named_type),
+            },
+            &union.span,
+        ),
+        TypeSystemExtensionNode::Enum(enum_type) => node(
+            Kind::EnumTypeExtension {
+                description: description(&enum_type.description),
+                name: boxed_name(&enum_type.name),
+                directives: optional_list(&enum_type.directives, directive),
+                values: optional_list(&enum_type.values, enum_value),
+            },
+            &enum_type.span,
+        ),
+        TypeSystemExtensionNode::Input(input) => node(
+            Kind::InputObjectTypeExtension {
+                description: description(&input.description),
+                name: boxed_name(&input.name),
+                directives: optional_list(&input.directives, directive),
+                fields: optional_list(&input.fields, input_value),
+            },
+            &input.span,
+        ),
+    }
+}
+
+fn definition(definition: &DefinitionNode) -> Node {
+    match definition {
+        DefinitionNode::Executable(ExecutableDefinitionNode::Operation(definition)) => {
+            operation(definition)
+        }
+        DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(fragment)) => node(
+            Kind::FragmentDefinition {
+                name: boxed_name(&fragment.name),
+                type_condition: Box::new(named_type(&fragment.node_type)),
+                directives: optional_list(&fragment.directives, directive),
+                selection_set: selection_set(&fragment.selections),
+            },
+            &fragment.span,
+        ),
+        DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Schema(schema)) => node(
+            Kind::SchemaDefinition {
+                description: description(&schema.description),
+                directives: optional_list(&schema.directives, directive),
+                operation_types: list(&schema.operations, operation_type),
+            },
+            &schema.span,
+        ),
+        DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(definition)) => {
+            type_definition(definition)
+        }
+        DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Directive(definition)) => node(
+            Kind::DirectiveDefinition {
+                description: description(&definition.description),
+                name: boxed_name(&definition.name),
+                arguments: optional_list(&definition.arguments, input_value),
+                repeatable: definition.repeatable,
+                locations: list(&definition.locations, directive_location),
+            },
+            &definition.span,
+        ),
+        DefinitionNode::Extension(definition) => extension(definition),
+    }
+}
+
+// Conversions back into the syntax tree
+
+fn span(loc: Option<Span>) -> Span {
+    loc.unwrap_or_else(Span::ignored)
+}
+
+fn expected<T>(kind: &str) -> Result<T> {
+    Err(format!("Expected a {} node", kind))
+}
+
+fn into_list<T>(nodes: Vec<Node>, convert: fn(Node) -> Result<T>) -> Result<Vec<T>> {
+    nodes.into_iter().map(convert).collect()
+}
+
+/// Empty lists become None, as the parser leaves out lists that were not written.
+fn into_optional_list<T>(
+    nodes: Vec<Node>,
+    convert: fn(Node) -> Result<T>,
+) -> Result<Option<Vec<T>>> {
+    if nodes.is_empty() {
+        Ok(None)
+    } else {
+        into_list(nodes, convert).map(Some)
+    }
+}
+
+fn into_operation(kind: OperationKind) -> Operation {
+    match kind {
+        OperationKind::Query => Operation::Query,
+        OperationKind::Mutation => Operation::Mutation,
+        OperationKind::Subscription => Operation::Subscription,
+    }
+}
+
+fn into_document(node: Node) -> Result<Document> {
+    match node.kind {
+        Kind::Document { definitions } => {
+            Ok(Document::new(into_list(definitions, into_definition)?))
+        }
+        _ => expected("Document"),
+    }
+}
+
+fn into_name(node: Node) -> Result<NameNode> {
+    match node.kind {
+        Kind::Name { value } => Ok(NameNode {
+            value,
+            span: span(node.loc),
+        }),
+        _ => expected("Name"),
+    }
+}
+
+fn into_string(node: Node) -> Result<StringValueNode> {
+    match node.kind {
+        Kind::StringValue { value, block } => {
+            let mut string = StringValueNode::from(&value, block);
+            string.span = span(node.loc);
+            Ok(string)
+        }
+        _ => expected("StringValue"),
+    }
+}
+
+fn into_description(node: Option<Box<Node>>) -> Result<Description> {
+    node.map(|node| into_string(*node)).transpose()
+}
+
+fn into_named_type(node: Node) -> Result<NamedTypeNode> {
+    match node.kind {
+        Kind::NamedType { name } => Ok(NamedTypeNode {
+            name: into_name(*name)?,
+            span: span(node.loc),
+        }),
+        _ => expected("NamedType"),
+    }
+}
+
+fn into_type(node: Node) -> Result<TypeNode> {
+    match node.kind {
+        Kind::NamedType { .. } => into_named_type(node).map(TypeNode::Named),
+        Kind::ListType { list_type } => Ok(TypeNode::List(ListTypeNode {
+            list_type: Arc::new(into_type(*list_type)?),
+            span: span(node.loc),
+        })),
+        Kind::NonNullType { non_null_type } => {
+            Ok(TypeNode::NonNull(Arc::new(into_type(*non_null_type)?)))
+        }
+        _ => expected("NamedType, ListType or NonNullType"),
+    }
+}
+
+fn into_variable(node: Node) -> Result<VariableNode> {
+    match node.kind {
+        Kind::Variable { name } => Ok(VariableNode {
+            name: into_name(*name)?,
+            span: span(node.loc),
+        }),
+        _ => expected("Variable"),
+    }
+}
+
+fn into_object_field(node: Node) -> Result<ObjectFieldNode> {
+    match node.kind {
+        Kind::ObjectField { name, value } => Ok(ObjectFieldNode {
+            name: into_name(*name)?,
+            value: into_value(*value)?,
+            span: span(node.loc),
+        }),
+        _ => expected("ObjectField"),
+    }
+}
+
+fn into_value(node: Node) -> Result<ValueNode> {
+    let span = span(node.loc);
+    match node.kind {
+        Kind::Variable { .. } => into_variable(node).map(ValueNode::Variable),
+        Kind::IntValue { value } => Ok(ValueNode::Int(IntValueNode { raw: value, span })),
+        Kind::FloatValue { value } => Ok(ValueNode::Float(FloatValueNode { raw: value, span })),
+        Kind::StringValue { .. } => into_string(node).map(ValueNode::Str),
+        Kind::BooleanValue { value } => Ok(ValueNode::Bool(BooleanValueNode { value, span })),
+        Kind::NullValue => Ok(ValueNode::Null),
+        Kind::EnumValue { value } => Ok(ValueNode::Enum(EnumValueNode { value, span })),
+        Kind::ListValue { values } => Ok(ValueNode::List(ListValueNode {
+            values: into_list(values, into_value)?,
+            span,
+        })),
+        Kind::ObjectValue { fields } => Ok(ValueNode::Object(ObjectValueNode {
+            fields: into_list(fields, into_object_field)?,
+            span,
+        })),
+        _ => expected("value"),
+    }
+}
+
+fn into_default_value(node: Option<Box<Node>>) -> Result<Option<ValueNode>> {
+    node.map(|node| into_value(*node)).transpose()
+}
+
+fn into_argument(node: Node) -> Result<Argument> {
+    match node.kind {
+        Kind::Argument { name, value } => Ok(Argument {
+            name: into_name(*name)?,
+            value: into_value(*value)?,
+            span: span(node.loc),
+        }),
+        _ => expected("Argument"),
+    }
+}
+
+fn into_directive(node: Node) -> Result<DirectiveNode> {
+    match node.kind {
+        Kind::Directive { name, arguments } => Ok(DirectiveNode {
+            name: into_name(*name)?,
+            arguments: into_optional_list(arguments, into_argument)?,
+            span: span(node.loc),
+        }),
+        _ => expected("Directive"),
+    }
+}
+
+fn into_input_value(node: Node) -> Result<InputValueDefinitionNode> {
+    match node.kind {
+        Kind::InputValueDefinition {
+            description,
+            name,
+            input_type,
+            default_value,
+            directives,
+        } => Ok(InputValueDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            input_type: into_type(*input_type)?,
+            default_value: into_default_value(default_value)?,
+            directives: into_optional_list(directives, into_directive)?,
+            span: span(node.loc),
+        }),
+        _ => expected("InputValueDefinition"),
+    }
+}
+
+fn into_field_definition(node: Node) -> Result<FieldDefinitionNode> {
+    match node.kind {
+        Kind::FieldDefinition {
+            description,
+            name,
+            arguments,
+            field_type,
+            directives,
+        } => Ok(FieldDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            arguments: into_optional_list(arguments, into_input_value)?,
+            field_type: into_type(*field_type)?,
+            directives: into_optional_list(directives, into_directive)?,
+            span: span(node.loc),
+        }),
+        _ => expected("FieldDefinition"),
+    }
+}
+
+fn into_enum_value(node: Node) -> Result<EnumValueDefinitionNode> {
+    match node.kind {
+        Kind::EnumValueDefinition {
+            description,
+            name,
+            directives,
+        } => Ok(EnumValueDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            span: span(node.loc),
+        }),
+        _ => expected("EnumValueDefinition"),
+    }
+}
+
+fn into_operation_type(node: Node) -> Result<OperationTypeDefinitionNode> {
+    match node.kind {
+        Kind::OperationTypeDefinition {
+            operation,
+            node_type,
+        } => Ok(OperationTypeDefinitionNode {
+            operation: into_operation(operation),
+            node_type: into_named_type(*node_type)?,
+            span: span(node.loc),
+        }),
+        _ => expected("OperationTypeDefinition"),
+    }
+}
+
+fn into_directive_location(node: Node) -> Result<DirectiveLocation> {
+    let name = into_name(node)?;
+    DirectiveLocation::try_from(Token::Name(Location::ignored(), &name.value))
+        .map_err(|_| format!("Unknown directive location {}", name.value))
+}
+
+fn into_selections(node: Node) -> Result<Vec<Selection>> {
+    match node.kind {
+        Kind::SelectionSet { selections } => into_list(selections, into_selection),
+        _ => expected("SelectionSet"),
+    }
+}
+
+fn into_selection(node: Node) -> Result<Selection> {
+    let span = span(node.loc);
+    match node.kind {
+        Kind::Field {
+            alias,
+            name,
+            arguments,
+            directives,
+            selection_set,
+        } => Ok(Selection::Field(FieldNode {
+            name: into_name(*name)?,
+            alias: alias.map(|node| into_name(*node)).transpose()?,
+            arguments: into_optional_list(arguments, into_argument)?,
+            directives: into_optional_list(directives, into_directive)?,
+            selections: selection_set
+                .map(|node| into_selections(*node))
+                .transpose()?,
+            span,
+        })),
+        Kind::FragmentSpread { name, directives } => Ok(Selection::Fragment(FragmentSpread::Node(
+            FragmentSpreadNode {
+                name: into_name(*name)?,
+                directives: into_optional_list(directives, into_directive)?,
+                span,
+            },
+        ))),
+        Kind::InlineFragment {
+            type_condition,
+            directives,
+            selection_set,
+        } => Ok(Selection::Fragment(FragmentSpread::Inline(
+            InlineFragmentSpreadNode {
+                node_type: type_condition
+                    .map(|condition| into_named_type(*condition))
+                    .transpose()?,
+                directives: into_optional_list(directives, into_directive)?,
+                selections: into_selections(*selection_set)?,
+                span,
+            },
+        ))),
+        _ => expected("Field, FragmentSpread or InlineFragment"),
+    }
+}
+
+fn into_variable_definition(node: Node) -> Result<VariableDefinitionNode> {
+    match node.kind {
+        Kind::VariableDefinition {
+            variable,
+            variable_type,
+            default_value,
+        } => Ok(VariableDefinitionNode {
+            variable: into_variable(*variable)?,
+            variable_type: into_type(*variable_type)?,
+            default_value: into_default_value(default_value)?,
+            span: span(node.loc),
+        }),
+        _ => expected("VariableDefinition"),
+    }
+}
+
+fn into_definition(node: Node) -> Result<DefinitionNode> {
+    let span = span(node.loc);
+    let definition = match node.kind {
+        Kind::OperationDefinition {
+            operation,
+            name,
+            variable_definitions,
+            directives,
+            selection_set,
+        } => {
+            let name = name.map(|node| into_name(*node)).transpose()?;
+            let variables = into_optional_list(variable_definitions, into_variable_definition)?;
+            let directives = into_optional_list(directives, into_directive)?;
+            let selections = into_selections(*selection_set)?;
+            let operation = match operation {
+                OperationKind::Query => OperationTypeNode::Query(QueryDefinitionNode {
+                    name,
+                    variables,
+                    directives,
+                    selections,
+                    span,
+                }),
+                OperationKind::Mutation => OperationTypeNode::Mutation(MutationDefinitionNode {
+                    name,
+                    variables,
+                    directives,
+                    selections,
+                    span,
+                }),
+                OperationKind::Subscription => {
+                    OperationTypeNode::Subscription(SubscriptionDefinitionNode {
+                        name,
+                        variables,
+                        directives,
+                        selections,
+                        span,
+                    })
+                }
+            };
+            DefinitionNode::Executable(ExecutableDefinitionNode::Operation(operation))
+        }
+        Kind::FragmentDefinition {
+            name,
+            type_condition,
+            directives,
+            selection_set,
+        } => {
+            DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(FragmentDefinitionNode {
+                name: into_name(*name)?,
+                node_type: into_named_type(*type_condition)?,
+                directives: into_optional_list(directives, into_directive)?,
+                selections: into_selections(*selection_set)?,
+                span,
+            }))
+        }
+        Kind::SchemaDefinition {
+            description,
+            directives,
+            operation_types,
+        } => DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Schema(SchemaDefinitionNode {
+            description: into_description(description)?,
+            directives: into_optional_list(directives, into_directive)?,
+            operations: into_list(operation_types, into_operation_type)?,
+            span,
+        })),
+        Kind::ScalarTypeDefinition {
+            description,
+            name,
+            directives,
+        } => type_system(TypeDefinitionNode::Scalar(ScalarTypeDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            span,
+        })),
+        Kind::ObjectTypeDefinition {
+            description,
+            name,
+            interfaces,
+            directives,
+            fields,
+        } => type_system(TypeDefinitionNode::Object(ObjectTypeDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            interfaces: into_optional_list(interfaces, into_named_type)?,
+            directives: into_optional_list(directives, into_directive)?,
+            fields: into_list(fields, into_field_definition)?,
+            span,
+        })),
+        Kind::InterfaceTypeDefinition {
+            description,
+            name,
+            interfaces,
+            directives,
+            fields,
+        } => type_system(TypeDefinitionNode::Interface(InterfaceTypeDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            interfaces: into_optional_list(interfaces, into_named_type)?,
+            directives: into_optional_list(directives, into_directive)?,
+            fields: into_list(fields, into_field_definition)?,
+            span,
+        })),
+        Kind::UnionTypeDefinition {
+            description,
+            name,
+            directives,
+            types,
+        } => type_system(TypeDefinitionNode::Union(UnionTypeDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            types: into_list(types, into_named_type)?,
+            span,
+        })),
+        Kind::EnumTypeDefinition {
+            description,
+            name,
+            directives,
+            values,
+        } => type_system(TypeDefinitionNode::Enum(EnumTypeDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            values: into_list(values, into_enum_value)?,
+            span,
+        })),
+        Kind::InputObjectTypeDefinition {
+            description,
+            name,
+            directives,
+            fields,
+        } => type_system(TypeDefinitionNode::Input(InputTypeDefinitionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            fields: into_list(fields, into_input_value)?,
+            span,
+        })),
+        Kind::DirectiveDefinition {
+            description,
+            name,
+            arguments,
+            repeatable,
+            locations,
+        } => DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Directive(
+            DirectiveDefinitionNode {
+                description: into_description(description)?,
+                name: into_name(*name)?,
+                arguments: into_optional_list(arguments, into_input_value)?,
+                repeatable,
+                locations: into_list(locations, into_directive_location)?,
+                span,
+            },
+        )),
+        Kind::SchemaExtension {
+            description,
+            directives,
+            operation_types,
+        } => DefinitionNode::Extension(TypeSystemExtensionNode::Schema(SchemaExtensionNode {
+            description: into_description(description)?,
+            directives: into_optional_list(directives, into_directive)?,
+            operations: into_optional_list(operation_types, into_operation_type)?,
+            span,
+        })),
+        Kind::ScalarTypeExtension {
+            description,
+            name,
+            directives,
+        } => DefinitionNode::Extension(TypeSystemExtensionNode::Scalar(ScalarTypeExtensionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            span,
+        })),
+        Kind::ObjectTypeExtension {
+            description,
+            name,
+            interfaces,
+            directives,
+            fields,
+        } => DefinitionNode::Extension(TypeSystemExtensionNode::Object(ObjectTypeExtensionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            interfaces: into_optional_list(interfaces, into_named_type)?,
+            directives: into_optional_list(directives, into_directive)?,
+            fields: into_optional_list(fields, into_field_definition)?,
+            span,
+        })),
+        Kind::InterfaceTypeExtension {
+            description,
+            name,
+            interfaces,
+            directives,
+            fields,
+        } => DefinitionNode::Extension(TypeSystemExtensionNode::Interface(
+            InterfaceTypeExtensionNode {
+                description: into_description(description)?,
+                name: into_name(*name)?,
+                interfaces: into_optional_list(interfaces, into_named_type)?,
+                directives: into_optional_list(directives, into_directive)?,
+                fields: into_optional_list(fields, into_field_definition)?,
+                span,
+            },
+        )),
+        Kind::UnionTypeExtension {
+            description,
+            name,
+            directives,
+            types,
+        } => DefinitionNode::Extension(TypeSystemExtensionNode::Union(UnionTypeExtensionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            types: into_optional_list(types, into_named_type)?,
+            span,
+        })),
+        Kind::EnumTypeExtension {
+            description,
+            name,
+            directives,
+            values,
+        } => DefinitionNode::Extension(TypeSystemExtensionNode::Enum(EnumTypeExtensionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            values: into_optional_list(values, into_enum_value)?,
+            span,
+        })),
+        Kind::InputObjectTypeExtension {
+            description,
+            name,
+            directives,
+            fields,
+        } => DefinitionNode::Extension(TypeSystemExtensionNode::Input(InputTypeExtensionNode {
+            description: into_description(description)?,
+            name: into_name(*name)?,
+            directives: into_optional_list(directives, into_directive)?,
+            fields: into_optional_list(fields, into_input_value)?,
+            span,
+        })),
+        _ => return expected("definition"),
+    };
+    Ok(definition)
+}
+
+fn type_system(definition: TypeDefinitionNode) -> DefinitionNode {
+    DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(definition))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::document::Document;
+    use crate::parse;
+    use serde_json::json;
+
+    fn round_trip(source: &str) {
+        let document = parse(source).unwrap();
+        let json = serde_json::to_string(&document).unwrap();
+        let read: Document = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, document);
+        assert_eq!(read.to_string(), document.to_string());
+    }
+
+    #[test]
+    fn round_trips_type_system_documents() {
+        round_trip(concat!(
+            r#"schema @live { query: Query mutation: Mutation }
+"""The root"""
+type Query implements Node @cached(ttl: 10) {
+  "The id" id: ID!
+  users(first: Int = 10, filter: Filter = {name: "a", tags: [1, 2.5, null, RED]}): [User!]!
+}
+interface Node { id: ID! }
+union Result = Query | Mutation
+enum Color { RED "Green" GREEN @deprecated }
+input Filter { name: String, tags: [String] }
+scalar Date @specifiedBy(url: "https://example.com")
+directive @cached(ttl: Int) repeatable on OBJECT | FIELD_DEFINITION
+type Mutation { noop: Boolean }
+"#,
+            r#"extend schema @live
+extend scalar Date @cached
+extend type Query implements Named { name: String }
+extend interface Node @cached
+extend union Result = Node
+extend enum Color { BLUE }
+extend input Filter { age: Int }"#
+        ));
+    }
+
+    #[test]
+    fn round_trips_executable_documents() {
+        round_trip(
+            r#"query Users($first: Int = 5, $filter: Filter!) {
+  users(first: $first, filter: $filter) {
+    id
+    handle: name @include(if: true)
+    ...Fields
+    ... on Admin @skip(if: false) { level }
+    ... { id }
+  }
+}
+mutation Save @audit { save { id } }
+subscription { updates { id } }
+fragment Fields on User { email }"#,
+        );
+    }
+
+    #[test]
+    fn serializes_like_graphql_js() {
+        let document = parse("type User { friends(first: Int = 10): [User!] }").unwrap();
+        let mut json = serde_json::to_value(&document).unwrap();
+        assert_eq!(
+            json["definitions"][0]["loc"],
+            json!({
+                "start": {"absolutePosition": 0, "line": 1, "column": 1},
+                "end": {"absolutePosition": 47, "line": 1, "column": 48},
+            })
+        );
+        remove_locations(&mut json);
+        assert_eq!(
+            json,
+            json!({
+                "kind": "Document",
+                "definitions": [{
+                    "kind": "ObjectTypeDefinition",
+                    "name": {"kind": "Name", "value": "User"},
+                    "interfaces": [],
+                    "directives": [],
+                    "fields": [{
+                        "kind": "FieldDefinition",
+                        "name": {"kind": "Name", "value": "friends"},
+                        "arguments": [{
+                            "kind": "InputValueDefinition",
+                            "name": {"kind": "Name", "value": "first"},
+                            "type": {
+                                "kind": "NamedType",
+                                "name": {"kind": "Name", "value": "Int"},
+                            },
+                            "defaultValue": {"kind": "IntValue", "value": "10"},
+                            "directives": [],
+                        }],
+                        "type": {
+                            "kind": "ListType",
+                            "type": {
+                                "kind": "NonNullType",
+                                "type": {
+                                    "kind": "NamedType",
+                                    "name": {"kind": "Name", "value": "User"},
+                                },
+                            },
+                        },
+                        "directives": [],
+                    }],
+                }],
+            })
+        );
+    }
+
+    fn remove_locations(json: &mut serde_json::Value) {
+        match json {
+            serde_json::Value::Object(object) => {
+                object.remove("loc");
+                object.values_mut().for_each(remove_locations);
+            }
+            serde_json::Value::Array(array) => array.iter_mut().for_each(remove_locations),
+            _ => (),
+        }
+    }
+
+    #[test]
+    fn reads_documents_without_locations() {
+        let document: Document = serde_json::from_value(json!({
+            "kind": "Document",
+            "definitions": [{
+                "kind": "OperationDefinition",
+                "operation": "query",
+                "selectionSet": {
+                    "kind": "SelectionSet",
+                    "selections": [{
+                        "kind": "Field",
+                        "name": {"kind": "Name", "value": "user"},
+                        "arguments": [{
+                            "kind": "Argument",
+                            "name": {"kind": "Name", "value": "id"},
+                            "value": {"kind": "StringValue", "value": "1"},
+                        }],
+                    }],
+                },
+            }],
+        }))
+        .unwrap();
+        assert_eq!(document, parse(r#"{ user(id: "1") }"#).unwrap());
+    }
+
+    #[test]
+    fn rejects_nodes_of_the_wrong_kind() {
+        let error = serde_json::from_value::<Document>(json!({
+            "kind": "Document",
+            "definitions": [{
+                "kind": "ScalarTypeDefinition",
+                "name": {"kind": "IntValue", "value": "1"},
+            }],
+        }))
+        .unwrap_err();
+        assert_eq!(error.to_string(), "Expected a Name node");
+    }
+}
@@ -29,15 +29,15 @@
 //! "#);
 //! assert_eq!(lexer.next(), Some(Ok(Token::Start)));
 //! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(1, 2, 1), "schema"))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(9, 2, 9), "Schema"))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::OpenBrace(Location::new(11, 2, 11)))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(14, 3, 3), "query"))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::Colon(Location::new(19, 3, 8)))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(21, 3, 10), "Query"))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(25, 4, 3), "mutation"))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::Colon(Location::new(34, 4, 12)))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(36, 4, 14), "Mutation"))));
-//! assert_eq!(lexer.next(), Some(Ok(Token::CloseBrace(Location::new(38, 5, 1)))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(8, 2, 8), "Schema"))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::OpenBrace(Location::new(15, 2, 15)))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(19, 3, 3), "query"))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::Colon(Location::new(24, 3, 8)))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(26, 3, 10), "Query"))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(35, 4, 3), "mutation"))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::Colon(Location::new(43, 4, 11)))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::Name(Location::new(45, 4, 13), "Mutation"))));
+//! assert_eq!(lexer.next(), Some(Ok(Token::CloseBrace(Location::new(54, 5, 1)))));
 //! assert_eq!(lexer.next(), Some(Ok(Token::End)));
 //! assert_eq!(lexer.next(), None);
 //! ```
@@ -69,6 +69,7 @@
 //!
 
 use crate::error::LexError;
+use crate::strings;
 use crate::token::{Location, Token};
 use log::debug;
 use regex::Regex;
@@ -103,7 +104,7 @@ type LexerItem<'a> = Result<Token<'a>, LexError>;
 impl<'a> Lexer<'a> {
     /// Creates a new lexer that passes over the provided input string.
     /// The token series will
-    pub fn new(input: &str) -> Lexer {
+    pub fn new(input: &str) -> Lexer<'_> {
         Lexer {
             raw: input,
             input: input.char_indices().peekable(),
@@ -115,6 +116,15 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Creates a lexer that starts part way through the input, at the absolute position `pos`,
+    /// without a leading `Start` token.
+    pub(crate) fn resume(input: &'a str, pos: usize) -> Lexer<'a> {
+        let mut lexer = Lexer::new(input);
+        lexer.initialized = true;
+        lexer.advance_to(pos);
+        lexer
+    }
+
     fn get_next_token(&mut self) -> LexerItem<'a> {
         if let Some((i, next)) = self.input.peek() {
             let index = *i;
@@ -137,8 +147,7 @@ impl<'a> Lexer<'a> {
                 '\n' => self.ignore_newline(),
                 '"' => self.lex_string(index),
                 // TODO Make this multilingual
-                'a'..='z' | 'A'..='Z' => self.lex_name(index),
-                // TODO Make this handle scientific notation
+                'a'..='z' | 'A'..='Z' | '_' => self.lex_name(index),
                 '0'..='9' | '-' => self.lex_number(index),
                 '.' => self.lex_ellipsis(index),
                 _ => self.make_unknown_character_error(),
@@ -151,138 +160,126 @@ impl<'a> Lexer<'a> {
     }
 
     fn lex_ellipsis(&mut self, index: usize) -> LexerItem<'a> {
-        lazy_static! {
-            static ref SPREAD: Regex = Regex::new("...").unwrap();
-        }
-        if SPREAD.is_match_at(self.raw, index) {
-            let cur_col = self.col;
-            let cur_pos = self.position;
-            self.advance_n(3);
-            Ok(Token::Spread(Location::new(cur_pos, self.line, cur_col)))
+        if self.raw[index..].starts_with("...") {
+            let tok = Token::Spread(self.get_current_location());
+            self.advance_to(index + 3);
+            Ok(tok)
         } else {
             self.make_unexpected_character_error()
         }
     }
 
+    /// Lexes an IntValue or FloatValue as defined by the spec. Numbers keep their raw lexeme so
+    /// that values which do not fit in an i64 or f64 are not lost.
     fn lex_number(&mut self, init_pos: usize) -> LexerItem<'a> {
-        lazy_static! {
-            static ref FLOAT: Regex = Regex::new(r#"-?[0-9]+\.[0-9]+"#).unwrap();
-            static ref INT: Regex = Regex::new(r#"-?[0-9]+"#).unwrap();
+        let bytes = self.raw.as_bytes();
+        let digits_from = |start: usize| {
+            bytes[start..]
+                .iter()
+                .take_while(|byte| byte.is_ascii_digit())
+                .count()
+        };
+        let mut end = init_pos;
+        if bytes[end] == b'-' {
+            end += 1;
         }
-        if FLOAT.is_match_at(self.raw, init_pos) {
-            let mut locations = FLOAT.capture_locations();
-            match FLOAT.captures_read_at(&mut locations, self.raw, init_pos) {
-                Some(_) => match locations.get(0) {
-                    Some((start, end)) => {
-                        let cur_col = self.col;
-                        let substr = self.raw.get(start..end).unwrap();
-                        match substr.parse::<f64>() {
-                            Ok(f) => {
-                                self.advance_to(end);
-                                Ok(Token::Float(Location::new(init_pos, self.line, cur_col), f))
-                            }
-                            Err(_) => self.make_conversion_error("Float"),
-                        }
-                    }
-                    None => self.make_unknown_character_error(),
-                },
-                None => self.make_unexpected_character_error(),
+        let integer_digits = digits_from(end);
+        // The integer part is a lone zero or starts with a non-zero digit
+        if integer_digits == 0 || (bytes[end] == b'0' && integer_digits > 1) {
+            return self.make_conversion_error("Int");
+        }
+        end += integer_digits;
+
+        let mut is_float = false;
+        if bytes.get(end) == Some(&b'.') {
+            is_float = true;
+            let fraction_digits = digits_from(end + 1);
+            if fraction_digits == 0 {
+                return self.make_conversion_error("Float");
             }
-        } else if INT.is_match_at(self.raw, init_pos) {
-            let mut locations = INT.capture_locations();
-            match INT.captures_read_at(&mut locations, self.raw, init_pos) {
-                Some(_) => match locations.get(0) {
-                    Some((start, end)) => {
-                        let substr = self.raw.get(start..end).unwrap();
-                        match substr.parse::<i64>() {
-                            Ok(i) => {
-                                let tok = Token::Int(self.get_current_location(), i);
-                                self.advance_to(end);
-                                Ok(tok)
-                            }
-                            Err(_) => self.make_conversion_error("Int"),
-                        }
-                    }
-                    None => self.make_unknown_character_error(),
-                },
-                None => self.make_unexpected_character_error(),
+            end += 1 + fraction_digits;
+        }
+        if let Some(b'e') | Some(b'E') = bytes.get(end) {
+            is_float = true;
+            end += 1;
+            if let Some(b'+') | Some(b'-') = bytes.get(end) {
+                end += 1;
             }
-        } else {
-            self.make_conversion_error("Int or Float")
+            let exponent_digits = digits_from(end);
+            if exponent_digits == 0 {
+                return self.make_conversion_error("Float");
+            }
+            end += exponent_digits;
         }
-    }
 
-    fn lex_name(&mut self, init_pos: usize) -> LexerItem<'a> {
-        let mut end_pos = 0;
-        while let Some((_, c)) = self.input.peek() {
-            if c.is_alphanumeric() || *c == '_' {
-                self.input.next();
-                end_pos += 1;
-            } else {
-                break;
+        // A number cannot be directly followed by a digit, a `.` or the start of a name
+        if let Some(next) = bytes.get(end) {
+            if next.is_ascii_alphanumeric() || *next == b'_' || *next == b'.' {
+                return self.make_conversion_error(if is_float { "Float" } else { "Int" });
             }
         }
-        self.position += end_pos;
-        let init_col = self.col;
-        self.col += end_pos;
-        end_pos += init_pos;
-        Ok(Token::Name(
-            Location::new(init_pos, self.line, init_col),
+
+        let lexeme = &self.raw[init_pos..end];
+        let tok = if is_float {
+            Token::Float(self.get_current_location(), lexeme)
+        } else {
+            Token::Int(self.get_current_location(), lexeme)
+        };
+        self.advance_to(end);
+        Ok(tok)
+    }
+
+    fn lex_name(&mut self, init_pos: usize) -> LexerItem<'a> {
+        let end_pos = self.raw[init_pos..]
+            .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .map_or(self.raw.len(), |offset| init_pos + offset);
+        let tok = Token::Name(
+            self.get_current_location(),
             self.raw.get(init_pos..end_pos).unwrap(),
-        ))
+        );
+        self.advance_to(end_pos);
+        Ok(tok)
     }
 
     fn lex_string(&mut self, init_pos: usize) -> LexerItem<'a> {
         lazy_static! {
-            static ref BLOCK_START: Regex = Regex::new(r#"""""#).unwrap();
-            static ref BLOCK: Regex = Regex::new(r#""""((?:\\.|[^"\\])*)""""#).unwrap();
-            static ref SINGLE: Regex = Regex::new(r#""((?:\\.|[^"\\])*)""#).unwrap();
+            static ref SINGLE: Regex = Regex::new(r#""((?:\\[^\n\r]|[^"\\\n\r])*)""#).unwrap();
         }
-        if BLOCK_START.is_match_at(self.raw, init_pos) {
-            let mut locations = BLOCK.capture_locations();
-            match BLOCK.captures_read_at(&mut locations, self.raw, init_pos) {
-                Some(_) => match locations.get(1) {
-                    Some((start_off, end_off)) => {
-                        let (start, end) = locations.get(0).unwrap();
-                        match self.input.position(|(i, _)| i == end) {
-                            Some(pos) => self.position = pos,
-                            None => (),
-                        }
-                        let tok = Token::BlockStr(
-                            Location::new(start, self.line, self.col),
-                            self.raw.get(start_off..end_off).unwrap(),
-                        );
-
-                        let substr = self.raw.get(start..end).unwrap();
-                        let newlines = substr.lines().count();
-                        self.line += newlines;
-                        Ok(tok)
-                    }
-                    None => self.make_unmatched_quote_error(),
-                },
-                None => self.make_unmatched_quote_error(),
+        if self.raw[init_pos..].starts_with("\"\"\"") {
+            // A block string ends at the first triple quote that is not escaped as \"""
+            let start_off = init_pos + 3;
+            let mut search_from = start_off;
+            while let Some(offset) = self.raw[search_from..].find("\"\"\"") {
+                let end_off = search_from + offset;
+                if self.raw[..end_off].ends_with('\\') {
+                    search_from = end_off + 3;
+                    continue;
+                }
+                let tok = Token::BlockStr(
+                    self.get_current_location(),
+                    self.raw.get(start_off..end_off).unwrap(),
+                );
+                self.advance_to(end_off + 3);
+                return Ok(tok);
             }
+            self.make_unmatched_quote_error()
         } else {
             let mut locations = SINGLE.capture_locations();
             match SINGLE.captures_read_at(&mut locations, self.raw, init_pos) {
                 Some(_) => match locations.get(1) {
                     Some((start_off, end_off)) => {
-                        let cur_col = self.col;
-                        match self.input.position(|(i, _)| i == end_off) {
-                            Some(pos) => {
-                                self.position += pos + 1;
-                                self.col += pos + 1;
-                            }
-                            None => (),
+                        let location = self.get_current_location();
+                        let value = self.raw.get(start_off..end_off).unwrap();
+                        if let Err(error) = strings::decode_string(value, location) {
+                            self.ended = true;
+                            return Err(error);
                         }
-                        Ok(Token::Str(
-                            Location::new(init_pos, self.line, cur_col),
-                            self.raw.get(start_off..end_off).unwrap(),
-                        ))
+                        self.advance_to(end_off + 1);
+                        Ok(Token::Str(location, value))
                     }
                     None => self.make_unmatched_quote_error(),
                 },
-                None => self.make_unmatched_quote_error(),
+                None => self.make_unterminated_string_error(init_pos),
             }
         }
     }
@@ -366,10 +363,7 @@ impl<'a> Lexer<'a> {
     }
 
     fn ignore_newline(&mut self) -> LexerItem<'a> {
-        self.line += 1;
-        self.col = 1;
-        self.position += 1;
-        self.input.next();
+        self.advance();
         self.get_next_token()
     }
 
@@ -379,10 +373,11 @@ impl<'a> Lexer<'a> {
     }
 
     fn ignore_comments(&mut self) -> LexerItem<'a> {
-        self.input.next(); // Consume #
-        if let Some((new_line_index, _new_line)) = self.input.find(|(_index, c)| *c == '\n') {
-            self.advance_to(new_line_index);
-        }
+        // The newline ending the comment is left for ignore_newline
+        let end_pos = self.raw[self.position..]
+            .find('\n')
+            .map_or(self.raw.len(), |offset| self.position + offset);
+        self.advance_to(end_pos);
         self.get_next_token()
     }
 
@@ -413,27 +408,48 @@ impl<'a> Lexer<'a> {
         )))
     }
 
+    /// Reports the first unescaped line terminator in the string starting at `init_pos`,
+    /// or an unmatched quote when the input ends before one is found.
+    fn make_unterminated_string_error(&mut self, init_pos: usize) -> LexerItem<'a> {
+        let rest = &self.raw[init_pos + 1..];
+        match rest.find(['\n', '\r']) {
+            Some(offset) => {
+                self.ended = true;
+                Err(LexError::LineTerminatorInString(Location::new(
+                    init_pos + 1 + offset,
+                    self.line,
+                    self.col + 1 + rest[..offset].chars().count(),
+                )))
+            }
+            None => self.make_unmatched_quote_error(),
+        }
+    }
+
     fn get_current_location(&mut self) -> Location {
         Location::new(self.position, self.line, self.col)
     }
 
+    /// Consumes the next character, keeping the line and column up to date.
     fn advance(&mut self) {
-        self.input.next();
-        self.position += 1;
-        self.col += 1;
-    }
-
-    fn advance_n(&mut self, n: usize) {
-        self.position += n;
-        let new_pos = self.position - 1;
-        self.col += n;
-        self.input.position(|(i, _)| i == new_pos);
+        if let Some((index, c)) = self.input.next() {
+            self.position = index + c.len_utf8();
+            if c == '\n' {
+                self.line += 1;
+                self.col = 1;
+            } else {
+                self.col += 1;
+            }
+        }
     }
 
+    /// Consumes every character before the absolute position `pos`.
     fn advance_to(&mut self, pos: usize) {
-        self.position = pos;
-        self.col = pos;
-        self.input.position(|(i, _)| i == pos - 1);
+        while let Some((index, _)) = self.input.peek() {
+            if *index >= pos {
+                break;
+            }
+            self.advance();
+        }
     }
 }
 
@@ -458,7 +474,7 @@ impl<'a> Iterator for Lexer<'a> {
             debug!("Uninizialized");
             self.initialized = true;
             Some(Ok(Token::Start))
-        } else if let Some(_) = self.input.peek() {
+        } else if self.input.peek().is_some() {
             let tok = self.get_next_token();
             debug!("Next Token: {:?}", tok);
             debug!("Next char: {:?}", self.input.peek());
@@ -488,7 +504,7 @@ impl<'a> Iterator for Lexer<'a> {
 /// assert!(tokens.is_ok());
 /// println!("Tokens: {:?}", tokens);
 /// ````
-pub fn tokenize(input: &str) -> Result<Vec<Token>, LexError> {
+pub fn tokenize(input: &str) -> Result<Vec<Token<'_>>, LexError> {
     let state = Lexer::new(input);
     let results: Result<Vec<Token>, LexError> = state.collect();
     results
@@ -716,7 +732,7 @@ mod tests {
             one.unwrap(),
             vec![
                 Token::Start,
-                Token::Int(Location::new(0, 1, 1), 123456i64),
+                Token::Int(Location::new(0, 1, 1), "123456"),
                 Token::End,
             ]
         );
@@ -726,7 +742,7 @@ mod tests {
             one.unwrap(),
             vec![
                 Token::Start,
-                Token::Int(Location::new(0, 1, 1), -9876i64),
+                Token::Int(Location::new(0, 1, 1), "-9876"),
                 Token::End,
             ]
         );
@@ -741,7 +757,7 @@ mod tests {
             one.unwrap(),
             vec![
                 Token::Start,
-                Token::Float(Location::new(0, 1, 1), 1.23456789f64),
+                Token::Float(Location::new(0, 1, 1), "1.23456789"),
                 Token::End,
             ]
         );
@@ -751,12 +767,70 @@ mod tests {
             one.unwrap(),
             vec![
                 Token::Start,
-                Token::Float(Location::new(0, 1, 1), -0.987654321f64),
+                Token::Float(Location::new(0, 1, 1), "-0.987654321"),
+                Token::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_int_before_float() {
+        let numbers = tokenize("1 2.5");
+        assert!(numbers.is_ok());
+        assert_eq!(
+            numbers.unwrap(),
+            vec![
+                Token::Start,
+                Token::Int(Location::new(0, 1, 1), "1"),
+                Token::Float(Location::new(2, 1, 3), "2.5"),
                 Token::End,
             ]
         );
     }
 
+    #[test]
+    fn lex_exponents_and_big_numbers() {
+        let numbers = tokenize("1e10 1.5E-3 -2e+2 340282366920938463463374607431768211455");
+        assert_eq!(
+            numbers.unwrap(),
+            vec![
+                Token::Start,
+                Token::Float(Location::new(0, 1, 1), "1e10"),
+                Token::Float(Location::new(5, 1, 6), "1.5E-3"),
+                Token::Float(Location::new(12, 1, 13), "-2e+2"),
+                Token::Int(
+                    Location::new(18, 1, 19),
+                    "340282366920938463463374607431768211455"
+                ),
+                Token::End,
+            ]
+        );
+    }
+
+    #[test]
+    fn lex_invalid_numbers() {
+        for (number, expected_type) in &[
+            ("0123", "Int"),
+            ("-", "Int"),
+            ("1.", "Float"),
+            ("1.e5", "Float"),
+            ("1e", "Float"),
+            ("1.5e+", "Float"),
+            ("12abc", "Int"),
+            ("1.5.2", "Float"),
+        ] {
+            assert_eq!(
+                tokenize(number),
+                Err(LexError::UnableToConvert(
+                    Location::new(0, 1, 1),
+                    expected_type
+                )),
+                "{} should not lex",
+                number
+            );
+        }
+    }
+
     #[test]
     fn lex_strings() {
         println!("Testing strings");
@@ -794,7 +868,7 @@ text""""#,
     #[test]
     fn lex_name() {
         println!("Testing names");
-        let text = tokenize("name\nname_with_underscore");
+        let text = tokenize("name\nname_with_underscore __typename");
         assert!(text.is_ok());
         assert_eq!(
             text.unwrap(),
@@ -802,6 +876,7 @@ text""""#,
                 Token::Start,
                 Token::Name(Location::new(0, 1, 1), "name"),
                 Token::Name(Location::new(5, 2, 1), "name_with_underscore"),
+                Token::Name(Location::new(26, 2, 22), "__typename"),
                 Token::End,
             ]
         );
@@ -934,6 +1009,50 @@ text""""#,
         );
     }
 
+    #[test]
+    fn handles_quotes_in_block_strings() {
+        assert_eq!(
+            tokenize(r#""""A "quoted" \""" value""""#),
+            Ok(vec![
+                Token::Start,
+                Token::BlockStr(Location::new(0, 1, 1), r#"A "quoted" \""" value"#),
+                Token::End,
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_escapes() {
+        assert_eq!(
+            tokenize(r#"name: "a\qb""#),
+            Err(LexError::InvalidEscape(Location::new(8, 1, 9)))
+        );
+        assert_eq!(
+            tokenize(r#""\uD83D""#),
+            Err(LexError::InvalidEscape(Location::new(1, 1, 2)))
+        );
+    }
+
+    #[test]
+    fn rejects_line_terminators_in_strings() {
+        assert_eq!(
+            tokenize("name: \"a\nb\""),
+            Err(LexError::LineTerminatorInString(Location::new(8, 1, 9)))
+        );
+        assert_eq!(
+            tokenize("\"a\\\r\""),
+            Err(LexError::LineTerminatorInString(Location::new(3, 1, 4)))
+        );
+        assert_eq!(
+            tokenize(r#""a\nb""#),
+            Ok(vec![
+                Token::Start,
+                Token::Str(Location::new(0, 1, 1), r"a\nb"),
+                Token::End,
+            ])
+        );
+    }
+
     #[test]
     fn handles_unmatched_quote() {
         let err = tokenize("\"test");
@@ -1030,6 +1149,61 @@ They can be multiple lines
         );
     }
 
+    #[test]
+    fn tracks_token_locations() {
+        let tokens = tokenize(
+            r#"
+schema Schema { # comment
+  query: Query,
+  """
+  Block
+  """ "str" ...Frag
+  mutation: Mutation
+}"#,
+        );
+        assert!(tokens.is_ok());
+        let locations: Vec<Location> = tokens
+            .unwrap()
+            .iter()
+            .filter(|tok| !matches!(tok, Token::Start | Token::End))
+            .map(|tok| tok.location())
+            .collect();
+        assert_eq!(
+            locations,
+            vec![
+                Location::new(1, 2, 1),
+                Location::new(8, 2, 8),
+                Location::new(15, 2, 15),
+                Location::new(29, 3, 3),
+                Location::new(34, 3, 8),
+                Location::new(36, 3, 10),
+                Location::new(45, 4, 3),
+                Location::new(63, 6, 7),
+                Location::new(69, 6, 13),
+                Location::new(72, 6, 16),
+                Location::new(79, 7, 3),
+                Location::new(87, 7, 11),
+                Location::new(89, 7, 13),
+                Location::new(98, 8, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_string_before_block_string() {
+        let strings = tokenize(r#""single" """block""""#);
+        assert!(strings.is_ok());
+        assert_eq!(
+            strings.unwrap(),
+            vec![
+                Token::Start,
+                Token::Str(Location::new(0, 1, 1), "single"),
+                Token::BlockStr(Location::new(9, 1, 10), "block"),
+                Token::End,
+            ]
+        );
+    }
+
     #[test]
     fn handles_multiple_block_strings() {
         let strings = tokenize(
@@ -14,34 +14,87 @@
 
 #[macro_use]
 extern crate lazy_static;
+pub mod analysis;
 mod ast;
+mod construct;
+pub mod diagnostic;
+pub mod diff;
 pub mod document;
 pub mod error;
+pub mod introspection;
+#[cfg(feature = "serde")]
+mod json;
 pub mod lexer;
 pub mod macros;
-mod nodes;
+mod merge;
+pub mod nodes;
+pub mod normalize;
+pub mod persisted;
+pub mod printer;
+pub mod schema;
+pub mod stream;
+mod strings;
 pub mod token;
-mod validation;
+pub mod validation;
+pub mod visitor;
+
+/// The functions that the `gql!` macro of `syntax-macros` relies on. These are not part of the
+/// public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::construct::construct;
+}
 
 use ast::AST;
 use document::Document;
-use error::ParseResult;
+use error::{ParseError, ParseResult};
 
 /// Parse a string into a GraphQL Document.
 /// This is a potentially heavy, synchronous operation.
-pub fn parse<'a>(query: &'a str) -> ParseResult<Document> {
+pub fn parse(query: &str) -> ParseResult<Document> {
     let mut ast = AST::new(query)?;
     let document = ast.parse()?;
     Ok(document)
 }
 
+/// Parse a string into a GraphQL Document, collecting every syntax error instead of stopping at
+/// the first one.
+///
+/// When a definition fails to parse, its error is recorded and parsing resumes at the start of
+/// the next definition. The returned Document contains every definition that parsed
+/// successfully.
+///
+/// # Example
+///
+/// ```
+/// let (document, errors) = syntax::parse_with_recovery(
+///     r#"type Person { name: }
+/// type Photo { url: String }
+/// type Empty {}
+/// scalar Date"#,
+/// );
+/// assert_eq!(document.definitions.len(), 2);
+/// assert_eq!(errors.len(), 2);
+/// ```
+pub fn parse_with_recovery(query: &str) -> (Document, Vec<ParseError>) {
+    match AST::new(query) {
+        Ok(mut ast) => ast.parse_with_recovery(),
+        Err(error) => (Document::new(Vec::new()), vec![error]),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::error::ParseError;
+    use crate::nodes::enum_type_extension::*;
+    use crate::nodes::input_type_extension::*;
+    use crate::nodes::interface_type_extension::*;
     use crate::nodes::object_type_extension::*;
+    use crate::nodes::scalar_type_extension::*;
+    use crate::nodes::schema_extension::*;
+    use crate::nodes::union_type_extension::*;
     use crate::nodes::*;
-    use crate::token::{Location, Token};
+    use crate::token::{Location, Span, Token};
     use std::sync::Arc;
 
     #[test]
@@ -82,7 +135,10 @@ mod tests {
                                 arguments: None,
                                 field_type: TypeNode::Named(NamedTypeNode {
                                     name: NameNode::from("String"),
-                                })
+                                    span: Span::ignored(),
+                                }),
+                                directives: None,
+                                span: Span::ignored(),
                             },
                             FieldDefinitionNode {
                                 description: None,
@@ -90,9 +146,12 @@ mod tests {
                                 arguments: None,
                                 field_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
                                     NamedTypeNode {
-                                        name: NameNode::from("Int")
+                                        name: NameNode::from("Int"),
+                                        span: Span::ignored(),
                                     }
-                                )))
+                                ))),
+                                directives: None,
+                                span: Span::ignored(),
                             },
                             FieldDefinitionNode {
                                 description: None,
@@ -100,9 +159,13 @@ mod tests {
                                 arguments: None,
                                 field_type: TypeNode::List(ListTypeNode {
                                     list_type: Arc::new(TypeNode::Named(NamedTypeNode {
-                                        name: NameNode::from("String")
-                                    }))
-                                })
+                                        name: NameNode::from("String"),
+                                        span: Span::ignored(),
+                                    })),
+                                    span: Span::ignored(),
+                                }),
+                                directives: None,
+                                span: Span::ignored(),
                             },
                             FieldDefinitionNode {
                                 description: None,
@@ -111,10 +174,13 @@ mod tests {
                                 field_type: TypeNode::NonNull(Arc::new(TypeNode::List(
                                     ListTypeNode::new(TypeNode::NonNull(Arc::new(
                                         TypeNode::Named(NamedTypeNode {
-                                            name: NameNode::from("Int")
+                                            name: NameNode::from("Int"),
+                                            span: Span::ignored(),
                                         })
                                     )))
-                                )))
+                                ))),
+                                directives: None,
+                                span: Span::ignored(),
                             },
                             FieldDefinitionNode {
                                 description: None,
@@ -122,9 +188,12 @@ mod tests {
                                 arguments: None,
                                 field_type: TypeNode::NonNull(Arc::new(TypeNode::List(
                                     ListTypeNode::new(TypeNode::Named(NamedTypeNode {
-                                        name: NameNode::from("Int")
+                                        name: NameNode::from("Int"),
+                                        span: Span::ignored(),
                                     }))
-                                )))
+                                ))),
+                                directives: None,
+                                span: Span::ignored(),
                             },
                             FieldDefinitionNode {
                                 description: None,
@@ -134,30 +203,36 @@ mod tests {
                                         description: None,
                                         name: NameNode::from("arg1"),
                                         input_type: TypeNode::Named(NamedTypeNode {
-                                            name: NameNode::from("Int")
+                                            name: NameNode::from("Int"),
+                                            span: Span::ignored(),
                                         }),
-                                        default_value: Some(ValueNode::Int(IntValueNode {
-                                            value: 42
-                                        })),
+                                        default_value: Some(ValueNode::Int(IntValueNode::from(42))),
                                         directives: None,
+                                        span: Span::ignored(),
                                     },
                                     InputValueDefinitionNode {
                                         description: None,
                                         name: NameNode::from("arg2"),
                                         input_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
                                             NamedTypeNode {
-                                                name: NameNode::from("Bool")
+                                                name: NameNode::from("Bool"),
+                                                span: Span::ignored(),
                                             }
                                         ))),
                                         default_value: None,
                                         directives: None,
+                                        span: Span::ignored(),
                                     },
                                 ]),
                                 field_type: TypeNode::Named(NamedTypeNode {
-                                    name: NameNode::from("Bool")
-                                })
+                                    name: NameNode::from("Bool"),
+                                    span: Span::ignored(),
+                                }),
+                                directives: None,
+                                span: Span::ignored(),
                             },
                         ],
+                        span: Span::ignored(),
                     })
                 ))]
             }
@@ -191,7 +266,8 @@ type Obj {
                             .unwrap()
                         ),
                         name: NameNode {
-                            value: String::from("Obj")
+                            value: String::from("Obj"),
+                            span: Span::ignored(),
                         },
                         interfaces: None,
                         directives: None,
@@ -204,15 +280,21 @@ type Obj {
                                 .unwrap()
                             ),
                             name: NameNode {
-                                value: String::from("name")
+                                value: String::from("name"),
+                                span: Span::ignored(),
                             },
                             arguments: None,
                             field_type: TypeNode::Named(NamedTypeNode {
                                 name: NameNode {
-                                    value: String::from("String")
-                                }
-                            })
+                                    value: String::from("String"),
+                                    span: Span::ignored(),
+                                },
+                                span: Span::ignored(),
+                            }),
+                            directives: None,
+                            span: Span::ignored(),
                         },],
+                        span: Span::ignored(),
                     })
                 ))]
             }
@@ -240,46 +322,58 @@ type Obj {
                     TypeDefinitionNode::Enum(EnumTypeDefinitionNode {
                         description: None,
                         name: NameNode {
-                            value: String::from("VEHICLE_TYPE")
+                            value: String::from("VEHICLE_TYPE"),
+                            span: Span::ignored(),
                         },
                         directives: None,
                         values: vec![
                             EnumValueDefinitionNode {
                                 description: None,
                                 name: NameNode {
-                                    value: String::from("SEDAN")
+                                    value: String::from("SEDAN"),
+                                    span: Span::ignored(),
                                 },
                                 directives: None,
+                                span: Span::ignored(),
                             },
                             EnumValueDefinitionNode {
                                 description: None,
                                 name: NameNode {
-                                    value: String::from("SUV")
+                                    value: String::from("SUV"),
+                                    span: Span::ignored(),
                                 },
                                 directives: None,
+                                span: Span::ignored(),
                             },
                             EnumValueDefinitionNode {
                                 description: None,
                                 name: NameNode {
-                                    value: String::from("COMPACT")
+                                    value: String::from("COMPACT"),
+                                    span: Span::ignored(),
                                 },
                                 directives: None,
+                                span: Span::ignored(),
                             },
                             EnumValueDefinitionNode {
                                 description: None,
                                 name: NameNode {
-                                    value: String::from("TRUCK")
+                                    value: String::from("TRUCK"),
+                                    span: Span::ignored(),
                                 },
                                 directives: None,
+                                span: Span::ignored(),
                             },
                             EnumValueDefinitionNode {
                                 description: None,
                                 name: NameNode {
-                                    value: String::from("HYBRID")
+                                    value: String::from("HYBRID"),
+                                    span: Span::ignored(),
                                 },
                                 directives: None,
+                                span: Span::ignored(),
                             },
-                        ]
+                        ],
+                        span: Span::ignored(),
                     })
                 ))]
             }
@@ -310,7 +404,8 @@ union Pic =
                             types: vec![
                                 NamedTypeNode::from("Photo"),
                                 NamedTypeNode::from("Person"),
-                            ]
+                            ],
+                            span: Span::ignored(),
                         })
                     )),
                     DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
@@ -323,7 +418,8 @@ union Pic =
                                 NamedTypeNode::from("Jpeg"),
                                 NamedTypeNode::from("Png"),
                                 NamedTypeNode::from("Svg"),
-                            ]
+                            ],
+                            span: Span::ignored(),
                         })
                     )),
                 ]
@@ -355,7 +451,10 @@ union Pic =
                             arguments: None,
                             name: NameNode::from("id"),
                             field_type: TypeNode::Named(NamedTypeNode::from("ID")),
+                            directives: None,
+                            span: Span::ignored(),
                         }],
+                        span: Span::ignored(),
                     })
                 ))]
             }
@@ -379,14 +478,20 @@ union Pic =
                         directives: Some(vec![
                             DirectiveNode {
                                 name: NameNode::from("depricated"),
-                                arguments: None
+                                arguments: None,
+                                span: Span::ignored(),
                             },
                             DirectiveNode {
                                 name: NameNode::from("old"),
                                 arguments: Some(vec![Argument {
                                     name: NameNode::from("allow"),
-                                    value: ValueNode::Bool(BooleanValueNode { value: false })
-                                }])
+                                    value: ValueNode::Bool(BooleanValueNode {
+                                        value: false,
+                                        span: Span::ignored(),
+                                    }),
+                                    span: Span::ignored(),
+                                }]),
+                                span: Span::ignored(),
                             },
                         ]),
                         fields: vec![FieldDefinitionNode {
@@ -394,7 +499,10 @@ union Pic =
                             arguments: None,
                             name: NameNode::from("id"),
                             field_type: TypeNode::Named(NamedTypeNode::from("ID")),
+                            directives: None,
+                            span: Span::ignored(),
                         }],
+                        span: Span::ignored(),
                     })
                 ))]
             }
@@ -421,31 +529,39 @@ interface Void @depricated {
                     DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
                         TypeDefinitionNode::Interface(InterfaceTypeDefinitionNode {
                             name: NameNode::from("Empty"),
+                            interfaces: None,
                             description: None,
                             directives: None,
                             fields: Vec::new(),
+                            span: Span::ignored(),
                         })
                     )),
                     DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
                         TypeDefinitionNode::Interface(InterfaceTypeDefinitionNode {
                             name: NameNode::from("Named"),
+                            interfaces: None,
                             description: None,
                             directives: None,
                             fields: vec![FieldDefinitionNode {
                                 description: None,
                                 name: NameNode::from("name"),
                                 arguments: None,
-                                field_type: TypeNode::Named(NamedTypeNode::from("String"))
+                                field_type: TypeNode::Named(NamedTypeNode::from("String")),
+                                directives: None,
+                                span: Span::ignored(),
                             }],
+                            span: Span::ignored(),
                         })
                     )),
                     DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
                         TypeDefinitionNode::Interface(InterfaceTypeDefinitionNode {
                             name: NameNode::from("Void"),
+                            interfaces: None,
                             description: None,
                             directives: Some(vec![DirectiveNode {
                                 name: NameNode::from("depricated"),
-                                arguments: None
+                                arguments: None,
+                                span: Span::ignored(),
                             }]),
                             fields: vec![FieldDefinitionNode {
                                 description: None,
@@ -453,8 +569,11 @@ interface Void @depricated {
                                 arguments: None,
                                 field_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
                                     NamedTypeNode::from("Boolean")
-                                )))
+                                ))),
+                                directives: None,
+                                span: Span::ignored(),
                             }],
+                            span: Span::ignored(),
                         })
                     )),
                 ]
@@ -480,22 +599,26 @@ input Point {
                     TypeDefinitionNode::Input(InputTypeDefinitionNode {
                         description: None,
                         name: NameNode::from("Point"),
+                        directives: None,
                         fields: vec![
                             InputValueDefinitionNode {
                                 description: None,
                                 name: NameNode::from("x"),
                                 input_type: TypeNode::Named(NamedTypeNode::from("Float")),
                                 default_value: None,
-                                directives: None
+                                directives: None,
+                                span: Span::ignored(),
                             },
                             InputValueDefinitionNode {
                                 description: None,
                                 name: NameNode::from("y"),
                                 input_type: TypeNode::Named(NamedTypeNode::from("Float")),
                                 default_value: None,
-                                directives: None
+                                directives: None,
+                                span: Span::ignored(),
                             },
                         ],
+                        span: Span::ignored(),
                     })
                 ))]
             }
@@ -519,6 +642,7 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                             description: None,
                             name: NameNode::from("Date"),
                             directives: None,
+                            span: Span::ignored(),
                         })
                     )),
                     DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
@@ -532,9 +656,12 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                 name: NameNode::from("format"),
                                 arguments: Some(vec![Argument {
                                     name: NameNode::from("pattern"),
-                                    value: ValueNode::Str(StringValueNode::from("HH:mm:ss", false))
-                                }])
+                                    value: ValueNode::Str(StringValueNode::from("HH:mm:ss", false)),
+                                    span: Span::ignored(),
+                                }]),
+                                span: Span::ignored(),
                             }]),
+                            span: Span::ignored(),
                         })
                     )),
                 ]
@@ -564,6 +691,7 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                             directives: Some(vec![DirectiveNode {
                                 name: NameNode::from("addedDirective"),
                                 arguments: None,
+                                span: Span::ignored(),
                             }]),
                             fields: Some(vec![
                                 FieldDefinitionNode {
@@ -571,14 +699,19 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                     description: None,
                                     name: NameNode::from("createdOn"),
                                     field_type: TypeNode::Named(NamedTypeNode::from("DateTime")),
+                                    directives: None,
+                                    span: Span::ignored(),
                                 },
                                 FieldDefinitionNode {
                                     arguments: None,
                                     description: None,
                                     name: NameNode::from("updatedOn"),
                                     field_type: TypeNode::Named(NamedTypeNode::from("DateTime")),
+                                    directives: None,
+                                    span: Span::ignored(),
                                 },
                             ]),
+                            span: Span::ignored(),
                         }
                     )),
                     DefinitionNode::Extension(TypeSystemExtensionNode::Object(
@@ -591,6 +724,7 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                             ]),
                             directives: None,
                             fields: None,
+                            span: Span::ignored(),
                         }
                     )),
                     DefinitionNode::Extension(TypeSystemExtensionNode::Object(
@@ -600,9 +734,11 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                             interfaces: None,
                             directives: Some(vec![DirectiveNode {
                                 name: NameNode::from("accessLevel"),
-                                arguments: None
+                                arguments: None,
+                                span: Span::ignored(),
                             }]),
                             fields: None,
+                            span: Span::ignored(),
                         }
                     ))
                 ],
@@ -631,6 +767,7 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                         QueryDefinitionNode {
                             name: None,
                             variables: None,
+                            directives: None,
                             selections: vec![
                                 Selection::Field(FieldNode {
                                     name: NameNode::from("user"),
@@ -638,6 +775,7 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                     arguments: None,
                                     directives: None,
                                     selections: None,
+                                    span: Span::ignored(),
                                 }),
                                 Selection::Field(FieldNode {
                                     name: NameNode::from("permissions"),
@@ -646,8 +784,10 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                     directives: Some(vec![DirectiveNode {
                                         name: NameNode::from("view"),
                                         arguments: None,
+                                        span: Span::ignored(),
                                     }]),
                                     selections: None,
+                                    span: Span::ignored(),
                                 }),
                                 Selection::Field(FieldNode {
                                     name: NameNode::from("photo"),
@@ -655,15 +795,18 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                     arguments: Some(vec![
                                         Argument {
                                             name: NameNode::from("height"),
-                                            value: ValueNode::Int(IntValueNode { value: 100 }),
+                                            value: ValueNode::Int(IntValueNode::from(100)),
+                                            span: Span::ignored(),
                                         },
                                         Argument {
                                             name: NameNode::from("width"),
-                                            value: ValueNode::Int(IntValueNode { value: 100 }),
+                                            value: ValueNode::Int(IntValueNode::from(100)),
+                                            span: Span::ignored(),
                                         }
                                     ]),
                                     directives: None,
                                     selections: None,
+                                    span: Span::ignored(),
                                 }),
                                 Selection::Field(FieldNode {
                                     name: NameNode::from("friends"),
@@ -672,9 +815,11 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                     directives: None,
                                     selections: Some(vec![Selection::Field(FieldNode::from(
                                         "name"
-                                    ))])
+                                    ))]),
+                                    span: Span::ignored(),
                                 })
-                            ]
+                            ],
+                            span: Span::ignored(),
                         }
                     ))
                 ),]
@@ -709,6 +854,7 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                         QueryDefinitionNode {
                             name: None,
                             variables: None,
+                            directives: None,
                             selections: vec![Selection::Field(FieldNode {
                                 name: NameNode::from("user"),
                                 alias: None,
@@ -719,13 +865,16 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                     Selection::Fragment(FragmentSpread::Node(FragmentSpreadNode {
                                         name: NameNode::from("standardProfilePic"),
                                         directives: None,
+                                        span: Span::ignored(),
                                     })),
                                     Selection::Fragment(FragmentSpread::Node(FragmentSpreadNode {
                                         name: NameNode::from("anonymousProfilePic"),
                                         directives: Some(vec![DirectiveNode {
                                             name: NameNode::from("svg"),
                                             arguments: None,
+                                            span: Span::ignored(),
                                         }]),
+                                        span: Span::ignored(),
                                     })),
                                     Selection::Fragment(FragmentSpread::Inline(
                                         InlineFragmentSpreadNode {
@@ -733,7 +882,8 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                             directives: None,
                                             selections: vec![Selection::Field(FieldNode::from(
                                                 "likeCount"
-                                            ))]
+                                            ))],
+                                            span: Span::ignored(),
                                         }
                                     )),
                                     Selection::Fragment(FragmentSpread::Inline(
@@ -745,17 +895,23 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                                     name: NameNode::from("if"),
                                                     value: ValueNode::Bool(BooleanValueNode {
                                                         value: true,
-                                                    })
-                                                }])
+                                                        span: Span::ignored(),
+                                                    }),
+                                                    span: Span::ignored(),
+                                                }]),
+                                                span: Span::ignored(),
                                             }]),
                                             selections: vec![
                                                 Selection::Field(FieldNode::from("birthday")),
                                                 Selection::Field(FieldNode::from("location")),
-                                            ]
+                                            ],
+                                            span: Span::ignored(),
                                         }
                                     ))
-                                ])
-                            })]
+                                ]),
+                                span: Span::ignored(),
+                            })],
+                            span: Span::ignored(),
                         }
                     ))
                 )]
@@ -781,6 +937,7 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                         QueryDefinitionNode {
                             name: Some(NameNode::from("TestQuery")),
                             variables: None,
+                            directives: None,
                             selections: vec![Selection::Field(FieldNode {
                                 name: NameNode::from("user"),
                                 alias: None,
@@ -789,8 +946,10 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                 selections: Some(vec![
                                     Selection::Field(FieldNode::from("name")),
                                     Selection::Field(FieldNode::from("email")),
-                                ])
-                            })]
+                                ]),
+                                span: Span::ignored(),
+                            })],
+                            span: Span::ignored(),
                         }
                     ))
                 )]
@@ -820,21 +979,26 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                     variable: VariableNode::from("email"),
                                     variable_type: TypeNode::Named(NamedTypeNode::from("Email")),
                                     default_value: None,
+                                    span: Span::ignored(),
                                 },
                                 VariableDefinitionNode {
                                     variable: VariableNode::from("isHuman"),
                                     variable_type: TypeNode::Named(NamedTypeNode::from("Boolean")),
                                     default_value: Some(ValueNode::Bool(BooleanValueNode {
                                         value: true,
-                                    }))
+                                        span: Span::ignored(),
+                                    })),
+                                    span: Span::ignored(),
                                 }
                             ]),
+                            directives: None,
                             selections: vec![Selection::Field(FieldNode {
                                 name: NameNode::from("user"),
                                 alias: None,
                                 arguments: Some(vec![Argument {
                                     name: NameNode::from("email"),
-                                    value: ValueNode::Variable(VariableNode::from("email"))
+                                    value: ValueNode::Variable(VariableNode::from("email")),
+                                    span: Span::ignored(),
                                 }]),
                                 directives: None,
                                 selections: Some(vec![
@@ -848,14 +1012,19 @@ scalar Time @format(pattern: "HH:mm:ss")"#,
                                                 name: NameNode::from("if"),
                                                 value: ValueNode::Variable(VariableNode::from(
                                                     "isHuman"
-                                                ))
-                                            }])
+                                                )),
+                                                span: Span::ignored(),
+                                            }]),
+                                            span: Span::ignored(),
                                         }]),
                                         selections: None,
+                                        span: Span::ignored(),
                                     }),
                                     Selection::Field(FieldNode::from("permissions"))
                                 ]),
-                            })]
+                                span: Span::ignored(),
+                            })],
+                            span: Span::ignored(),
                         }
                     ))
                 )]
@@ -887,6 +1056,7 @@ fragment friendFields on User @traverse(depth: 1) {
                             node_type: NamedTypeNode::from("User"),
                             directives: None,
                             selections: vec![Selection::Field(FieldNode::from("name"))],
+                            span: Span::ignored(),
                         }
                     )),
                     DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(
@@ -897,15 +1067,18 @@ fragment friendFields on User @traverse(depth: 1) {
                                 name: NameNode::from("traverse"),
                                 arguments: Some(vec![Argument {
                                     name: NameNode::from("depth"),
-                                    value: ValueNode::Int(IntValueNode { value: 1 })
-                                }])
+                                    value: ValueNode::Int(IntValueNode::from(1)),
+                                    span: Span::ignored(),
+                                }]),
+                                span: Span::ignored(),
                             }]),
                             selections: vec![
                                 Selection::Field(FieldNode::from("id")),
                                 Selection::Fragment(FragmentSpread::Node(
                                     FragmentSpreadNode::from("Name")
                                 ))
-                            ]
+                            ],
+                            span: Span::ignored(),
                         }
                     ))
                 ]
@@ -932,24 +1105,669 @@ fragment friendFields on User @traverse(depth: 1) {
                         directives: Some(vec![DirectiveNode {
                             name: NameNode::from("depricated"),
                             arguments: None,
+                            span: Span::ignored(),
                         }]),
                         operations: vec![
                             OperationTypeDefinitionNode {
                                 operation: Operation::Query,
                                 node_type: NamedTypeNode::from("Query"),
+                                span: Span::ignored(),
                             },
                             OperationTypeDefinitionNode {
                                 operation: Operation::Mutation,
                                 node_type: NamedTypeNode::from("Mutation"),
+                                span: Span::ignored(),
                             },
                             OperationTypeDefinitionNode {
                                 operation: Operation::Subscription,
                                 node_type: NamedTypeNode::from("Subscription"),
+                                span: Span::ignored(),
                             },
-                        ]
+                        ],
+                        span: Span::ignored(),
                     })
                 ),]
             }
         )
     }
+
+    #[test]
+    fn parse_query_with_directives() {
+        let res = parse("query Cached @cached(ttl: 60) { me }");
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Document {
+                definitions: vec![DefinitionNode::Executable(
+                    ExecutableDefinitionNode::Operation(OperationTypeNode::Query(
+                        QueryDefinitionNode {
+                            name: Some(NameNode::from("Cached")),
+                            variables: None,
+                            directives: Some(vec![DirectiveNode {
+                                name: NameNode::from("cached"),
+                                arguments: Some(vec![Argument {
+                                    name: NameNode::from("ttl"),
+                                    value: ValueNode::Int(IntValueNode::from(60)),
+                                    span: Span::ignored(),
+                                }]),
+                                span: Span::ignored(),
+                            }]),
+                            selections: vec![Selection::Field(FieldNode::from("me"))],
+                            span: Span::ignored(),
+                        }
+                    ))
+                )]
+            }
+        )
+    }
+
+    #[test]
+    fn parse_mutation_with_variables() {
+        let mutation = r#"mutation CreateUser($name: String!) @audit {
+  createUser(name: $name) {
+    id
+  }
+}"#;
+        let res = parse(mutation);
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Document {
+                definitions: vec![DefinitionNode::Executable(
+                    ExecutableDefinitionNode::Operation(OperationTypeNode::Mutation(
+                        MutationDefinitionNode {
+                            name: Some(NameNode::from("CreateUser")),
+                            variables: Some(vec![VariableDefinitionNode {
+                                variable: VariableNode::from("name"),
+                                variable_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
+                                    NamedTypeNode::from("String")
+                                ))),
+                                default_value: None,
+                                span: Span::ignored(),
+                            }]),
+                            directives: Some(vec![DirectiveNode {
+                                name: NameNode::from("audit"),
+                                arguments: None,
+                                span: Span::ignored(),
+                            }]),
+                            selections: vec![Selection::Field(FieldNode {
+                                name: NameNode::from("createUser"),
+                                alias: None,
+                                arguments: Some(vec![Argument {
+                                    name: NameNode::from("name"),
+                                    value: ValueNode::Variable(VariableNode::from("name")),
+                                    span: Span::ignored(),
+                                }]),
+                                directives: None,
+                                selections: Some(vec![Selection::Field(FieldNode::from("id"))]),
+                                span: Span::ignored(),
+                            })],
+                            span: Span::ignored(),
+                        }
+                    ))
+                )]
+            }
+        )
+    }
+
+    #[test]
+    fn parse_unnamed_mutation() {
+        let res = parse("mutation { logout }");
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Document {
+                definitions: vec![DefinitionNode::Executable(
+                    ExecutableDefinitionNode::Operation(OperationTypeNode::Mutation(
+                        MutationDefinitionNode {
+                            name: None,
+                            variables: None,
+                            directives: None,
+                            selections: vec![Selection::Field(FieldNode::from("logout"))],
+                            span: Span::ignored(),
+                        }
+                    ))
+                )]
+            }
+        )
+    }
+
+    #[test]
+    fn parse_subscription() {
+        let res = parse(
+            r#"subscription OnMessage($room: ID!) {
+  messageAdded(room: $room) {
+    body
+  }
+}"#,
+        );
+        assert!(res.is_ok());
+        let document = res.unwrap();
+        assert_eq!(
+            document,
+            Document {
+                definitions: vec![DefinitionNode::Executable(
+                    ExecutableDefinitionNode::Operation(OperationTypeNode::Subscription(
+                        SubscriptionDefinitionNode {
+                            name: Some(NameNode::from("OnMessage")),
+                            variables: Some(vec![VariableDefinitionNode {
+                                variable: VariableNode::from("room"),
+                                variable_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
+                                    NamedTypeNode::from("ID")
+                                ))),
+                                default_value: None,
+                                span: Span::ignored(),
+                            }]),
+                            directives: None,
+                            selections: vec![Selection::Field(FieldNode {
+                                name: NameNode::from("messageAdded"),
+                                alias: None,
+                                arguments: Some(vec![Argument {
+                                    name: NameNode::from("room"),
+                                    value: ValueNode::Variable(VariableNode::from("room")),
+                                    span: Span::ignored(),
+                                }]),
+                                directives: None,
+                                selections: Some(vec![Selection::Field(FieldNode::from("body"))]),
+                                span: Span::ignored(),
+                            })],
+                            span: Span::ignored(),
+                        }
+                    ))
+                )]
+            }
+        );
+    }
+
+    #[test]
+    fn parse_directive_definition() {
+        let res = parse(
+            r#""Marks a field for indexing"
+directive @index(unique: Boolean = false) repeatable on
+  | FIELD_DEFINITION
+  | INPUT_FIELD_DEFINITION
+
+directive @skip(if: Boolean!) on FIELD | FRAGMENT_SPREAD | INLINE_FRAGMENT
+"#,
+        );
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Document {
+                definitions: vec![
+                    DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Directive(
+                        DirectiveDefinitionNode {
+                            description: Some(StringValueNode::from(
+                                "Marks a field for indexing",
+                                false
+                            )),
+                            name: NameNode::from("index"),
+                            arguments: Some(vec![InputValueDefinitionNode {
+                                description: None,
+                                name: NameNode::from("unique"),
+                                input_type: TypeNode::Named(NamedTypeNode::from("Boolean")),
+                                default_value: Some(ValueNode::Bool(BooleanValueNode {
+                                    value: false,
+                                    span: Span::ignored(),
+                                })),
+                                directives: None,
+                                span: Span::ignored(),
+                            }]),
+                            repeatable: true,
+                            locations: vec![
+                                DirectiveLocation::TypeSystem(
+                                    TypeSystemDirectiveLocation::FieldDefinition
+                                ),
+                                DirectiveLocation::TypeSystem(
+                                    TypeSystemDirectiveLocation::InputFieldDefinition
+                                ),
+                            ],
+                            span: Span::ignored(),
+                        }
+                    )),
+                    DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Directive(
+                        DirectiveDefinitionNode {
+                            description: None,
+                            name: NameNode::from("skip"),
+                            arguments: Some(vec![InputValueDefinitionNode {
+                                description: None,
+                                name: NameNode::from("if"),
+                                input_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
+                                    NamedTypeNode::from("Boolean")
+                                ))),
+                                default_value: None,
+                                directives: None,
+                                span: Span::ignored(),
+                            }]),
+                            repeatable: false,
+                            locations: vec![
+                                DirectiveLocation::Executable(ExecutableDirectiveLocation::Field),
+                                DirectiveLocation::Executable(
+                                    ExecutableDirectiveLocation::FragmentSpread
+                                ),
+                                DirectiveLocation::Executable(
+                                    ExecutableDirectiveLocation::InlineFragment
+                                ),
+                            ],
+                            span: Span::ignored(),
+                        }
+                    )),
+                ]
+            }
+        )
+    }
+
+    #[test]
+    fn rejects_unknown_directive_location() {
+        let res = parse("directive @bad on SOMEWHERE");
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err(),
+            ParseError::UnexpectedKeyword {
+                expected: "A valid directive location".into(),
+                received: "SOMEWHERE".into(),
+                location: Location::new(18, 1, 19),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_type_extensions() {
+        let res = parse(
+            r#"extend interface Node @key { id: ID! }
+            extend union SearchResult = Photo | Video
+            extend enum Direction { UP DOWN }
+            extend input UserInput @audit { email: String }
+            extend scalar Date @format(pattern: "YYYY-MM-DD")
+            extend schema { subscription: Subscription }
+            "#,
+        );
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Document {
+                definitions: vec![
+                    DefinitionNode::Extension(TypeSystemExtensionNode::Interface(
+                        InterfaceTypeExtensionNode {
+                            description: None,
+                            name: NameNode::from("Node"),
+                            interfaces: None,
+                            directives: Some(vec![DirectiveNode {
+                                name: NameNode::from("key"),
+                                arguments: None,
+                                span: Span::ignored(),
+                            }]),
+                            fields: Some(vec![FieldDefinitionNode {
+                                arguments: None,
+                                description: None,
+                                name: NameNode::from("id"),
+                                field_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
+                                    NamedTypeNode::from("ID")
+                                ))),
+                                directives: None,
+                                span: Span::ignored(),
+                            }]),
+                            span: Span::ignored(),
+                        }
+                    )),
+                    DefinitionNode::Extension(TypeSystemExtensionNode::Union(
+                        UnionTypeExtensionNode {
+                            description: None,
+                            name: NameNode::from("SearchResult"),
+                            directives: None,
+                            types: Some(vec![
+                                NamedTypeNode::from("Photo"),
+                                NamedTypeNode::from("Video"),
+                            ]),
+                            span: Span::ignored(),
+                        }
+                    )),
+                    DefinitionNode::Extension(TypeSystemExtensionNode::Enum(
+                        EnumTypeExtensionNode {
+                            description: None,
+                            name: NameNode::from("Direction"),
+                            directives: None,
+                            values: Some(vec![
+                                EnumValueDefinitionNode {
+                                    description: None,
+                                    name: NameNode::from("UP"),
+                                    directives: None,
+                                    span: Span::ignored(),
+                                },
+                                EnumValueDefinitionNode {
+                                    description: None,
+                                    name: NameNode::from("DOWN"),
+                                    directives: None,
+                                    span: Span::ignored(),
+                                },
+                            ]),
+                            span: Span::ignored(),
+                        }
+                    )),
+                    DefinitionNode::Extension(TypeSystemExtensionNode::Input(
+                        InputTypeExtensionNode {
+                            description: None,
+                            name: NameNode::from("UserInput"),
+                            directives: Some(vec![DirectiveNode {
+                                name: NameNode::from("audit"),
+                                arguments: None,
+                                span: Span::ignored(),
+                            }]),
+                            fields: Some(vec![InputValueDefinitionNode {
+                                description: None,
+                                name: NameNode::from("email"),
+                                input_type: TypeNode::Named(NamedTypeNode::from("String")),
+                                default_value: None,
+                                directives: None,
+                                span: Span::ignored(),
+                            }]),
+                            span: Span::ignored(),
+                        }
+                    )),
+                    DefinitionNode::Extension(TypeSystemExtensionNode::Scalar(
+                        ScalarTypeExtensionNode {
+                            description: None,
+                            name: NameNode::from("Date"),
+                            directives: Some(vec![DirectiveNode {
+                                name: NameNode::from("format"),
+                                arguments: Some(vec![Argument {
+                                    name: NameNode::from("pattern"),
+                                    value: ValueNode::Str(StringValueNode::from(
+                                        "YYYY-MM-DD",
+                                        false
+                                    )),
+                                    span: Span::ignored(),
+                                }]),
+                                span: Span::ignored(),
+                            }]),
+                            span: Span::ignored(),
+                        }
+                    )),
+                    DefinitionNode::Extension(TypeSystemExtensionNode::Schema(
+                        SchemaExtensionNode {
+                            description: None,
+                            directives: None,
+                            operations: Some(vec![OperationTypeDefinitionNode {
+                                operation: Operation::Subscription,
+                                node_type: NamedTypeNode::from("Subscription"),
+                                span: Span::ignored(),
+                            }]),
+                            span: Span::ignored(),
+                        }
+                    )),
+                ]
+            }
+        )
+    }
+
+    #[test]
+    fn parses_field_definition_directives() {
+        let res = parse(
+            r#"type User {
+  id: ID! @index(unique: true)
+  name: String @deprecated(reason: "Use fullName") @index
+}"#,
+        );
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Document {
+                definitions: vec![DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                    TypeDefinitionNode::Object(ObjectTypeDefinitionNode {
+                        description: None,
+                        name: NameNode::from("User"),
+                        interfaces: None,
+                        directives: None,
+                        fields: vec![
+                            FieldDefinitionNode {
+                                description: None,
+                                name: NameNode::from("id"),
+                                arguments: None,
+                                field_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
+                                    NamedTypeNode::from("ID")
+                                ))),
+                                directives: Some(vec![DirectiveNode {
+                                    name: NameNode::from("index"),
+                                    arguments: Some(vec![Argument {
+                                        name: NameNode::from("unique"),
+                                        value: ValueNode::Bool(BooleanValueNode {
+                                            value: true,
+                                            span: Span::ignored(),
+                                        }),
+                                        span: Span::ignored(),
+                                    }]),
+                                    span: Span::ignored(),
+                                }]),
+                                span: Span::ignored(),
+                            },
+                            FieldDefinitionNode {
+                                description: None,
+                                name: NameNode::from("name"),
+                                arguments: None,
+                                field_type: TypeNode::Named(NamedTypeNode::from("String")),
+                                directives: Some(vec![
+                                    DirectiveNode {
+                                        name: NameNode::from("deprecated"),
+                                        arguments: Some(vec![Argument {
+                                            name: NameNode::from("reason"),
+                                            value: ValueNode::Str(StringValueNode::from(
+                                                "Use fullName",
+                                                false
+                                            )),
+                                            span: Span::ignored(),
+                                        }]),
+                                        span: Span::ignored(),
+                                    },
+                                    DirectiveNode {
+                                        name: NameNode::from("index"),
+                                        arguments: None,
+                                        span: Span::ignored(),
+                                    },
+                                ]),
+                                span: Span::ignored(),
+                            },
+                        ],
+                        span: Span::ignored(),
+                    })
+                ))]
+            }
+        )
+    }
+
+    #[test]
+    fn parses_interface_implementing_interfaces() {
+        let res = parse(
+            r#"interface Node implements Entity & Timestamped {
+  id: ID!
+}
+type User implements & Node & Entity {
+  id: ID!
+}"#,
+        );
+        assert!(res.is_ok());
+        assert_eq!(
+            res.unwrap(),
+            Document {
+                definitions: vec![
+                    DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                        TypeDefinitionNode::Interface(InterfaceTypeDefinitionNode {
+                            name: NameNode::from("Node"),
+                            description: None,
+                            interfaces: Some(vec![
+                                NamedTypeNode::from("Entity"),
+                                NamedTypeNode::from("Timestamped"),
+                            ]),
+                            directives: None,
+                            fields: vec![FieldDefinitionNode {
+                                description: None,
+                                name: NameNode::from("id"),
+                                arguments: None,
+                                field_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
+                                    NamedTypeNode::from("ID")
+                                ))),
+                                directives: None,
+                                span: Span::ignored(),
+                            }],
+                            span: Span::ignored(),
+                        })
+                    )),
+                    DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                        TypeDefinitionNode::Object(ObjectTypeDefinitionNode {
+                            name: NameNode::from("User"),
+                            description: None,
+                            interfaces: Some(vec![
+                                NamedTypeNode::from("Node"),
+                                NamedTypeNode::from("Entity"),
+                            ]),
+                            directives: None,
+                            fields: vec![FieldDefinitionNode {
+                                description: None,
+                                name: NameNode::from("id"),
+                                arguments: None,
+                                field_type: TypeNode::NonNull(Arc::new(TypeNode::Named(
+                                    NamedTypeNode::from("ID")
+                                ))),
+                                directives: None,
+                                span: Span::ignored(),
+                            }],
+                            span: Span::ignored(),
+                        })
+                    )),
+                ]
+            }
+        )
+    }
+
+    #[test]
+    fn tracks_node_spans() {
+        let document = parse(
+            r#"type Person {
+  name: String
+}"#,
+        )
+        .unwrap();
+        match &document.definitions[0] {
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                TypeDefinitionNode::Object(object),
+            )) => {
+                assert!(object
+                    .span
+                    .is_identical(&Span::new(Location::new(0, 1, 1), Location::new(30, 3, 2))));
+                assert!(object.fields[0].span.is_identical(&Span::new(
+                    Location::new(16, 2, 3),
+                    Location::new(28, 2, 15)
+                )));
+            }
+            _ => panic!("Expected an object type definition"),
+        }
+    }
+
+    #[test]
+    fn keeps_numbers_beyond_i64() {
+        let document = parse(
+            r#"type Query {
+  total(limit: BigUint = 340282366920938463463374607431768211455, ratio: Float = 1.5E-3): Int
+}"#,
+        )
+        .unwrap();
+        match &document.definitions[0] {
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                TypeDefinitionNode::Object(object),
+            )) => {
+                let arguments = object.fields[0].arguments.as_ref().unwrap();
+                match &arguments[0].default_value {
+                    Some(ValueNode::Int(int)) => {
+                        assert_eq!(int.as_i64(), None);
+                        assert_eq!(int.as_u128(), Some(u128::MAX));
+                    }
+                    _ => panic!("Expected an int default value"),
+                }
+                match &arguments[1].default_value {
+                    Some(ValueNode::Float(float)) => {
+                        assert_eq!(float.raw, "1.5E-3");
+                        assert_eq!(float.as_f64(), Some(0.0015));
+                    }
+                    _ => panic!("Expected a float default value"),
+                }
+            }
+            _ => panic!("Expected an object type definition"),
+        }
+    }
+
+    #[test]
+    fn recovers_from_errors_in_each_definition() {
+        let (document, errors) = parse_with_recovery(
+            r#"type Person {
+  name: String
+  friends(first: ): [Person]
+}
+
+"A type with no fields"
+type Empty {}
+
+enum Color { RED GREEN }
+
+union = Person
+
+query {
+  person(id: ) { name }
+}
+
+type Photo {
+  url: String
+}"#,
+        );
+        assert_eq!(errors.len(), 4);
+        assert!(matches!(errors[0], ParseError::UnexpectedToken { .. }));
+        assert!(matches!(errors[1], ParseError::ObjectEmpty(_)));
+        assert_eq!(document.definitions.len(), 2);
+        match &document.definitions[1] {
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                TypeDefinitionNode::Object(object),
+            )) => assert_eq!(object.name.value, "Photo"),
+            _ => panic!("Expected the Photo type to be parsed"),
+        }
+    }
+
+    #[test]
+    fn recovery_skips_unknown_keywords() {
+        let (document, errors) = parse_with_recovery("typo Person { name: String } scalar Date");
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ParseError::UnexpectedKeyword { .. }));
+        assert_eq!(document.definitions.len(), 1);
+    }
+
+    #[test]
+    fn recovery_resumes_after_an_unclosed_body() {
+        let (document, errors) =
+            parse_with_recovery("type Person {\n  name: String\n\ntype Photo { url: String }");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(document.definitions.len(), 1);
+        match &document.definitions[0] {
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                TypeDefinitionNode::Object(object),
+            )) => assert_eq!(object.name.value, "Photo"),
+            _ => panic!("Expected the Photo type to be parsed"),
+        }
+
+        let (document, errors) =
+            parse_with_recovery("query { person(id: 1 { name }\nscalar Date\nscalar Url");
+        assert_eq!(errors.len(), 1);
+        assert_eq!(document.definitions.len(), 2);
+    }
+
+    #[test]
+    fn recovery_stops_at_lex_errors() {
+        let (document, errors) = parse_with_recovery("scalar Date\nscalar % Time\nscalar Url");
+        assert_eq!(document.definitions.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], ParseError::LexError(_)));
+    }
+
+    #[test]
+    fn recovery_reports_empty_documents() {
+        let (document, errors) = parse_with_recovery("");
+        assert!(document.definitions.is_empty());
+        assert_eq!(errors, vec![ParseError::DocumentEmpty]);
+    }
 }
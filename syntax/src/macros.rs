@@ -31,6 +31,7 @@ macro_rules! gql {
 mod tests {
     use crate::document::Document;
     use crate::nodes::*;
+    use crate::token::Span;
 
     #[test]
     fn it_parses() {
@@ -50,13 +51,16 @@ mod tests {
                         QueryDefinitionNode {
                             name: None,
                             variables: None,
+                            directives: None,
                             selections: vec![Selection::Field(FieldNode {
                                 name: NameNode::from("user"),
                                 alias: None,
                                 arguments: None,
                                 directives: None,
-                                selections: Some(vec![Selection::Field(FieldNode::from("name")),])
-                            })]
+                                selections: Some(vec![Selection::Field(FieldNode::from("name")),]),
+                                span: Span::ignored(),
+                            })],
+                            span: Span::ignored(),
                         }
                     ))
                 )]
@@ -0,0 +1,395 @@
+//! Folds type system extensions into the definitions they extend.
+
+use crate::document::Document;
+use crate::error::ValidationError;
+use crate::nodes::schema_extension::SchemaExtensionNode;
+use crate::nodes::*;
+use crate::validation::{self, ValidExtensionNode, ValidationResult};
+
+lazy_static! {
+    /// The types every schema defines, such as `String`.
+    static ref BUILTIN_TYPES: Vec<String> = Document::default()
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(type_definition)) => {
+                Some(type_definition.name().value.clone())
+            }
+            _ => None,
+        })
+        .collect();
+}
+
+/// Merges documents into one, applying every extension to its base definition. Extensions
+/// may come before or after the definition they extend and are applied in the order they
+/// appear. Every extension that cannot be applied is reported.
+///
+/// The built-in types are only defined by a schema, so extensions of them are kept at the end
+/// of the merged document for the schema to apply.
+pub(crate) fn merge(documents: Vec<Document>) -> Result<Document, Vec<ValidationError>> {
+    let mut definitions = Vec::new();
+    let mut builtin_extensions = Vec::new();
+    let mut extensions = Vec::new();
+    for document in documents {
+        for definition in document.definitions {
+            match definition {
+                DefinitionNode::Extension(extension) => extensions.push(extension),
+                definition => definitions.push(definition),
+            }
+        }
+    }
+
+    let mut errors = Vec::new();
+    for extension in extensions {
+        let result = match extension {
+            TypeSystemExtensionNode::Schema(extension) => {
+                let schema = definitions
+                    .iter_mut()
+                    .find_map(|definition| match definition {
+                        DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Schema(schema)) => {
+                            Some(schema)
+                        }
+                        _ => None,
+                    });
+                extend_schema(schema, extension)
+            }
+            extension => {
+                let name = extension.name().map(|name| name.value.clone());
+                let type_definition =
+                    definitions
+                        .iter_mut()
+                        .find_map(|definition| match definition {
+                            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                                type_definition,
+                            )) if Some(&type_definition.name().value) == name.as_ref() => {
+                                Some(type_definition)
+                            }
+                            _ => None,
+                        });
+                if type_definition.is_none()
+                    && matches!(name, Some(name) if BUILTIN_TYPES.contains(&name))
+                {
+                    builtin_extensions.push(DefinitionNode::Extension(extension));
+                    continue;
+                }
+                extend_type(type_definition, extension)
+            }
+        };
+        if let Err(error) = result {
+            errors.push(error);
+        }
+    }
+
+    if errors.is_empty() {
+        definitions.extend(builtin_extensions);
+        Ok(Document::new(definitions))
+    } else {
+        Err(errors)
+    }
+}
+
+/// What a definition defines, so a newer definition of the same thing can replace it.
+#[derive(PartialEq)]
+enum Defines {
+    Schema,
+    Type(String),
+    Directive(String),
+}
+
+fn defines(definition: &DefinitionNode) -> Option<Defines> {
+    match definition {
+        DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Schema(_)) => Some(Defines::Schema),
+        DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(type_definition)) => {
+            Some(Defines::Type(type_definition.name().value.clone()))
+        }
+        DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Directive(directive)) => {
+            Some(Defines::Directive(directive.name.value.clone()))
+        }
+        _ => None,
+    }
+}
+
+/// Applies a document of changes to a merged document. A definition in `changes` replaces the
+/// definition of the same type, directive or schema in `document`, and extensions are merged as
+/// [`merge`] does.
+pub(crate) fn update(
+    document: Document,
+    changes: Document,
+) -> Result<Document, Vec<ValidationError>> {
+    let replaced: Vec<Defines> = changes.definitions.iter().filter_map(defines).collect();
+    let kept = document
+        .definitions
+        .into_iter()
+        .filter(|definition| !matches!(defines(definition), Some(kind) if replaced.contains(&kind)))
+        .collect();
+    merge(vec![Document::new(kept), changes])
+}
+
+fn append<T>(target: &mut Option<Vec<T>>, items: Option<Vec<T>>) {
+    if let Some(items) = items {
+        target.get_or_insert_with(Vec::new).extend(items);
+    }
+}
+
+fn names(types: &Option<Vec<NamedTypeNode>>) -> Vec<&str> {
+    types
+        .iter()
+        .flatten()
+        .map(|named_type| named_type.name.value.as_str())
+        .collect()
+}
+
+/// Adds the directives and root operation types of an extension to the schema definition.
+pub(crate) fn extend_schema(
+    schema: Option<&mut SchemaDefinitionNode>,
+    extension: SchemaExtensionNode,
+) -> ValidationResult {
+    let schema = match schema {
+        Some(schema) => schema,
+        None => return extension.validate_extension(None),
+    };
+    extension.validate_extension(Some(schema))?;
+    append(&mut schema.directives, extension.directives);
+    schema
+        .operations
+        .extend(extension.operations.into_iter().flatten());
+    Ok(())
+}
+
+/// Adds the directives, interfaces, fields, members or values of an extension to the type it
+/// extends. The extension cannot redefine anything the type already defines and has to be of
+/// the same kind as the type.
+pub(crate) fn extend_type(
+    type_definition: Option<&mut TypeDefinitionNode>,
+    extension: TypeSystemExtensionNode,
+) -> ValidationResult {
+    match (type_definition, extension) {
+        (_, TypeSystemExtensionNode::Schema(extension)) => extend_schema(None, extension),
+        (Some(TypeDefinitionNode::Scalar(scalar)), TypeSystemExtensionNode::Scalar(extension)) => {
+            extension.validate_extension(Some(scalar))?;
+            append(&mut scalar.directives, extension.directives);
+            Ok(())
+        }
+        (Some(TypeDefinitionNode::Object(object)), TypeSystemExtensionNode::Object(extension)) => {
+            extension.validate_extension(Some(object))?;
+            validation::validate_extension_names_against_original(
+                "interface",
+                &names(&extension.interfaces),
+                &names(&object.interfaces),
+                extension.span.start,
+            )?;
+            append(&mut object.interfaces, extension.interfaces);
+            append(&mut object.directives, extension.directives);
+            object.fields.extend(extension.fields.into_iter().flatten());
+            Ok(())
+        }
+        (
+            Some(TypeDefinitionNode::Interface(interface)),
+            TypeSystemExtensionNode::Interface(extension),
+        ) => {
+            extension.validate_extension(Some(interface))?;
+            validation::validate_extension_names_against_original(
+                "interface",
+                &names(&extension.interfaces),
+                &names(&interface.interfaces),
+                extension.span.start,
+            )?;
+            append(&mut interface.interfaces, extension.interfaces);
+            append(&mut interface.directives, extension.directives);
+            interface
+                .fields
+                .extend(extension.fields.into_iter().flatten());
+            Ok(())
+        }
+        (Some(TypeDefinitionNode::Union(union)), TypeSystemExtensionNode::Union(extension)) => {
+            extension.validate_extension(Some(union))?;
+            append(&mut union.directives, extension.directives);
+            union.types.extend(extension.types.into_iter().flatten());
+            Ok(())
+        }
+        (Some(TypeDefinitionNode::Enum(enum_type)), TypeSystemExtensionNode::Enum(extension)) => {
+            extension.validate_extension(Some(enum_type))?;
+            append(&mut enum_type.directives, extension.directives);
+            enum_type
+                .values
+                .extend(extension.values.into_iter().flatten());
+            Ok(())
+        }
+        (Some(TypeDefinitionNode::Input(input)), TypeSystemExtensionNode::Input(extension)) => {
+            extension.validate_extension(Some(input))?;
+            append(&mut input.directives, extension.directives);
+            input.fields.extend(extension.fields.into_iter().flatten());
+            Ok(())
+        }
+        (Some(type_definition), extension) => Err(ValidationError::new(
+            format!(
+                "Invalid {} Extension {}: {} is {}",
+                extension_kind(&extension),
+                type_definition.name().value,
+                type_definition.name().value,
+                definition_kind(type_definition)
+            )
+            .as_str(),
+        )
+        .with_location(DefinitionNode::Extension(extension).span().start)),
+        (None, TypeSystemExtensionNode::Scalar(extension)) => extension.validate_extension(None),
+        (None, TypeSystemExtensionNode::Object(extension)) => extension.validate_extension(None),
+        (None, TypeSystemExtensionNode::Interface(extension)) => extension.validate_extension(None),
+        (None, TypeSystemExtensionNode::Union(extension)) => extension.validate_extension(None),
+        (None, TypeSystemExtensionNode::Enum(extension)) => extension.validate_extension(None),
+        (None, TypeSystemExtensionNode::Input(extension)) => extension.validate_extension(None),
+    }
+}
+
+fn extension_kind(extension: &TypeSystemExtensionNode) -> &'static str {
+    match extension {
+        TypeSystemExtensionNode::Schema(_) => "Schema",
+        TypeSystemExtensionNode::Scalar(_) => "Scalar",
+        TypeSystemExtensionNode::Object(_) => "Object",
+        TypeSystemExtensionNode::Interface(_) => "Interface",
+        TypeSystemExtensionNode::Union(_) => "Union",
+        TypeSystemExtensionNode::Enum(_) => "Enum",
+        TypeSystemExtensionNode::Input(_) => "Input",
+    }
+}
+
+fn definition_kind(type_definition: &TypeDefinitionNode) -> &'static str {
+    match type_definition {
+        TypeDefinitionNode::Scalar(_) => "a scalar",
+        TypeDefinitionNode::Object(_) => "an object",
+        TypeDefinitionNode::Interface(_) => "an interface",
+        TypeDefinitionNode::Union(_) => "a union",
+        TypeDefinitionNode::Enum(_) => "an enum",
+        TypeDefinitionNode::Input(_) => "an input",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn merged(sources: &[&str]) -> Result<String, Vec<String>> {
+        let documents = sources
+            .iter()
+            .map(|source| parse(source).unwrap())
+            .collect();
+        merge(documents)
+            .map(|document| document.to_string())
+            .map_err(|errors| errors.into_iter().map(|error| error.message).collect())
+    }
+
+    #[test]
+    fn folds_extensions_into_their_definitions() {
+        let expected = merged(&[r#"schema @live {
+  query: Query
+  mutation: Mutation
+}
+
+type Query implements Node & Named @cached {
+  id: ID!
+  name: String
+}
+
+interface Node {
+  id: ID!
+}
+
+interface Named {
+  name: String
+}
+
+enum Color {
+  RED
+  GREEN
+}
+
+union Result = Query | Mutation
+
+input Filter @oneOf @trim {
+  name: String
+  id: ID
+}
+
+type Mutation {
+  noop: Int
+}"#]);
+        assert_eq!(
+            merged(&[
+                r#"extend type Query implements Named { name: String }
+schema { query: Query }
+type Query implements Node { id: ID! }
+interface Node { id: ID! }
+interface Named { name: String }
+enum Color { RED }
+union Result = Query
+input Filter @oneOf { name: String }"#,
+                r#"extend schema @live { mutation: Mutation }
+extend type Query @cached
+extend enum Color { GREEN }
+extend union Result = Mutation
+extend input Filter @trim { id: ID }
+type Mutation { noop: Int }"#,
+            ]),
+            expected
+        );
+    }
+
+    #[test]
+    fn keeps_extensions_of_builtin_types() {
+        assert_eq!(
+            merged(&[
+                "extend scalar String @trim",
+                "scalar Url\nextend scalar Url @format",
+            ]),
+            Ok(String::from(
+                "scalar Url @format\n\nextend scalar String @trim\n"
+            ))
+        );
+        assert_eq!(
+            merged(&["scalar String\nextend scalar String @trim"]),
+            Ok(String::from("scalar String @trim\n"))
+        );
+    }
+
+    #[test]
+    fn replaces_definitions_when_updating() {
+        let document = merge(vec![parse(
+            "type Query { user: User }\ntype User { id: ID }\nextend type User { name: String }",
+        )
+        .unwrap()])
+        .unwrap();
+        let updated = update(
+            document,
+            parse("type User { id: ID! }\nextend type Query { users: [User] }").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            updated.to_string(),
+            "type Query {\n  user: User\n  users: [User]\n}\n\ntype User {\n  id: ID!\n}\n"
+        );
+    }
+
+    #[test]
+    fn reports_conflicting_extensions() {
+        assert_eq!(
+            merged(&[r#"type Query implements Node { id: ID! }
+interface Node { id: ID! }
+enum Color { RED }
+extend type Query { id: ID }
+extend type Query implements Node
+extend enum Color { RED }
+extend input Color { value: Int }
+extend type Missing { id: ID }
+extend schema { query: Query }"#]),
+            Err(vec![
+                String::from("Invalid Extension: Cannot redefine field(s) id"),
+                String::from("Invalid Extension: Cannot redefine interface(s) Node"),
+                String::from("Invalid Extension: Cannot redefine value(s) RED"),
+                String::from("Invalid Input Extension Color: Color is an enum"),
+                String::from("Invalid Object Extension Missing: No type of name Missing in schema"),
+                String::from("Invalid Schema Extension: No schema definition in document"),
+            ])
+        );
+    }
+}
@@ -1,33 +1,67 @@
+//! The nodes of a parsed GraphQL document.
+//!
+//! A [`Document`] is a list of [`DefinitionNode`]s, each the root of a tree of the nodes in this
+//! module. Nodes keep the [`Span`] of the source they were parsed from, and nodes built in code
+//! use [`Span::ignored`]. Use a [`Visitor`] to walk a tree of nodes.
+//!
+//! [`Document`]: ../document/struct.Document.html
+//! [`DefinitionNode`]: enum.DefinitionNode.html
+//! [`Span`]: ../token/struct.Span.html
+//! [`Span::ignored`]: ../token/struct.Span.html#method.ignored
+//! [`Visitor`]: ../visitor/trait.Visitor.html
+
 use crate::error::{ParseError, ParseResult, ValidationError};
-use crate::token::Token;
+use crate::strings;
+use crate::token::{Span, Token, TokenKind};
 use crate::validation::{self, ValidExtensionNode, ValidNode, ValidationResult};
 use std::convert::TryFrom;
 use std::sync::Arc;
 
+pub mod enum_type_extension;
+pub mod input_type_extension;
+pub mod interface_type_extension;
 pub mod object_type_extension;
-use object_type_extension::ObjectTypeExtensionNode;
-
+pub mod scalar_type_extension;
+pub mod schema_extension;
+pub mod union_type_extension;
+pub use enum_type_extension::EnumTypeExtensionNode;
+pub use input_type_extension::InputTypeExtensionNode;
+pub use interface_type_extension::InterfaceTypeExtensionNode;
+pub use object_type_extension::ObjectTypeExtensionNode;
+pub use scalar_type_extension::ScalarTypeExtensionNode;
+pub use schema_extension::SchemaExtensionNode;
+pub use union_type_extension::UnionTypeExtensionNode;
+
+/// A node that defines fields, such as an object or an interface.
+#[allow(dead_code)]
 pub trait NodeWithFields {
+    /// The fields the node defines.
     fn get_fields(&self) -> &[FieldDefinitionNode] {
         &[]
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// A name, such as the name of a type, field or argument.
+#[derive(Debug, Clone, PartialEq)]
 pub struct NameNode {
+    /// The name as written.
     pub value: String,
+    /// Where the name was written in the source.
+    pub span: Span,
 }
 impl NameNode {
     /// Generates a new name node from the token.
     /// If the token is not of type Token::Name,
     /// an error is thrown
     pub fn new(token: Token) -> ParseResult<NameNode> {
+        let span = Span::from_token(&token);
         match token {
             Token::Name(_, value) => Ok(NameNode {
                 value: value.to_owned(),
+                span,
             }),
             _ => Err(ParseError::UnexpectedToken {
-                expected: "Token<Name>".into(),
+                expected: vec![TokenKind::Name],
                 received: token.to_string(),
                 location: token.location(),
             }),
@@ -39,6 +73,7 @@ impl From<&str> for NameNode {
     fn from(name: &str) -> NameNode {
         NameNode {
             value: String::from(name),
+            span: Span::ignored(),
         }
     }
 }
@@ -46,12 +81,14 @@ impl From<&str> for NameNode {
 impl<'a> TryFrom<Token<'a>> for NameNode {
     type Error = ParseError;
     fn try_from(token: Token<'a>) -> Result<Self, Self::Error> {
+        let span = Span::from_token(&token);
         match token {
             Token::Name(_, value) => Ok(NameNode {
                 value: value.to_owned(),
+                span,
             }),
             _ => Err(ParseError::UnexpectedToken {
-                expected: "Token<Name>".into(),
+                expected: vec![TokenKind::Name],
                 received: token.to_string(),
                 location: token.location(),
             }),
@@ -59,42 +96,71 @@ impl<'a> TryFrom<Token<'a>> for NameNode {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// A string or block string, with its escape sequences decoded and the common
+/// indentation of a block string removed.
+#[derive(Debug, Clone)]
 pub struct StringValueNode {
+    /// The content of the string.
     pub value: String,
     block: bool,
+    /// Where the string was written in the source.
+    pub span: Span,
+}
+
+/// Whether a string was written as a block string is only presentation, so it is ignored when
+/// comparing nodes.
+impl PartialEq for StringValueNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
 }
 
 impl StringValueNode {
+    /// Creates a string from a string or block string token, decoding its content.
     pub fn new(token: Token) -> ParseResult<StringValueNode> {
+        let span = Span::from_token(&token);
         match token {
-            Token::Str(_, val) => Ok(StringValueNode {
-                value: val.to_owned(),
+            Token::Str(location, val) => Ok(StringValueNode {
+                value: strings::decode_string(val, location).map_err(ParseError::LexError)?,
                 block: false,
+                span,
             }),
             Token::BlockStr(_, val) => Ok(StringValueNode {
-                value: val.to_owned(),
+                value: strings::block_string_value(val),
                 block: true,
+                span,
             }),
             _ => Err(ParseError::UnexpectedToken {
-                expected: "Token<Str> or Token<BlockStr>".into(),
+                expected: vec![TokenKind::Str, TokenKind::BlockStr],
                 received: token.to_string(),
                 location: token.location(),
             }),
         }
     }
 
+    /// Creates a string with the given content, to be printed as a block string if
+    /// `block` is set.
     pub fn from(content: &str, block: bool) -> StringValueNode {
         StringValueNode {
             value: content.into(),
             block,
+            span: Span::ignored(),
         }
     }
+
+    /// Whether the string was written as a block string.
+    pub fn is_block(&self) -> bool {
+        self.block
+    }
 }
 
-#[derive(Debug, PartialEq)]
+/// A reference to a type by its name.
+#[derive(Debug, Clone, PartialEq)]
 pub struct NamedTypeNode {
+    /// The name of the type.
     pub name: NameNode,
+    /// Where the type reference was written in the source.
+    pub span: Span,
 }
 
 impl NamedTypeNode {
@@ -102,16 +168,17 @@ impl NamedTypeNode {
     /// NameNode will throw an error if the token is not
     /// of type Token::Name
     pub fn new(tok: Token) -> ParseResult<NamedTypeNode> {
-        Ok(NamedTypeNode {
-            name: NameNode::try_from(tok)?,
-        })
+        NamedTypeNode::try_from(tok)
     }
 }
 
 impl From<&str> for NamedTypeNode {
     /// Used for internal testing.
     fn from(name: &str) -> NamedTypeNode {
-        NamedTypeNode { name: name.into() }
+        NamedTypeNode {
+            name: name.into(),
+            span: Span::ignored(),
+        }
     }
 }
 
@@ -119,39 +186,70 @@ impl<'a> TryFrom<Token<'a>> for NamedTypeNode {
     type Error = ParseError;
     fn try_from(token: Token<'a>) -> Result<Self, Self::Error> {
         let name = NameNode::try_from(token)?;
-        Ok(NamedTypeNode { name })
+        Ok(NamedTypeNode {
+            span: name.span,
+            name,
+        })
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// A list type, such as `[String]`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ListTypeNode {
+    /// The type of the items of the list.
     pub list_type: Arc<TypeNode>,
+    /// Where the list type was written in the source.
+    pub span: Span,
 }
 
 impl ListTypeNode {
+    /// Creates a list of `list_type`.
     pub fn new(list_type: TypeNode) -> ListTypeNode {
         ListTypeNode {
             list_type: Arc::new(list_type),
+            span: Span::ignored(),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// The type of a field, argument or variable.
+#[derive(Debug, Clone, PartialEq)]
 pub enum TypeNode {
+    /// A type referred to by its name.
     Named(NamedTypeNode),
+    /// A list of another type.
     List(ListTypeNode),
+    /// A type that cannot be null, written with a trailing `!`.
     NonNull(Arc<TypeNode>),
 }
 
-#[derive(Debug, PartialEq)]
+impl TypeNode {
+    /// The named type inside of any list and non-null wrappers.
+    pub fn named_type(&self) -> &NamedTypeNode {
+        match self {
+            TypeNode::Named(named) => named,
+            TypeNode::List(list) => list.list_type.named_type(),
+            TypeNode::NonNull(inner) => inner.named_type(),
+        }
+    }
+}
+
+/// A variable, such as `$id`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct VariableNode {
+    /// The name of the variable, without the `$`.
     pub name: NameNode,
+    /// Where the variable was written in the source.
+    pub span: Span,
 }
 
 impl VariableNode {
+    /// Creates a variable from the name token that follows the `$`.
     pub fn new(tok: Token) -> ParseResult<Self> {
+        let name = NameNode::new(tok)?;
         Ok(Self {
-            name: NameNode::new(tok)?,
+            span: name.span,
+            name,
         })
     }
 }
@@ -160,84 +258,185 @@ impl From<&str> for VariableNode {
     fn from(name: &str) -> Self {
         Self {
             name: NameNode::from(name),
+            span: Span::ignored(),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// Keeps the integer as it was written, so values up to a u128 are not lost. Use the `as_*`
+/// methods to convert it to the size of number that is needed.
+#[derive(Debug, Clone, PartialEq)]
 pub struct IntValueNode {
-    pub value: i64,
+    /// The integer as it was written.
+    pub raw: String,
+    /// Where the integer was written in the source.
+    pub span: Span,
+}
+
+impl IntValueNode {
+    /// The integer as an `i64`, if it fits in one.
+    pub fn as_i64(&self) -> Option<i64> {
+        self.raw.parse().ok()
+    }
+
+    /// The integer as an `i128`, if it fits in one.
+    pub fn as_i128(&self) -> Option<i128> {
+        self.raw.parse().ok()
+    }
+
+    /// The integer as a `u128`, if it fits in one.
+    pub fn as_u128(&self) -> Option<u128> {
+        self.raw.parse().ok()
+    }
+}
+
+impl From<i64> for IntValueNode {
+    /// Used for internal testing.
+    fn from(value: i64) -> Self {
+        Self {
+            raw: value.to_string(),
+            span: Span::ignored(),
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+/// Keeps the float as it was written, including any exponent.
+#[derive(Debug, Clone, PartialEq)]
 pub struct FloatValueNode {
-    pub value: f64,
+    /// The number as it was written.
+    pub raw: String,
+    /// Where the number was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+impl FloatValueNode {
+    /// The number as an `f64`.
+    pub fn as_f64(&self) -> Option<f64> {
+        self.raw.parse().ok()
+    }
+}
+
+impl From<f64> for FloatValueNode {
+    /// Used for internal testing.
+    fn from(value: f64) -> Self {
+        Self {
+            raw: format!("{:?}", value),
+            span: Span::ignored(),
+        }
+    }
+}
+
+/// A `true` or `false` value.
+#[derive(Debug, Clone, PartialEq)]
 pub struct BooleanValueNode {
+    /// Whether the value is `true`.
     pub value: bool,
+    /// Where the boolean was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// An enum value, such as `RED`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnumValueNode {
+    /// The name of the enum value.
     pub value: String,
+    /// Where the enum value was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// A list value, such as `[1, 2, 3]`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ListValueNode {
+    /// The items of the list.
     pub values: Vec<ValueNode>,
+    /// Where the list was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// A field of an input object value.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectFieldNode {
+    /// The name of the field.
     pub name: NameNode,
+    /// The value of the field.
     pub value: ValueNode,
+    /// Where the field was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// An input object value, such as `{ x: 1, y: 2 }`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectValueNode {
+    /// The fields of the object.
     pub fields: Vec<ObjectFieldNode>,
+    /// Where the object was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// A value passed as an argument or given as a default.
+#[derive(Debug, Clone, PartialEq)]
 pub enum ValueNode {
+    /// A variable, whose value is given with the request.
     Variable(VariableNode),
+    /// An integer.
     Int(IntValueNode),
+    /// A floating point number.
     Float(FloatValueNode),
+    /// A string or block string.
     Str(StringValueNode),
+    /// `true` or `false`.
     Bool(BooleanValueNode),
+    /// `null`.
     Null,
+    /// An enum value.
     Enum(EnumValueNode),
+    /// A list of values.
     List(ListValueNode),
+    /// An input object.
     Object(ObjectValueNode),
 }
 
-#[derive(Debug, PartialEq)]
+/// A directive applied to part of a document, such as `@skip(if: true)`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct DirectiveNode {
+    /// The name of the directive, without the `@`.
     pub name: NameNode,
+    /// The arguments passed to the directive.
     pub arguments: Option<Arguments>,
+    /// Where the directive was written in the source.
+    pub span: Span,
 }
 
 impl DirectiveNode {
+    /// Creates a directive from its name token and arguments.
     pub fn new(name: Token, arguments: Option<Arguments>) -> ParseResult<DirectiveNode> {
         Ok(DirectiveNode {
             name: NameNode::new(name)?,
             arguments,
+            span: Span::ignored(),
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// The definition of an argument or of a field of an input object.
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputValueDefinitionNode {
+    /// The description of the argument or input field, if it has one.
     pub description: Description,
+    /// The name of the argument or input field.
     pub name: NameNode,
+    /// The type of the values it accepts.
     pub input_type: TypeNode,
+    /// The value used when none is given.
     pub default_value: Option<ValueNode>,
+    /// The directives applied to the argument or input field.
     pub directives: Option<Directives>,
+    /// Where the argument or input field was written in the source.
+    pub span: Span,
 }
 
 impl InputValueDefinitionNode {
+    /// Creates a definition from its name token, type and description.
     pub fn new(
         name: Token,
         input_type: TypeNode,
@@ -249,49 +448,78 @@ impl InputValueDefinitionNode {
             input_type,
             default_value: None,
             directives: None,
+            span: Span::ignored(),
         })
     }
 
+    /// Sets the value used when none is given.
     pub fn with_default_value(&mut self, default_value: Option<ValueNode>) -> &mut Self {
         self.default_value = default_value;
         self
     }
 
+    /// Sets the directives applied to the argument or input field.
     pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
         self.directives = directives;
         self
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// A variable defined by an operation, such as `$first: Int = 10`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct VariableDefinitionNode {
+    /// The variable being defined.
     pub variable: VariableNode,
+    /// The type of the variable.
     pub variable_type: TypeNode,
+    /// The value used when the request does not give one.
     pub default_value: Option<ValueNode>,
+    /// Where the variable definition was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// An argument passed to a field or directive.
+#[derive(Debug, Clone, PartialEq)]
 pub struct Argument {
+    /// The name of the argument.
     pub name: NameNode,
+    /// The value of the argument.
     pub value: ValueNode,
+    /// Where the argument was written in the source.
+    pub span: Span,
 }
 
+/// The description written before a definition, if it has one.
 pub type Description = Option<StringValueNode>;
+/// The arguments passed to a field or directive.
 pub type Arguments = Vec<Argument>;
+/// The arguments that a field or directive accepts.
 pub type ArgumentDefinitions = Vec<InputValueDefinitionNode>;
+/// The directives applied to part of a document.
 pub type Directives = Vec<DirectiveNode>;
+/// The variables defined by an operation.
 pub type Variables = Vec<VariableDefinitionNode>;
 
-#[derive(Debug, PartialEq)]
+/// The definition of a field of an object or interface.
+#[derive(Debug, Clone, PartialEq)]
 pub struct FieldDefinitionNode {
+    /// The description of the field, if it has one.
     pub description: Description,
+    /// The name of the field.
     pub name: NameNode,
+    /// The arguments the field accepts.
     pub arguments: Option<ArgumentDefinitions>,
+    /// The type of the value of the field.
     pub field_type: TypeNode,
-    // directives: Vec<DirectiveDefinitionNode>,
+    /// The directives applied to the field.
+    pub directives: Option<Directives>,
+    /// Where the field was written in the source.
+    pub span: Span,
 }
 
 impl FieldDefinitionNode {
+    /// Creates a field definition from its name token, type, description and
+    /// arguments.
     pub fn new(
         name: Token,
         field_type: TypeNode,
@@ -303,18 +531,34 @@ impl FieldDefinitionNode {
             name: NameNode::new(name)?,
             arguments,
             field_type,
+            directives: None,
+            span: Span::ignored(),
         })
     }
+
+    /// Sets the directives applied to the field.
+    pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
+        self.directives = directives;
+        self
+    }
 }
 
-#[derive(Debug, PartialEq)]
+/// The definition of one of the values of an enum.
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnumValueDefinitionNode {
+    /// The description of the enum value, if it has one.
     pub description: Description,
+    /// The name of the enum value.
     pub name: NameNode,
+    /// The directives applied to the enum value.
     pub directives: Option<Directives>,
+    /// Where the enum value was written in the source.
+    pub span: Span,
 }
 
 impl EnumValueDefinitionNode {
+    /// Creates an enum value definition from its name token, description and
+    /// directives.
     pub fn new(
         name: Token,
         description: Description,
@@ -324,56 +568,89 @@ impl EnumValueDefinitionNode {
             description,
             name: NameNode::new(name)?,
             directives,
+            span: Span::ignored(),
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// The kinds of operation.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Operation {
+    /// A read-only fetch.
     Query,
+    /// A write followed by a fetch.
     Mutation,
+    /// A fetch in response to each of a stream of events.
     Subscription,
 }
 
-#[derive(Debug, PartialEq)]
+/// A root operation type of a schema, such as `query: Query`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct OperationTypeDefinitionNode {
+    /// The kind of operation that starts from the type.
     pub operation: Operation,
+    /// The root type.
     pub node_type: NamedTypeNode,
+    /// Where the root operation type was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// A schema definition, which names the root operation types.
+#[derive(Debug, Clone, PartialEq)]
 pub struct SchemaDefinitionNode {
+    /// The description of the schema, if it has one.
     pub description: Description,
+    /// The directives applied to the schema.
     pub directives: Option<Directives>,
+    /// The root operation types.
     pub operations: Vec<OperationTypeDefinitionNode>,
+    /// Where the schema was written in the source.
+    pub span: Span,
 }
 impl SchemaDefinitionNode {
+    /// Creates a schema definition without any root operation types.
     pub fn new() -> SchemaDefinitionNode {
         SchemaDefinitionNode {
             description: None,
             directives: None,
             operations: vec![],
+            span: Span::ignored(),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl Default for SchemaDefinitionNode {
+    fn default() -> Self {
+        SchemaDefinitionNode::new()
+    }
+}
+
+/// The definition of a scalar type.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ScalarTypeDefinitionNode {
+    /// The description of the scalar, if it has one.
     pub description: Description,
+    /// The name of the scalar.
     pub name: NameNode,
+    /// The directives applied to the scalar.
     pub directives: Option<Directives>,
+    /// Where the scalar was written in the source.
+    pub span: Span,
 }
 
 impl ScalarTypeDefinitionNode {
+    /// Creates a scalar definition from its name token and description.
     pub fn new(tok: Token, description: Description) -> ParseResult<ScalarTypeDefinitionNode> {
         let name = NameNode::new(tok)?;
         Ok(ScalarTypeDefinitionNode {
             description,
             name,
             directives: None,
+            span: Span::ignored(),
         })
     }
 
+    /// Sets the directives applied to the scalar.
     pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
         self.directives = directives;
         self
@@ -386,20 +663,31 @@ impl From<&str> for ScalarTypeDefinitionNode {
             name: NameNode::from(name),
             description: None,
             directives: None,
+            span: Span::ignored(),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// The definition of an object type.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectTypeDefinitionNode {
+    /// The description of the object, if it has one.
     pub description: Description,
+    /// The name of the object.
     pub name: NameNode,
+    /// The interfaces the object implements.
     pub interfaces: Option<Vec<NamedTypeNode>>,
+    /// The directives applied to the object.
     pub directives: Option<Directives>,
+    /// The fields of the object.
     pub fields: Vec<FieldDefinitionNode>,
+    /// Where the object was written in the source.
+    pub span: Span,
 }
 
 impl ObjectTypeDefinitionNode {
+    /// Creates an object definition from its name token, description and fields,
+    /// failing when there are no fields.
     pub fn new(
         tok: Token,
         description: Description,
@@ -412,22 +700,26 @@ impl ObjectTypeDefinitionNode {
                 interfaces: None,
                 directives: None,
                 fields,
+                span: Span::ignored(),
             })
         } else {
             Err(ParseError::ObjectEmpty(tok.location()))
         }
     }
 
+    /// Sets the interfaces the object implements.
     pub fn with_interfaces(&mut self, interfaces: Option<Vec<NamedTypeNode>>) -> &mut Self {
         self.interfaces = interfaces;
         self
     }
 
+    /// Sets the directives applied to the object.
     pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
         self.directives = directives;
         self
     }
 
+    /// Sets the fields of the object.
     pub fn with_fields(&mut self, fields: Vec<FieldDefinitionNode>) -> &mut Self {
         self.fields = fields;
         self
@@ -440,65 +732,119 @@ impl NodeWithFields for ObjectTypeDefinitionNode {
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// The definition of an input object type.
+#[derive(Debug, Clone, PartialEq)]
 pub struct InputTypeDefinitionNode {
+    /// The description of the input object, if it has one.
     pub description: Description,
+    /// The name of the input object.
     pub name: NameNode,
+    /// The directives applied to the input object.
+    pub directives: Option<Directives>,
+    /// The fields of the input object.
     pub fields: Vec<InputValueDefinitionNode>,
+    /// Where the input object was written in the source.
+    pub span: Span,
 }
 
 impl InputTypeDefinitionNode {
+    /// Creates an input object definition from its name token and description.
     pub fn new(name_tok: Token, description: Description) -> ParseResult<InputTypeDefinitionNode> {
         Ok(InputTypeDefinitionNode {
             name: NameNode::new(name_tok)?,
             description,
+            directives: None,
             fields: Vec::new(),
+            span: Span::ignored(),
         })
     }
 
+    /// Sets the directives applied to the input object.
+    pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Sets the fields of the input object.
     pub fn with_fields(&mut self, fields: Vec<InputValueDefinitionNode>) -> &mut Self {
         self.fields = fields;
         self
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// The definition of an interface type.
+#[derive(Debug, Clone, PartialEq)]
 pub struct InterfaceTypeDefinitionNode {
+    /// The description of the interface, if it has one.
     pub description: Description,
+    /// The name of the interface.
     pub name: NameNode,
+    /// The interfaces the interface implements.
+    pub interfaces: Option<Vec<NamedTypeNode>>,
+    /// The directives applied to the interface.
     pub directives: Option<Directives>,
+    /// The fields of the interface.
     pub fields: Vec<FieldDefinitionNode>,
+    /// Where the interface was written in the source.
+    pub span: Span,
 }
 
 impl InterfaceTypeDefinitionNode {
+    /// Creates an interface definition from its name token and description.
     pub fn new(tok: Token, description: Description) -> ParseResult<InterfaceTypeDefinitionNode> {
         Ok(InterfaceTypeDefinitionNode {
             name: NameNode::new(tok)?,
             description,
+            interfaces: None,
             directives: None,
             fields: Vec::new(),
+            span: Span::ignored(),
         })
     }
+
+    /// Sets the interfaces the interface implements.
+    pub fn with_interfaces(&mut self, interfaces: Option<Vec<NamedTypeNode>>) -> &mut Self {
+        self.interfaces = interfaces;
+        self
+    }
+
+    /// Sets the fields of the interface.
     pub fn with_fields(&mut self, fields: Vec<FieldDefinitionNode>) -> &mut Self {
         self.fields = fields;
         self
     }
 
+    /// Sets the directives applied to the interface.
     pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
         self.directives = directives;
         self
     }
 }
 
-#[derive(Debug, PartialEq)]
+impl NodeWithFields for InterfaceTypeDefinitionNode {
+    fn get_fields(&self) -> &[FieldDefinitionNode] {
+        &self.fields
+    }
+}
+
+/// The definition of an enum type.
+#[derive(Debug, Clone, PartialEq)]
 pub struct EnumTypeDefinitionNode {
+    /// The description of the enum, if it has one.
     pub description: Description,
+    /// The name of the enum.
     pub name: NameNode,
+    /// The directives applied to the enum.
     pub directives: Option<Directives>,
+    /// The values of the enum.
     pub values: Vec<EnumValueDefinitionNode>,
+    /// Where the enum was written in the source.
+    pub span: Span,
 }
 
 impl EnumTypeDefinitionNode {
+    /// Creates an enum definition from its name token, description, directives and
+    /// values.
     pub fn new(
         tok: Token,
         description: Description,
@@ -510,19 +856,29 @@ impl EnumTypeDefinitionNode {
             name: NameNode::new(tok)?,
             directives,
             values,
+            span: Span::ignored(),
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// The definition of a union type.
+#[derive(Debug, Clone, PartialEq)]
 pub struct UnionTypeDefinitionNode {
+    /// The description of the union, if it has one.
     pub description: Description,
+    /// The name of the union.
     pub name: NameNode,
+    /// The directives applied to the union.
     pub directives: Option<Directives>,
+    /// The object types that are members of the union.
     pub types: Vec<NamedTypeNode>,
+    /// Where the union was written in the source.
+    pub span: Span,
 }
 
 impl UnionTypeDefinitionNode {
+    /// Creates a union definition from its name token, description, directives and
+    /// members.
     pub fn new(
         tok: Token,
         description: Description,
@@ -534,44 +890,255 @@ impl UnionTypeDefinitionNode {
             name: NameNode::new(tok)?,
             directives,
             types,
+            span: Span::ignored(),
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// The definition of a named type.
+#[derive(Debug, Clone, PartialEq)]
 pub enum TypeDefinitionNode {
+    /// A scalar type.
     Scalar(ScalarTypeDefinitionNode),
+    /// An object type.
     Object(ObjectTypeDefinitionNode),
+    /// An interface type.
     Interface(InterfaceTypeDefinitionNode),
+    /// A union type.
     Union(UnionTypeDefinitionNode),
+    /// An enum type.
     Enum(EnumTypeDefinitionNode),
+    /// An input object type.
     Input(InputTypeDefinitionNode),
 }
 
-#[derive(Debug, PartialEq)]
+impl TypeDefinitionNode {
+    /// The name of the type.
+    pub fn name(&self) -> &NameNode {
+        match self {
+            TypeDefinitionNode::Scalar(scalar) => &scalar.name,
+            TypeDefinitionNode::Object(object) => &object.name,
+            TypeDefinitionNode::Interface(interface) => &interface.name,
+            TypeDefinitionNode::Union(union) => &union.name,
+            TypeDefinitionNode::Enum(enum_type) => &enum_type.name,
+            TypeDefinitionNode::Input(input) => &input.name,
+        }
+    }
+
+    /// Where the definition was written in the source.
+    pub fn span(&self) -> &Span {
+        match self {
+            TypeDefinitionNode::Scalar(scalar) => &scalar.span,
+            TypeDefinitionNode::Object(object) => &object.span,
+            TypeDefinitionNode::Interface(interface) => &interface.span,
+            TypeDefinitionNode::Union(union) => &union.span,
+            TypeDefinitionNode::Enum(enum_type) => &enum_type.span,
+            TypeDefinitionNode::Input(input) => &input.span,
+        }
+    }
+}
+
+/// The places in an executable document where a directive can be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutableDirectiveLocation {
+    /// `QUERY`, a query operation.
+    Query,
+    /// `MUTATION`, a mutation operation.
+    Mutation,
+    /// `SUBSCRIPTION`, a subscription operation.
+    Subscription,
+    /// `FIELD`, a selected field.
+    Field,
+    /// `FRAGMENT_DEFINITION`, a fragment definition.
+    FragmentDefinition,
+    /// `FRAGMENT_SPREAD`, a named fragment spread.
+    FragmentSpread,
+    /// `INLINE_FRAGMENT`, an inline fragment.
+    InlineFragment,
+    /// `VARIABLE_DEFINITION`, a variable definition.
+    VariableDefinition,
+}
+
+/// The places in a type system document where a directive can be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeSystemDirectiveLocation {
+    /// `SCHEMA`, the schema definition.
+    Schema,
+    /// `SCALAR`, a scalar definition.
+    Scalar,
+    /// `OBJECT`, an object definition.
+    Object,
+    /// `FIELD_DEFINITION`, a field of an object or interface.
+    FieldDefinition,
+    /// `ARGUMENT_DEFINITION`, an argument of a field or directive.
+    ArgumentDefinition,
+    /// `INTERFACE`, an interface definition.
+    Interface,
+    /// `UNION`, a union definition.
+    Union,
+    /// `ENUM`, an enum definition.
+    Enum,
+    /// `ENUM_VALUE`, a value of an enum.
+    EnumValue,
+    /// `INPUT_OBJECT`, an input object definition.
+    InputObject,
+    /// `INPUT_FIELD_DEFINITION`, a field of an input object.
+    InputFieldDefinition,
+}
+
+/// A place where a directive can be applied.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DirectiveLocation {
+    /// A place in an executable document.
+    Executable(ExecutableDirectiveLocation),
+    /// A place in a type system document.
+    TypeSystem(TypeSystemDirectiveLocation),
+}
+
+impl<'a> TryFrom<Token<'a>> for DirectiveLocation {
+    type Error = ParseError;
+    fn try_from(token: Token<'a>) -> Result<Self, Self::Error> {
+        use ExecutableDirectiveLocation as E;
+        use TypeSystemDirectiveLocation as T;
+        match token {
+            Token::Name(location, value) => match value {
+                "QUERY" => Ok(DirectiveLocation::Executable(E::Query)),
+                "MUTATION" => Ok(DirectiveLocation::Executable(E::Mutation)),
+                "SUBSCRIPTION" => Ok(DirectiveLocation::Executable(E::Subscription)),
+                "FIELD" => Ok(DirectiveLocation::Executable(E::Field)),
+                "FRAGMENT_DEFINITION" => Ok(DirectiveLocation::Executable(E::FragmentDefinition)),
+                "FRAGMENT_SPREAD" => Ok(DirectiveLocation::Executable(E::FragmentSpread)),
+                "INLINE_FRAGMENT" => Ok(DirectiveLocation::Executable(E::InlineFragment)),
+                "VARIABLE_DEFINITION" => Ok(DirectiveLocation::Executable(E::VariableDefinition)),
+                "SCHEMA" => Ok(DirectiveLocation::TypeSystem(T::Schema)),
+                "SCALAR" => Ok(DirectiveLocation::TypeSystem(T::Scalar)),
+                "OBJECT" => Ok(DirectiveLocation::TypeSystem(T::Object)),
+                "FIELD_DEFINITION" => Ok(DirectiveLocation::TypeSystem(T::FieldDefinition)),
+                "ARGUMENT_DEFINITION" => Ok(DirectiveLocation::TypeSystem(T::ArgumentDefinition)),
+                "INTERFACE" => Ok(DirectiveLocation::TypeSystem(T::Interface)),
+                "UNION" => Ok(DirectiveLocation::TypeSystem(T::Union)),
+                "ENUM" => Ok(DirectiveLocation::TypeSystem(T::Enum)),
+                "ENUM_VALUE" => Ok(DirectiveLocation::TypeSystem(T::EnumValue)),
+                "INPUT_OBJECT" => Ok(DirectiveLocation::TypeSystem(T::InputObject)),
+                "INPUT_FIELD_DEFINITION" => {
+                    Ok(DirectiveLocation::TypeSystem(T::InputFieldDefinition))
+                }
+                _ => Err(ParseError::UnexpectedKeyword {
+                    expected: "A valid directive location".into(),
+                    received: value.into(),
+                    location,
+                }),
+            },
+            _ => Err(ParseError::UnexpectedToken {
+                expected: vec![TokenKind::Name],
+                received: token.to_string(),
+                location: token.location(),
+            }),
+        }
+    }
+}
+
+/// The definition of a directive.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DirectiveDefinitionNode {
+    /// The description of the directive, if it has one.
+    pub description: Description,
+    /// The name of the directive, without the `@`.
+    pub name: NameNode,
+    /// The arguments the directive accepts.
+    pub arguments: Option<ArgumentDefinitions>,
+    /// Whether the directive can be applied more than once to the same place.
+    pub repeatable: bool,
+    /// The places where the directive can be applied.
+    pub locations: Vec<DirectiveLocation>,
+    /// Where the directive was written in the source.
+    pub span: Span,
+}
+
+impl DirectiveDefinitionNode {
+    /// Creates a directive definition from its name token and description.
+    pub fn new(tok: Token, description: Description) -> ParseResult<DirectiveDefinitionNode> {
+        Ok(DirectiveDefinitionNode {
+            description,
+            name: NameNode::new(tok)?,
+            arguments: None,
+            repeatable: false,
+            locations: Vec::new(),
+            span: Span::ignored(),
+        })
+    }
+
+    /// Sets the arguments the directive accepts.
+    pub fn with_arguments(&mut self, arguments: Option<ArgumentDefinitions>) -> &mut Self {
+        self.arguments = arguments;
+        self
+    }
+
+    /// Sets whether the directive is repeatable.
+    pub fn with_repeatable(&mut self, repeatable: bool) -> &mut Self {
+        self.repeatable = repeatable;
+        self
+    }
+
+    /// Sets the places where the directive can be applied.
+    pub fn with_locations(&mut self, locations: Vec<DirectiveLocation>) -> &mut Self {
+        self.locations = locations;
+        self
+    }
+}
+
+/// A definition of a type system document.
+#[derive(Debug, Clone, PartialEq)]
 pub enum TypeSystemDefinitionNode {
+    /// The schema definition.
     Schema(SchemaDefinitionNode),
+    /// A type definition.
     Type(TypeDefinitionNode),
-    // Directive(DirectiveDefinitionNode),
+    /// A directive definition.
+    Directive(DirectiveDefinitionNode),
 }
 
-#[derive(Debug, PartialEq)]
+/// An extension, which adds to the definition of the schema or of a type.
+#[derive(Debug, Clone, PartialEq)]
 pub enum TypeSystemExtensionNode {
+    /// An extension of the schema.
+    Schema(SchemaExtensionNode),
+    /// An extension of a scalar.
+    Scalar(ScalarTypeExtensionNode),
+    /// An extension of an object.
     Object(ObjectTypeExtensionNode),
+    /// An extension of an interface.
+    Interface(InterfaceTypeExtensionNode),
+    /// An extension of a union.
+    Union(UnionTypeExtensionNode),
+    /// An extension of an enum.
+    Enum(EnumTypeExtensionNode),
+    /// An extension of an input object.
+    Input(InputTypeExtensionNode),
 }
 
 type Selections = Vec<Selection>;
 
-#[derive(Debug, PartialEq)]
+/// A field selected by an operation or fragment.
+#[derive(Debug, Clone, PartialEq)]
 pub struct FieldNode {
+    /// The name of the field.
     pub name: NameNode,
+    /// The name the field is returned under in place of its own.
     pub alias: Option<NameNode>,
+    /// The arguments passed to the field.
     pub arguments: Option<Arguments>,
+    /// The directives applied to the field.
     pub directives: Option<Directives>,
+    /// The fields selected from the value of the field, when it is an object,
+    /// interface or union.
     pub selections: Option<Selections>,
+    /// Where the field was written in the source.
+    pub span: Span,
 }
 
 impl FieldNode {
+    /// Creates a field from its name token.
     pub fn new(name: Token) -> ParseResult<FieldNode> {
         Ok(FieldNode {
             name: NameNode::new(name)?,
@@ -579,24 +1146,29 @@ impl FieldNode {
             arguments: None,
             directives: None,
             selections: None,
+            span: Span::ignored(),
         })
     }
 
+    /// Sets the alias from its name token.
     pub fn with_alias(&mut self, alias: Token) -> ParseResult<&Self> {
         self.alias = Some(NameNode::new(alias)?);
         Ok(self)
     }
 
+    /// Sets the arguments passed to the field.
     pub fn with_arguments(&mut self, arguments: Option<Arguments>) -> &Self {
         self.arguments = arguments;
         self
     }
 
+    /// Sets the directives applied to the field.
     pub fn with_directives(&mut self, directives: Option<Directives>) -> &Self {
         self.directives = directives;
         self
     }
 
+    /// Sets the fields selected from the value of the field.
     pub fn with_selections(&mut self, selections: Selections) -> &Self {
         self.selections = Some(selections);
         self
@@ -611,6 +1183,7 @@ impl From<&str> for FieldNode {
             arguments: None,
             directives: None,
             selections: None,
+            span: Span::ignored(),
         }
     }
 }
@@ -624,14 +1197,20 @@ impl<'a> TryFrom<Token<'a>> for FieldNode {
             arguments: None,
             directives: None,
             selections: None,
+            span: Span::ignored(),
         })
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// A spread of a named fragment, such as `...UserFields`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct FragmentSpreadNode {
+    /// The name of the fragment.
     pub name: NameNode,
+    /// The directives applied to the fragment spread.
     pub directives: Option<Directives>,
+    /// Where the fragment spread was written in the source.
+    pub span: Span,
 }
 
 impl From<&str> for FragmentSpreadNode {
@@ -639,81 +1218,246 @@ impl From<&str> for FragmentSpreadNode {
         Self {
             name: NameNode::from(name),
             directives: None,
+            span: Span::ignored(),
         }
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// An inline fragment, such as `... on User { name }`.
+#[derive(Debug, Clone, PartialEq)]
 pub struct InlineFragmentSpreadNode {
+    /// The type the fragment applies to, if it has a type condition.
     pub node_type: Option<NamedTypeNode>,
+    /// The directives applied to the inline fragment.
     pub directives: Option<Directives>,
+    /// The fields selected by the fragment.
     pub selections: Selections,
+    /// Where the inline fragment was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// A fragment in a selection set.
+#[derive(Debug, Clone, PartialEq)]
 pub enum FragmentSpread {
+    /// A spread of a named fragment.
     Node(FragmentSpreadNode),
+    /// An inline fragment.
     Inline(InlineFragmentSpreadNode),
 }
 
-#[derive(Debug, PartialEq)]
+/// The definition of a named fragment.
+#[derive(Debug, Clone, PartialEq)]
 pub struct FragmentDefinitionNode {
+    /// The name of the fragment.
     pub name: NameNode,
+    /// The type the fragment applies to.
     pub node_type: NamedTypeNode,
+    /// The directives applied to the fragment.
     pub directives: Option<Directives>,
+    /// The fields selected by the fragment.
     pub selections: Selections,
+    /// Where the fragment was written in the source.
+    pub span: Span,
 }
 
 impl FragmentDefinitionNode {
+    /// Creates a fragment from its name token and the name token of its type
+    /// condition.
     pub fn new(name: Token, node_type: Token) -> ParseResult<Self> {
         Ok(Self {
             name: NameNode::new(name)?,
             node_type: NamedTypeNode::new(node_type)?,
             directives: None,
             selections: Vec::new(),
+            span: Span::ignored(),
         })
     }
 
+    /// Sets the directives applied to the fragment.
     pub fn with_directives(mut self, directives: Option<Directives>) -> Self {
         self.directives = directives;
         self
     }
 
+    /// Sets the fields selected by the fragment.
     pub fn with_selections(mut self, selections: Selections) -> Self {
         self.selections = selections;
         self
     }
 }
 
-#[derive(Debug, PartialEq)]
+/// An item of a selection set.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Selection {
+    /// A field.
     Field(FieldNode),
+    /// A named or inline fragment.
     Fragment(FragmentSpread),
 }
 
-#[derive(Debug, PartialEq)]
+/// A query operation.
+#[derive(Debug, Clone, PartialEq)]
 pub struct QueryDefinitionNode {
+    /// The name of the query, if it has one.
+    pub name: Option<NameNode>,
+    /// The variables the query defines.
+    pub variables: Option<Variables>,
+    /// The directives applied to the query.
+    pub directives: Option<Directives>,
+    /// The fields selected by the query.
+    pub selections: Selections,
+    /// Where the query was written in the source.
+    pub span: Span,
+}
+
+/// A mutation operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MutationDefinitionNode {
+    /// The name of the mutation, if it has one.
+    pub name: Option<NameNode>,
+    /// The variables the mutation defines.
+    pub variables: Option<Variables>,
+    /// The directives applied to the mutation.
+    pub directives: Option<Directives>,
+    /// The fields selected by the mutation.
+    pub selections: Selections,
+    /// Where the mutation was written in the source.
+    pub span: Span,
+}
+
+/// A subscription operation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubscriptionDefinitionNode {
+    /// The name of the subscription, if it has one.
     pub name: Option<NameNode>,
+    /// The variables the subscription defines.
     pub variables: Option<Variables>,
+    /// The directives applied to the subscription.
+    pub directives: Option<Directives>,
+    /// The fields selected by the subscription.
     pub selections: Selections,
+    /// Where the subscription was written in the source.
+    pub span: Span,
 }
 
-#[derive(Debug, PartialEq)]
+/// An operation of any kind.
+#[derive(Debug, Clone, PartialEq)]
 pub enum OperationTypeNode {
+    /// A query.
     Query(QueryDefinitionNode),
-    // Mutation,
-    // Subscription,
+    /// A mutation.
+    Mutation(MutationDefinitionNode),
+    /// A subscription.
+    Subscription(SubscriptionDefinitionNode),
+}
+
+impl OperationTypeNode {
+    /// The name of the operation, if it has one.
+    pub fn name(&self) -> Option<&NameNode> {
+        match self {
+            OperationTypeNode::Query(query) => query.name.as_ref(),
+            OperationTypeNode::Mutation(mutation) => mutation.name.as_ref(),
+            OperationTypeNode::Subscription(subscription) => subscription.name.as_ref(),
+        }
+    }
+
+    /// The variables the operation defines.
+    pub fn variables(&self) -> &[VariableDefinitionNode] {
+        let variables = match self {
+            OperationTypeNode::Query(query) => &query.variables,
+            OperationTypeNode::Mutation(mutation) => &mutation.variables,
+            OperationTypeNode::Subscription(subscription) => &subscription.variables,
+        };
+        variables.as_deref().unwrap_or(&[])
+    }
+
+    /// The directives applied to the operation.
+    pub fn directives(&self) -> &Option<Directives> {
+        match self {
+            OperationTypeNode::Query(query) => &query.directives,
+            OperationTypeNode::Mutation(mutation) => &mutation.directives,
+            OperationTypeNode::Subscription(subscription) => &subscription.directives,
+        }
+    }
+
+    /// The fields selected by the operation.
+    pub fn selections(&self) -> &[Selection] {
+        match self {
+            OperationTypeNode::Query(query) => &query.selections,
+            OperationTypeNode::Mutation(mutation) => &mutation.selections,
+            OperationTypeNode::Subscription(subscription) => &subscription.selections,
+        }
+    }
+
+    /// Where the operation was written in the source.
+    pub fn span(&self) -> &Span {
+        match self {
+            OperationTypeNode::Query(query) => &query.span,
+            OperationTypeNode::Mutation(mutation) => &mutation.span,
+            OperationTypeNode::Subscription(subscription) => &subscription.span,
+        }
+    }
 }
 
-#[derive(Debug, PartialEq)]
+/// A definition of an executable document.
+#[derive(Debug, Clone, PartialEq)]
 pub enum ExecutableDefinitionNode {
+    /// An operation.
     Operation(OperationTypeNode),
+    /// A fragment definition.
     Fragment(FragmentDefinitionNode),
 }
 
-#[derive(Debug, PartialEq)]
+/// A definition of a document.
+#[derive(Debug, Clone, PartialEq)]
 pub enum DefinitionNode {
+    /// An operation or fragment definition.
     Executable(ExecutableDefinitionNode),
+    /// A schema, type or directive definition.
     TypeSystem(TypeSystemDefinitionNode),
+    /// An extension of the schema or of a type.
     Extension(TypeSystemExtensionNode),
 }
+
+impl TypeSystemExtensionNode {
+    /// The name of the extended type, or None for a schema extension.
+    pub fn name(&self) -> Option<&NameNode> {
+        match self {
+            TypeSystemExtensionNode::Schema(_) => None,
+            TypeSystemExtensionNode::Scalar(scalar) => Some(&scalar.name),
+            TypeSystemExtensionNode::Object(object) => Some(&object.name),
+            TypeSystemExtensionNode::Interface(interface) => Some(&interface.name),
+            TypeSystemExtensionNode::Union(union) => Some(&union.name),
+            TypeSystemExtensionNode::Enum(enum_type) => Some(&enum_type.name),
+            TypeSystemExtensionNode::Input(input) => Some(&input.name),
+        }
+    }
+}
+
+impl DefinitionNode {
+    /// Where the definition was written in the source.
+    pub fn span(&self) -> &Span {
+        match self {
+            DefinitionNode::Executable(ExecutableDefinitionNode::Operation(operation)) => {
+                operation.span()
+            }
+            DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(fragment)) => {
+                &fragment.span
+            }
+            DefinitionNode::TypeSystem(definition) => match definition {
+                TypeSystemDefinitionNode::Schema(schema) => &schema.span,
+                TypeSystemDefinitionNode::Type(type_definition) => type_definition.span(),
+                TypeSystemDefinitionNode::Directive(directive) => &directive.span,
+            },
+            DefinitionNode::Extension(extension) => match extension {
+                TypeSystemExtensionNode::Schema(schema) => &schema.span,
+                TypeSystemExtensionNode::Scalar(scalar) => &scalar.span,
+                TypeSystemExtensionNode::Object(object) => &object.span,
+                TypeSystemExtensionNode::Interface(interface) => &interface.span,
+                TypeSystemExtensionNode::Union(union) => &union.span,
+                TypeSystemExtensionNode::Enum(enum_type) => &enum_type.span,
+                TypeSystemExtensionNode::Input(input) => &input.span,
+            },
+        }
+    }
+}
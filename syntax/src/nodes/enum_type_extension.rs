@@ -0,0 +1,142 @@
+//! Extensions of enums.
+
+use crate::error::ParseResult;
+use crate::nodes::*;
+
+/// An extension of an enum, which adds directives or values.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumTypeExtensionNode {
+    /// The description written before the extension, if it has one.
+    pub description: Description,
+    /// The name of the extended enum.
+    pub name: NameNode,
+    /// The directives added to the enum.
+    pub directives: Option<Directives>,
+    /// The values added to the enum.
+    pub values: Option<Vec<EnumValueDefinitionNode>>,
+    /// Where the enum was written in the source.
+    pub span: Span,
+}
+
+impl EnumTypeExtensionNode {
+    /// Creates an extension of the enum named by the token.
+    pub fn new(tok: Token, description: Description) -> ParseResult<EnumTypeExtensionNode> {
+        Ok(EnumTypeExtensionNode {
+            description,
+            name: NameNode::new(tok)?,
+            directives: None,
+            values: None,
+            span: Span::ignored(),
+        })
+    }
+
+    /// Sets the directives added to the enum.
+    pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Sets the values added to the enum.
+    pub fn with_values(&mut self, values: Vec<EnumValueDefinitionNode>) -> &mut Self {
+        self.values = Some(values);
+        self
+    }
+}
+
+impl ValidNode for EnumTypeExtensionNode {
+    fn validate(&self) -> ValidationResult {
+        if !(self.directives.is_none() && self.values.is_none()) {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                "Enum Extension must have at least one of the following: Directive or Value",
+            )
+            .with_location(self.span.start))
+        }
+    }
+}
+
+impl ValidExtensionNode<EnumTypeDefinitionNode> for EnumTypeExtensionNode {
+    fn validate_extension(&self, original: Option<&EnumTypeDefinitionNode>) -> ValidationResult {
+        if let Some(enum_type) = original {
+            let extension_values: Vec<&str> = match &self.values {
+                Some(values) => values.iter().map(|v| v.name.value.as_str()).collect(),
+                None => Vec::new(),
+            };
+            let original_values: Vec<&str> = enum_type
+                .values
+                .iter()
+                .map(|v| v.name.value.as_str())
+                .collect();
+            validation::validate_extension_names_against_original(
+                "value",
+                &extension_values,
+                &original_values,
+                self.span.start,
+            )
+        } else {
+            Err(ValidationError::new(
+                format!(
+                    "Invalid Enum Extension {0}: No enum of name {0} in schema",
+                    self.name.value
+                )
+                .as_str(),
+            )
+            .with_location(self.span.start))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enum_value(name: &str) -> EnumValueDefinitionNode {
+        EnumValueDefinitionNode {
+            description: None,
+            name: NameNode::from(name),
+            directives: None,
+            span: Span::ignored(),
+        }
+    }
+
+    #[test]
+    fn enum_extension_validates() {
+        let mut extension = EnumTypeExtensionNode {
+            name: NameNode::from("Direction"),
+            description: None,
+            directives: None,
+            values: None,
+            span: Span::ignored(),
+        };
+        assert!(extension.validate().is_err());
+        extension.with_values(vec![enum_value("UP")]);
+        assert!(extension.validate().is_ok());
+    }
+
+    #[test]
+    fn enum_extension_validates_against_original() {
+        let mut extension = EnumTypeExtensionNode {
+            name: NameNode::from("Direction"),
+            description: None,
+            directives: None,
+            values: Some(vec![enum_value("UP")]),
+            span: Span::ignored(),
+        };
+        assert!(extension.validate_extension(None).is_err());
+
+        let original = EnumTypeDefinitionNode {
+            name: NameNode::from("Direction"),
+            description: None,
+            directives: None,
+            values: vec![enum_value("NORTH"), enum_value("SOUTH")],
+            span: Span::ignored(),
+        };
+        assert!(extension.validate_extension(Some(&original)).is_ok());
+
+        extension.with_values(vec![enum_value("SOUTH")]);
+        let res = extension.validate_extension(Some(&original));
+        assert!(res.is_err());
+        assert!(res.unwrap_err().message.contains("SOUTH"));
+    }
+}
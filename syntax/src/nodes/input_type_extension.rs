@@ -0,0 +1,141 @@
+//! Extensions of input objects.
+
+use crate::error::ParseResult;
+use crate::nodes::*;
+
+/// An extension of an input object, which adds directives or fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputTypeExtensionNode {
+    /// The description written before the extension, if it has one.
+    pub description: Description,
+    /// The name of the extended input object.
+    pub name: NameNode,
+    /// The directives added to the input object.
+    pub directives: Option<Directives>,
+    /// The fields added to the input object.
+    pub fields: Option<Vec<InputValueDefinitionNode>>,
+    /// Where the input object was written in the source.
+    pub span: Span,
+}
+
+impl InputTypeExtensionNode {
+    /// Creates an extension of the input object named by the token.
+    pub fn new(tok: Token, description: Description) -> ParseResult<InputTypeExtensionNode> {
+        Ok(InputTypeExtensionNode {
+            description,
+            name: NameNode::new(tok)?,
+            directives: None,
+            fields: None,
+            span: Span::ignored(),
+        })
+    }
+
+    /// Sets the directives added to the input object.
+    pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Sets the fields added to the input object.
+    pub fn with_fields(&mut self, fields: Vec<InputValueDefinitionNode>) -> &mut Self {
+        self.fields = Some(fields);
+        self
+    }
+}
+
+impl ValidNode for InputTypeExtensionNode {
+    fn validate(&self) -> ValidationResult {
+        if !(self.directives.is_none() && self.fields.is_none()) {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                "Input Extension must have at least one of the following: Directive or Field",
+            )
+            .with_location(self.span.start))
+        }
+    }
+}
+
+impl ValidExtensionNode<InputTypeDefinitionNode> for InputTypeExtensionNode {
+    fn validate_extension(&self, original: Option<&InputTypeDefinitionNode>) -> ValidationResult {
+        if let Some(input) = original {
+            let extension_fields: Vec<&str> = match &self.fields {
+                Some(fields) => fields.iter().map(|f| f.name.value.as_str()).collect(),
+                None => Vec::new(),
+            };
+            let original_fields: Vec<&str> =
+                input.fields.iter().map(|f| f.name.value.as_str()).collect();
+            validation::validate_extension_names_against_original(
+                "field",
+                &extension_fields,
+                &original_fields,
+                self.span.start,
+            )
+        } else {
+            Err(ValidationError::new(
+                format!(
+                    "Invalid Input Extension {0}: No input of name {0} in schema",
+                    self.name.value
+                )
+                .as_str(),
+            )
+            .with_location(self.span.start))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_field(name: &str) -> InputValueDefinitionNode {
+        InputValueDefinitionNode {
+            description: None,
+            name: NameNode::from(name),
+            input_type: TypeNode::Named(NamedTypeNode::from("String")),
+            default_value: None,
+            directives: None,
+            span: Span::ignored(),
+        }
+    }
+
+    #[test]
+    fn input_extension_validates() {
+        let mut extension = InputTypeExtensionNode {
+            name: NameNode::from("UserInput"),
+            description: None,
+            directives: None,
+            fields: None,
+            span: Span::ignored(),
+        };
+        assert!(extension.validate().is_err());
+        extension.with_fields(vec![input_field("email")]);
+        assert!(extension.validate().is_ok());
+    }
+
+    #[test]
+    fn input_extension_validates_against_original() {
+        let mut extension = InputTypeExtensionNode {
+            name: NameNode::from("UserInput"),
+            description: None,
+            directives: None,
+            fields: Some(vec![input_field("email")]),
+            span: Span::ignored(),
+        };
+        assert!(extension.validate_extension(None).is_err());
+
+        let original = InputTypeDefinitionNode {
+            name: NameNode::from("UserInput"),
+            description: None,
+            directives: None,
+            fields: vec![input_field("name")],
+            span: Span::ignored(),
+        };
+        assert!(extension.validate_extension(Some(&original)).is_ok());
+
+        extension.with_fields(vec![input_field("name")]);
+        let res = extension.validate_extension(Some(&original));
+        assert!(res.is_err());
+        assert!(res.unwrap_err().message.contains("name"));
+    }
+}
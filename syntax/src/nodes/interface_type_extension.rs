@@ -0,0 +1,172 @@
+//! Extensions of interfaces.
+
+use crate::error::ParseResult;
+use crate::nodes::*;
+
+/// An extension of an interface, which adds interfaces, directives or fields.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterfaceTypeExtensionNode {
+    /// The description written before the extension, if it has one.
+    pub description: Description,
+    /// The name of the extended interface.
+    pub name: NameNode,
+    /// The interfaces added to the interface.
+    pub interfaces: Option<Vec<NamedTypeNode>>,
+    /// The directives added to the interface.
+    pub directives: Option<Directives>,
+    /// The fields added to the interface.
+    pub fields: Option<Vec<FieldDefinitionNode>>,
+    /// Where the interface was written in the source.
+    pub span: Span,
+}
+
+impl InterfaceTypeExtensionNode {
+    /// Creates an extension of the interface named by the token.
+    pub fn new(tok: Token, description: Description) -> ParseResult<InterfaceTypeExtensionNode> {
+        Ok(InterfaceTypeExtensionNode {
+            description,
+            name: NameNode::new(tok)?,
+            interfaces: None,
+            directives: None,
+            fields: None,
+            span: Span::ignored(),
+        })
+    }
+
+    /// Sets the interfaces added to the interface.
+    pub fn with_interfaces(&mut self, interfaces: Option<Vec<NamedTypeNode>>) -> &mut Self {
+        self.interfaces = interfaces;
+        self
+    }
+
+    /// Sets the directives added to the interface.
+    pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Sets the fields added to the interface.
+    pub fn with_fields(&mut self, fields: Vec<FieldDefinitionNode>) -> &mut Self {
+        self.fields = Some(fields);
+        self
+    }
+}
+
+impl NodeWithFields for InterfaceTypeExtensionNode {
+    fn get_fields(&self) -> &[FieldDefinitionNode] {
+        if let Some(fields) = &self.fields {
+            fields
+        } else {
+            &[]
+        }
+    }
+}
+
+impl ValidNode for InterfaceTypeExtensionNode {
+    fn validate(&self) -> ValidationResult {
+        if !(self.directives.is_none() && self.interfaces.is_none() && self.fields.is_none()) {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                "Interface Extension must have at least one of the following: Directive, Interface, or Field",
+            ).with_location(self.span.start))
+        }
+    }
+}
+
+impl ValidExtensionNode<InterfaceTypeDefinitionNode> for InterfaceTypeExtensionNode {
+    fn validate_extension(
+        &self,
+        original: Option<&InterfaceTypeDefinitionNode>,
+    ) -> ValidationResult {
+        if let Some(interface) = original {
+            validation::validate_extension_fields_against_original(self, interface)?;
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                format!(
+                    "Invalid Interface Extension {0}: No interface of name {0} in schema",
+                    self.name.value
+                )
+                .as_str(),
+            )
+            .with_location(self.span.start))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interface_extension_validates() {
+        let mut extension = InterfaceTypeExtensionNode {
+            name: NameNode::from("Node"),
+            interfaces: None,
+            description: None,
+            directives: None,
+            fields: None,
+            span: Span::ignored(),
+        };
+        assert!(extension.validate().is_err());
+        extension.with_fields(vec![FieldDefinitionNode {
+            arguments: None,
+            description: None,
+            name: NameNode::from("id"),
+            field_type: TypeNode::Named(NamedTypeNode::from("ID")),
+            directives: None,
+            span: Span::ignored(),
+        }]);
+        assert!(extension.validate().is_ok());
+    }
+
+    #[test]
+    fn interface_extension_validates_against_original() {
+        let extension = InterfaceTypeExtensionNode {
+            name: NameNode::from("Node"),
+            interfaces: None,
+            description: None,
+            directives: None,
+            fields: Some(vec![FieldDefinitionNode {
+                name: NameNode::from("id"),
+                description: None,
+                arguments: None,
+                field_type: TypeNode::Named(NamedTypeNode::from("ID")),
+                directives: None,
+                span: Span::ignored(),
+            }]),
+            span: Span::ignored(),
+        };
+        assert!(extension.validate_extension(None).is_err());
+
+        let mut interface = InterfaceTypeDefinitionNode {
+            name: NameNode::from("Node"),
+            interfaces: None,
+            description: None,
+            directives: None,
+            fields: vec![FieldDefinitionNode {
+                name: NameNode::from("createdAt"),
+                description: None,
+                arguments: None,
+                field_type: TypeNode::Named(NamedTypeNode::from("DateTime")),
+                directives: None,
+                span: Span::ignored(),
+            }],
+            span: Span::ignored(),
+        };
+        assert!(extension.validate_extension(Some(&interface)).is_ok());
+
+        interface.with_fields(vec![FieldDefinitionNode {
+            name: NameNode::from("id"),
+            description: None,
+            arguments: None,
+            field_type: TypeNode::Named(NamedTypeNode::from("ID")),
+            directives: None,
+            span: Span::ignored(),
+        }]);
+        let res = extension.validate_extension(Some(&interface));
+        assert!(res.is_err());
+        assert!(res.unwrap_err().message.contains("id"));
+    }
+}
@@ -1,16 +1,27 @@
+//! Extensions of objects.
+
 use crate::error::ParseResult;
 use crate::nodes::*;
 
-#[derive(Debug, PartialEq)]
+/// An extension of an object, which adds interfaces, directives or fields.
+#[derive(Debug, Clone, PartialEq)]
 pub struct ObjectTypeExtensionNode {
+    /// The description written before the extension, if it has one.
     pub description: Description,
+    /// The name of the extended object.
     pub name: NameNode,
+    /// The interfaces added to the object.
     pub interfaces: Option<Vec<NamedTypeNode>>,
+    /// The directives added to the object.
     pub directives: Option<Directives>,
+    /// The fields added to the object.
     pub fields: Option<Vec<FieldDefinitionNode>>,
+    /// Where the object was written in the source.
+    pub span: Span,
 }
 
 impl ObjectTypeExtensionNode {
+    /// Creates an extension of the object named by the token.
     pub fn new(tok: Token, description: Description) -> ParseResult<ObjectTypeExtensionNode> {
         Ok(ObjectTypeExtensionNode {
             description,
@@ -18,19 +29,23 @@ impl ObjectTypeExtensionNode {
             interfaces: None,
             directives: None,
             fields: None,
+            span: Span::ignored(),
         })
     }
 
+    /// Sets the interfaces added to the object.
     pub fn with_interfaces(&mut self, interfaces: Option<Vec<NamedTypeNode>>) -> &mut Self {
         self.interfaces = interfaces;
         self
     }
 
+    /// Sets the directives added to the object.
     pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
         self.directives = directives;
         self
     }
 
+    /// Sets the fields added to the object.
     pub fn with_fields(&mut self, fields: Vec<FieldDefinitionNode>) -> &mut Self {
         self.fields = Some(fields);
         self
@@ -40,7 +55,7 @@ impl ObjectTypeExtensionNode {
 impl NodeWithFields for ObjectTypeExtensionNode {
     fn get_fields(&self) -> &[FieldDefinitionNode] {
         if let Some(fields) = &self.fields {
-            &fields
+            fields
         } else {
             &[]
         }
@@ -52,7 +67,10 @@ impl ValidNode for ObjectTypeExtensionNode {
         if !(self.directives.is_none() && self.interfaces.is_none() && self.fields.is_none()) {
             Ok(())
         } else {
-            Err(ValidationError::new("Object Extension must have at least one of the following: Directive, Interface, or Field"))
+            Err(ValidationError::new(
+                "Object Extension must have at least one of the following: Directive, Interface, or Field",
+            )
+            .with_location(self.span.start))
         }
     }
 }
@@ -69,7 +87,8 @@ impl ValidExtensionNode<ObjectTypeDefinitionNode> for ObjectTypeExtensionNode {
                     self.name.value
                 )
                 .as_str(),
-            ))
+            )
+            .with_location(self.span.start))
         }
     }
 }
@@ -86,11 +105,13 @@ mod tests {
             directives: None,
             interfaces: None,
             fields: None,
+            span: Span::ignored(),
         };
         assert!(extension.validate().is_err());
         extension.with_directives(Some(vec![DirectiveNode {
             arguments: None,
             name: NameNode::from("someDirective"),
+            span: Span::ignored(),
         }]));
         assert!(extension.validate().is_ok());
         extension.with_directives(None);
@@ -103,6 +124,8 @@ mod tests {
             description: None,
             name: NameNode::from("someField"),
             field_type: TypeNode::Named(NamedTypeNode::from("String")),
+            directives: None,
+            span: Span::ignored(),
         }]);
         assert!(extension.validate().is_ok());
     }
@@ -115,6 +138,7 @@ mod tests {
             directives: Some(vec![DirectiveNode {
                 name: NameNode::from("depricated"),
                 arguments: None,
+                span: Span::ignored(),
             }]),
             interfaces: Some(vec![NamedTypeNode::from("Timestamped")]),
             fields: Some(vec![FieldDefinitionNode {
@@ -122,7 +146,10 @@ mod tests {
                 description: None,
                 arguments: None,
                 field_type: TypeNode::Named(NamedTypeNode::from("String")),
+                directives: None,
+                span: Span::ignored(),
             }]),
+            span: Span::ignored(),
         };
 
         println!("Validating against None");
@@ -138,7 +165,10 @@ mod tests {
                 description: None,
                 arguments: None,
                 field_type: TypeNode::Named(NamedTypeNode::from("Int")),
+                directives: None,
+                span: Span::ignored(),
             }],
+            span: Span::ignored(),
         };
         println!("Validating against object with NO overlap");
         assert!(extension.validate_extension(Some(&object)).is_ok());
@@ -148,9 +178,52 @@ mod tests {
             description: None,
             arguments: None,
             field_type: TypeNode::Named(NamedTypeNode::from("String")),
+            directives: None,
+            span: Span::ignored(),
         }]);
         let res = extension.validate_extension(Some(&object));
         assert!(res.is_err());
         assert!(res.unwrap_err().message.contains("someField"));
     }
+
+    #[test]
+    fn object_extension_cannot_redefine_field_with_directives() {
+        let extension = ObjectTypeExtensionNode {
+            name: NameNode::from("Obj"),
+            description: None,
+            directives: None,
+            interfaces: None,
+            fields: Some(vec![FieldDefinitionNode {
+                name: NameNode::from("name"),
+                description: None,
+                arguments: None,
+                field_type: TypeNode::Named(NamedTypeNode::from("String")),
+                directives: Some(vec![DirectiveNode {
+                    name: NameNode::from("deprecated"),
+                    arguments: None,
+                    span: Span::ignored(),
+                }]),
+                span: Span::ignored(),
+            }]),
+            span: Span::ignored(),
+        };
+        let object = ObjectTypeDefinitionNode {
+            name: NameNode::from("Obj"),
+            description: None,
+            directives: None,
+            interfaces: None,
+            fields: vec![FieldDefinitionNode {
+                name: NameNode::from("name"),
+                description: None,
+                arguments: None,
+                field_type: TypeNode::Named(NamedTypeNode::from("String")),
+                directives: None,
+                span: Span::ignored(),
+            }],
+            span: Span::ignored(),
+        };
+        let res = extension.validate_extension(Some(&object));
+        assert!(res.is_err());
+        assert!(res.unwrap_err().message.contains("name"));
+    }
 }
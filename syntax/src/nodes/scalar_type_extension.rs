@@ -0,0 +1,91 @@
+//! Extensions of scalars.
+
+use crate::error::ParseResult;
+use crate::nodes::*;
+
+/// An extension of a scalar, such as `extend scalar Date @specifiedBy(url: "...")`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScalarTypeExtensionNode {
+    /// The description written before the extension, if it has one.
+    pub description: Description,
+    /// The name of the extended scalar.
+    pub name: NameNode,
+    /// The directives added to the scalar.
+    pub directives: Option<Directives>,
+    /// Where the scalar was written in the source.
+    pub span: Span,
+}
+
+impl ScalarTypeExtensionNode {
+    /// Creates an extension of the scalar named by the token.
+    pub fn new(tok: Token, description: Description) -> ParseResult<ScalarTypeExtensionNode> {
+        Ok(ScalarTypeExtensionNode {
+            description,
+            name: NameNode::new(tok)?,
+            directives: None,
+            span: Span::ignored(),
+        })
+    }
+
+    /// Sets the directives added to the scalar.
+    pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
+        self.directives = directives;
+        self
+    }
+}
+
+impl ValidNode for ScalarTypeExtensionNode {
+    fn validate(&self) -> ValidationResult {
+        if self.directives.is_some() {
+            Ok(())
+        } else {
+            Err(
+                ValidationError::new("Scalar Extension must have at least one Directive")
+                    .with_location(self.span.start),
+            )
+        }
+    }
+}
+
+impl ValidExtensionNode<ScalarTypeDefinitionNode> for ScalarTypeExtensionNode {
+    fn validate_extension(&self, original: Option<&ScalarTypeDefinitionNode>) -> ValidationResult {
+        if original.is_some() {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                format!(
+                    "Invalid Scalar Extension {0}: No scalar of name {0} in schema",
+                    self.name.value
+                )
+                .as_str(),
+            )
+            .with_location(self.span.start))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scalar_extension_validates() {
+        let mut extension = ScalarTypeExtensionNode {
+            name: NameNode::from("Date"),
+            description: None,
+            directives: None,
+            span: Span::ignored(),
+        };
+        assert!(extension.validate().is_err());
+        extension.with_directives(Some(vec![DirectiveNode {
+            name: NameNode::from("format"),
+            arguments: None,
+            span: Span::ignored(),
+        }]));
+        assert!(extension.validate().is_ok());
+        assert!(extension.validate_extension(None).is_err());
+        assert!(extension
+            .validate_extension(Some(&ScalarTypeDefinitionNode::from("Date")))
+            .is_ok());
+    }
+}
@@ -0,0 +1,135 @@
+//! Extensions of the schema.
+
+use crate::nodes::*;
+
+/// An extension of the schema, which adds directives or root operation types.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaExtensionNode {
+    /// The description written before the extension, if it has one.
+    pub description: Description,
+    /// The directives added to the schema.
+    pub directives: Option<Directives>,
+    /// The root operation types added to the schema.
+    pub operations: Option<Vec<OperationTypeDefinitionNode>>,
+    /// Where the schema was written in the source.
+    pub span: Span,
+}
+
+impl SchemaExtensionNode {
+    /// Creates an extension of the schema that adds nothing yet.
+    pub fn new(description: Description) -> SchemaExtensionNode {
+        SchemaExtensionNode {
+            description,
+            directives: None,
+            operations: None,
+            span: Span::ignored(),
+        }
+    }
+
+    /// Sets the directives added to the schema.
+    pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Sets the root operation types added to the schema.
+    pub fn with_operations(&mut self, operations: Vec<OperationTypeDefinitionNode>) -> &mut Self {
+        self.operations = Some(operations);
+        self
+    }
+}
+
+impl ValidNode for SchemaExtensionNode {
+    fn validate(&self) -> ValidationResult {
+        if !(self.directives.is_none() && self.operations.is_none()) {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                "Schema Extension must have at least one of the following: Directive or Operation Type",
+            ).with_location(self.span.start))
+        }
+    }
+}
+
+fn operation_name(operation: &Operation) -> &'static str {
+    match operation {
+        Operation::Query => "query",
+        Operation::Mutation => "mutation",
+        Operation::Subscription => "subscription",
+    }
+}
+
+impl ValidExtensionNode<SchemaDefinitionNode> for SchemaExtensionNode {
+    fn validate_extension(&self, original: Option<&SchemaDefinitionNode>) -> ValidationResult {
+        if let Some(schema) = original {
+            let extension_operations: Vec<&str> = match &self.operations {
+                Some(operations) => operations
+                    .iter()
+                    .map(|o| operation_name(&o.operation))
+                    .collect(),
+                None => Vec::new(),
+            };
+            let original_operations: Vec<&str> = schema
+                .operations
+                .iter()
+                .map(|o| operation_name(&o.operation))
+                .collect();
+            validation::validate_extension_names_against_original(
+                "operation type",
+                &extension_operations,
+                &original_operations,
+                self.span.start,
+            )
+        } else {
+            Err(
+                ValidationError::new("Invalid Schema Extension: No schema definition in document")
+                    .with_location(self.span.start),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_extension_validates() {
+        let mut extension = SchemaExtensionNode::new(None);
+        assert!(extension.validate().is_err());
+        extension.with_operations(vec![OperationTypeDefinitionNode {
+            operation: Operation::Subscription,
+            node_type: NamedTypeNode::from("Subscription"),
+            span: Span::ignored(),
+        }]);
+        assert!(extension.validate().is_ok());
+    }
+
+    #[test]
+    fn schema_extension_validates_against_original() {
+        let mut extension = SchemaExtensionNode::new(None);
+        extension.with_operations(vec![OperationTypeDefinitionNode {
+            operation: Operation::Subscription,
+            node_type: NamedTypeNode::from("Subscription"),
+            span: Span::ignored(),
+        }]);
+        assert!(extension.validate_extension(None).is_err());
+
+        let mut schema = SchemaDefinitionNode::new();
+        schema.operations.push(OperationTypeDefinitionNode {
+            operation: Operation::Query,
+            node_type: NamedTypeNode::from("Query"),
+            span: Span::ignored(),
+        });
+        assert!(extension.validate_extension(Some(&schema)).is_ok());
+
+        schema.operations.push(OperationTypeDefinitionNode {
+            operation: Operation::Subscription,
+            node_type: NamedTypeNode::from("Events"),
+            span: Span::ignored(),
+        });
+        let res = extension.validate_extension(Some(&schema));
+        assert!(res.is_err());
+        assert!(res.unwrap_err().message.contains("subscription"));
+    }
+}
@@ -0,0 +1,130 @@
+//! Extensions of unions.
+
+use crate::error::ParseResult;
+use crate::nodes::*;
+
+/// An extension of a union, which adds directives or members.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnionTypeExtensionNode {
+    /// The description written before the extension, if it has one.
+    pub description: Description,
+    /// The name of the extended union.
+    pub name: NameNode,
+    /// The directives added to the union.
+    pub directives: Option<Directives>,
+    /// The members added to the union.
+    pub types: Option<Vec<NamedTypeNode>>,
+    /// Where the union was written in the source.
+    pub span: Span,
+}
+
+impl UnionTypeExtensionNode {
+    /// Creates an extension of the union named by the token.
+    pub fn new(tok: Token, description: Description) -> ParseResult<UnionTypeExtensionNode> {
+        Ok(UnionTypeExtensionNode {
+            description,
+            name: NameNode::new(tok)?,
+            directives: None,
+            types: None,
+            span: Span::ignored(),
+        })
+    }
+
+    /// Sets the directives added to the union.
+    pub fn with_directives(&mut self, directives: Option<Directives>) -> &mut Self {
+        self.directives = directives;
+        self
+    }
+
+    /// Sets the members added to the union.
+    pub fn with_types(&mut self, types: Vec<NamedTypeNode>) -> &mut Self {
+        self.types = Some(types);
+        self
+    }
+}
+
+impl ValidNode for UnionTypeExtensionNode {
+    fn validate(&self) -> ValidationResult {
+        if !(self.directives.is_none() && self.types.is_none()) {
+            Ok(())
+        } else {
+            Err(ValidationError::new(
+                "Union Extension must have at least one of the following: Directive or Member Type",
+            )
+            .with_location(self.span.start))
+        }
+    }
+}
+
+impl ValidExtensionNode<UnionTypeDefinitionNode> for UnionTypeExtensionNode {
+    fn validate_extension(&self, original: Option<&UnionTypeDefinitionNode>) -> ValidationResult {
+        if let Some(union) = original {
+            let extension_types: Vec<&str> = match &self.types {
+                Some(types) => types.iter().map(|t| t.name.value.as_str()).collect(),
+                None => Vec::new(),
+            };
+            let original_types: Vec<&str> =
+                union.types.iter().map(|t| t.name.value.as_str()).collect();
+            validation::validate_extension_names_against_original(
+                "member type",
+                &extension_types,
+                &original_types,
+                self.span.start,
+            )
+        } else {
+            Err(ValidationError::new(
+                format!(
+                    "Invalid Union Extension {0}: No union of name {0} in schema",
+                    self.name.value
+                )
+                .as_str(),
+            )
+            .with_location(self.span.start))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn union_extension_validates() {
+        let mut extension = UnionTypeExtensionNode {
+            name: NameNode::from("SearchResult"),
+            description: None,
+            directives: None,
+            types: None,
+            span: Span::ignored(),
+        };
+        assert!(extension.validate().is_err());
+        extension.with_types(vec![NamedTypeNode::from("Photo")]);
+        assert!(extension.validate().is_ok());
+    }
+
+    #[test]
+    fn union_extension_validates_against_original() {
+        let mut extension = UnionTypeExtensionNode {
+            name: NameNode::from("SearchResult"),
+            description: None,
+            directives: None,
+            types: Some(vec![NamedTypeNode::from("Photo")]),
+            span: Span::ignored(),
+        };
+        assert!(extension.validate_extension(None).is_err());
+
+        let union = UnionTypeDefinitionNode {
+            name: NameNode::from("SearchResult"),
+            description: None,
+            directives: None,
+            types: vec![NamedTypeNode::from("Person"), NamedTypeNode::from("Page")],
+            span: Span::ignored(),
+        };
+        assert!(extension.validate_extension(Some(&union)).is_ok());
+
+        extension.with_types(vec![NamedTypeNode::from("Page")]);
+        let res = extension.validate_extension(Some(&union));
+        assert!(res.is_err());
+        assert!(res.unwrap_err().message.contains("Page"));
+    }
+}
@@ -0,0 +1,608 @@
+//! Rewrites an operation into a normal form, so operations that select the same data in
+//! different ways become identical. Normal forms can be compared, printed for logs, or used as
+//! cache keys.
+//!
+//! Normalising an operation:
+//! - inlines fragment spreads, and flattens fragments that always apply to the type they are
+//!   selected on
+//! - applies `@skip` and `@include` directives whose condition is a literal
+//! - merges fields that have the same response key and fragments that have the same type
+//!   condition and directives
+//! - sorts arguments and the fields of input objects by name, and drops aliases that repeat
+//!   the name of their field
+//!
+//! # Example
+//!
+//! ```
+//! use syntax::normalize::normalize;
+//! use syntax::schema::Schema;
+//!
+//! let schema = Schema::new(vec![syntax::parse(
+//!     "type Query { user(id: ID, active: Boolean): User }\ntype User { id: ID name: String }",
+//! )
+//! .unwrap()])
+//! .unwrap();
+//! let document = syntax::parse(
+//!     r#"{
+//!   user(id: 1, active: true) { ...Names id @include(if: false) }
+//!   user(active: true, id: 1) { id }
+//! }
+//! fragment Names on User { name }"#,
+//! )
+//! .unwrap();
+//!
+//! let normalized = normalize(&schema, &document, None).unwrap();
+//! assert_eq!(
+//!     normalized.to_string(),
+//!     "{\n  user(active: true, id: 1) {\n    name\n    id\n  }\n}\n"
+//! );
+//! ```
+
+use crate::document::Document;
+use crate::error::ValidationError;
+use crate::nodes::*;
+use crate::schema::Schema;
+use std::collections::HashMap;
+
+/// Normalises one operation of a document, returning a document that contains only the
+/// normalised operation.
+///
+/// The operation is picked by `operation_name`, or is the only operation of the document when
+/// no name is given. The document is expected to be valid for the schema, which is used to
+/// find the types fragments are selected on.
+pub fn normalize(
+    schema: &Schema,
+    document: &Document,
+    operation_name: Option<&str>,
+) -> Result<Document, ValidationError> {
+    let mut operations = Vec::new();
+    let mut fragments = HashMap::new();
+    for definition in &document.definitions {
+        match definition {
+            DefinitionNode::Executable(ExecutableDefinitionNode::Operation(operation)) => {
+                operations.push(operation)
+            }
+            DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(fragment)) => {
+                fragments.insert(fragment.name.value.as_str(), fragment);
+            }
+            _ => (),
+        }
+    }
+    let operation = match operation_name {
+        Some(name) => operations
+            .into_iter()
+            .find(|operation| {
+                operation.name().map(|operation| operation.value.as_str()) == Some(name)
+            })
+            .ok_or_else(|| ValidationError::new(&format!("Unknown operation named {}", name)))?,
+        None => match operations.as_slice() {
+            [operation] => *operation,
+            [] => return Err(ValidationError::new("The document contains no operations")),
+            _ => {
+                return Err(ValidationError::new(
+                    "Must provide an operation name when the document contains multiple \
+                     operations",
+                ))
+            }
+        },
+    };
+
+    let normalizer = Normalizer { schema, fragments };
+    let root = match operation {
+        OperationTypeNode::Query(_) => schema.query_type(),
+        OperationTypeNode::Mutation(_) => schema.mutation_type(),
+        OperationTypeNode::Subscription(_) => schema.subscription_type(),
+    }
+    .map(|root| root.name.value.as_str());
+    let selections: Vec<&Selection> = operation.selections().iter().collect();
+    let selections = normalizer.normalize_selections(root, &selections, &mut Vec::new());
+
+    let mut operation = operation.clone();
+    match &mut operation {
+        OperationTypeNode::Query(query) => query.selections = selections,
+        OperationTypeNode::Mutation(mutation) => mutation.selections = selections,
+        OperationTypeNode::Subscription(subscription) => subscription.selections = selections,
+    }
+    Ok(Document::new(vec![DefinitionNode::Executable(
+        ExecutableDefinitionNode::Operation(operation),
+    )]))
+}
+
+/// The value of a literal `if` argument.
+fn literal_condition(directive: &DirectiveNode) -> Option<bool> {
+    let argument = directive
+        .arguments
+        .iter()
+        .flatten()
+        .find(|argument| argument.name.value == "if")?;
+    match &argument.value {
+        ValueNode::Bool(condition) => Some(condition.value),
+        _ => None,
+    }
+}
+
+/// Applies `@skip` and `@include` directives with literal conditions. Returns None when the
+/// selection is left out, or the directives that are left otherwise.
+fn apply_conditions(directives: &Option<Directives>) -> Option<Option<Directives>> {
+    let mut remaining = Vec::new();
+    for directive in directives.iter().flatten() {
+        let condition = match directive.name.value.as_str() {
+            "skip" => literal_condition(directive).map(|condition| !condition),
+            "include" => literal_condition(directive),
+            _ => None,
+        };
+        match condition {
+            Some(true) => (),
+            Some(false) => return None,
+            None => remaining.push(DirectiveNode {
+                name: directive.name.clone(),
+                arguments: sort_arguments(&directive.arguments),
+                span: directive.span,
+            }),
+        }
+    }
+    if remaining.is_empty() {
+        Some(None)
+    } else {
+        Some(Some(remaining))
+    }
+}
+
+/// Sorts the fields of input objects by name, including objects inside lists.
+pub(crate) fn sort_value(value: &ValueNode) -> ValueNode {
+    match value {
+        ValueNode::List(list) => ValueNode::List(ListValueNode {
+            values: list.values.iter().map(sort_value).collect(),
+            span: list.span,
+        }),
+        ValueNode::Object(object) => {
+            let mut fields: Vec<ObjectFieldNode> = object
+                .fields
+                .iter()
+                .map(|field| ObjectFieldNode {
+                    name: field.name.clone(),
+                    value: sort_value(&field.value),
+                    span: field.span,
+                })
+                .collect();
+            fields.sort_by(|first, second| first.name.value.cmp(&second.name.value));
+            ValueNode::Object(ObjectValueNode {
+                fields,
+                span: object.span,
+            })
+        }
+        value => value.clone(),
+    }
+}
+
+/// Sorts arguments by name, along with the fields of any input objects they are given.
+pub(crate) fn sort_arguments(arguments: &Option<Arguments>) -> Option<Arguments> {
+    let mut arguments: Arguments = arguments
+        .iter()
+        .flatten()
+        .map(|argument| Argument {
+            name: argument.name.clone(),
+            value: sort_value(&argument.value),
+            span: argument.span,
+        })
+        .collect();
+    arguments.sort_by(|first, second| first.name.value.cmp(&second.name.value));
+    if arguments.is_empty() {
+        None
+    } else {
+        Some(arguments)
+    }
+}
+
+/// A selection whose own parts are normalised, with the selections below it still to do.
+enum Collected<'a> {
+    Field {
+        field: FieldNode,
+        /// The type the field returns
+        field_type: Option<&'a str>,
+        selections: Vec<&'a Selection>,
+        /// The fragments being inlined where the field was selected
+        fragment_path: Vec<&'a str>,
+    },
+    Fragment {
+        fragment: InlineFragmentSpreadNode,
+        selections: Vec<&'a Selection>,
+        fragment_path: Vec<&'a str>,
+    },
+}
+
+struct Normalizer<'a> {
+    schema: &'a Schema,
+    fragments: HashMap<&'a str, &'a FragmentDefinitionNode>,
+}
+
+impl<'a> Normalizer<'a> {
+    fn normalize_selections(
+        &self,
+        parent: Option<&'a str>,
+        selections: &[&'a Selection],
+        fragment_path: &mut Vec<&'a str>,
+    ) -> Vec<Selection> {
+        let mut collected = Vec::new();
+        self.collect(parent, selections, fragment_path, &mut collected);
+        collected
+            .into_iter()
+            .map(|collected| match collected {
+                Collected::Field {
+                    mut field,
+                    field_type,
+                    selections,
+                    mut fragment_path,
+                } => {
+                    if field.selections.is_some() {
+                        field.selections = Some(self.normalize_selections(
+                            field_type,
+                            &selections,
+                            &mut fragment_path,
+                        ));
+                    }
+                    Selection::Field(field)
+                }
+                Collected::Fragment {
+                    mut fragment,
+                    selections,
+                    mut fragment_path,
+                } => {
+                    let type_condition = fragment
+                        .node_type
+                        .as_ref()
+                        .map(|named_type| named_type.name.value.as_str());
+                    let type_condition = match type_condition {
+                        Some(name) => self
+                            .schema
+                            .get_type(name)
+                            .map(|type_definition| type_definition.name().value.as_str()),
+                        None => parent,
+                    };
+                    fragment.selections =
+                        self.normalize_selections(type_condition, &selections, &mut fragment_path);
+                    Selection::Fragment(FragmentSpread::Inline(fragment))
+                }
+            })
+            .collect()
+    }
+
+    /// Whether a fragment with this type condition applies to every value of the parent type.
+    fn always_applies(&self, parent: Option<&str>, type_condition: &str) -> bool {
+        match parent {
+            Some(parent) => {
+                parent == type_condition
+                    || (matches!(
+                        self.schema.get_type(parent),
+                        Some(TypeDefinitionNode::Object(_))
+                    ) && self.schema.is_possible_type(type_condition, parent))
+            }
+            None => false,
+        }
+    }
+
+    fn collect(
+        &self,
+        parent: Option<&'a str>,
+        selections: &[&'a Selection],
+        fragment_path: &mut Vec<&'a str>,
+        collected: &mut Vec<Collected<'a>>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => {
+                    self.collect_field(parent, field, fragment_path, collected)
+                }
+                Selection::Fragment(FragmentSpread::Node(spread)) => {
+                    let name = spread.name.value.as_str();
+                    let fragment = match self.fragments.get(name) {
+                        Some(fragment) if !fragment_path.contains(&name) => *fragment,
+                        _ => continue,
+                    };
+                    let directives = match apply_conditions(&spread.directives) {
+                        Some(directives) => directives,
+                        None => continue,
+                    };
+                    fragment_path.push(name);
+                    self.collect_fragment(
+                        parent,
+                        Some(&fragment.node_type),
+                        directives,
+                        &fragment.selections,
+                        spread.span,
+                        fragment_path,
+                        collected,
+                    );
+                    fragment_path.pop();
+                }
+                Selection::Fragment(FragmentSpread::Inline(inline)) => {
+                    let directives = match apply_conditions(&inline.directives) {
+                        Some(directives) => directives,
+                        None => continue,
+                    };
+                    self.collect_fragment(
+                        parent,
+                        inline.node_type.as_ref(),
+                        directives,
+                        &inline.selections,
+                        inline.span,
+                        fragment_path,
+                        collected,
+                    );
+                }
+            }
+        }
+    }
+
+    fn collect_field(
+        &self,
+        parent: Option<&'a str>,
+        field: &'a FieldNode,
+        fragment_path: &[&'a str],
+        collected: &mut Vec<Collected<'a>>,
+    ) {
+        let directives = match apply_conditions(&field.directives) {
+            Some(directives) => directives,
+            None => return,
+        };
+        let alias = field
+            .alias
+            .as_ref()
+            .filter(|alias| alias.value != field.name.value);
+        let normalized = FieldNode {
+            name: field.name.clone(),
+            alias: alias.cloned(),
+            arguments: sort_arguments(&field.arguments),
+            directives,
+            selections: field.selections.as_ref().map(|_| Vec::new()),
+            span: field.span,
+        };
+        let response_key = alias.unwrap_or(&field.name).value.as_str();
+
+        // Fields with the same response key are merged when they are otherwise the same
+        let existing = collected.iter_mut().find_map(|collected| match collected {
+            Collected::Field {
+                field, selections, ..
+            } if field.alias.as_ref().unwrap_or(&field.name).value == response_key
+                && field.name.value == normalized.name.value
+                && field.arguments == normalized.arguments
+                && field.directives == normalized.directives =>
+            {
+                Some(selections)
+            }
+            _ => None,
+        });
+        match existing {
+            Some(selections) => selections.extend(field.selections.iter().flatten()),
+            None => {
+                let field_type = parent
+                    .and_then(|parent| self.schema.field(parent, &field.name.value))
+                    .and_then(|definition| self.schema.named_type(&definition.field_type))
+                    .map(|field_type| field_type.name().value.as_str());
+                collected.push(Collected::Field {
+                    field: normalized,
+                    field_type,
+                    selections: field.selections.iter().flatten().collect(),
+                    fragment_path: fragment_path.to_vec(),
+                });
+            }
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn collect_fragment(
+        &self,
+        parent: Option<&'a str>,
+        type_condition: Option<&'a NamedTypeNode>,
+        directives: Option<Directives>,
+        selections: &'a [Selection],
+        span: crate::token::Span,
+        fragment_path: &mut Vec<&'a str>,
+        collected: &mut Vec<Collected<'a>>,
+    ) {
+        let selections: Vec<&Selection> = selections.iter().collect();
+        let applies = type_condition
+            .is_none_or(|type_condition| self.always_applies(parent, &type_condition.name.value));
+        if directives.is_none() && applies {
+            self.collect(parent, &selections, fragment_path, collected);
+            return;
+        }
+
+        let type_condition = type_condition.filter(|_| !applies);
+        let existing = collected.iter_mut().find_map(|collected| match collected {
+            Collected::Fragment {
+                fragment,
+                selections,
+                ..
+            } if fragment.node_type == type_condition.cloned()
+                && fragment.directives == directives =>
+            {
+                Some(selections)
+            }
+            _ => None,
+        });
+        match existing {
+            Some(existing) => existing.extend(selections),
+            None => collected.push(Collected::Fragment {
+                fragment: InlineFragmentSpreadNode {
+                    node_type: type_condition.cloned(),
+                    directives,
+                    selections: Vec::new(),
+                    span,
+                },
+                selections,
+                fragment_path: fragment_path.clone(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const SCHEMA: &str = r#"type Query {
+  hero(episode: Episode, filter: Filter): Character
+  search(text: String!, first: Int): [Result!]!
+}
+input Filter { side: String, minAge: Int }
+enum Episode { NEWHOPE EMPIRE JEDI }
+interface Character { id: ID! name: String friends: [Character] }
+type Human implements Character { id: ID! name: String friends: [Character] height: Float }
+type Droid implements Character { id: ID! name: String friends: [Character] model: String }
+union Result = Human | Droid"#;
+
+    fn normalized(query: &str, operation_name: Option<&str>) -> Result<String, String> {
+        let schema = Schema::new(vec![parse(SCHEMA).unwrap()]).unwrap();
+        normalize(&schema, &parse(query).unwrap(), operation_name)
+            .map(|document| document.to_string().trim_end().to_string())
+            .map_err(|error| error.message)
+    }
+
+    #[test]
+    fn inlines_fragments() {
+        assert_eq!(
+            normalized(
+                r#"query Hero {
+  hero {
+    ...CharacterFields
+    ... { id }
+    ... on Character { name }
+    ... on Human { height }
+    ...HumanFields
+  }
+  search(text: "a") { ... on Human { name } }
+}
+fragment CharacterFields on Character { id friends { ...Names } }
+fragment Names on Character { name }
+fragment HumanFields on Human { name }"#,
+                None
+            ),
+            Ok(String::from(
+                r#"query Hero {
+  hero {
+    id
+    friends {
+      name
+    }
+    name
+    ... on Human {
+      height
+      name
+    }
+  }
+  search(text: "a") {
+    ... on Human {
+      name
+    }
+  }
+}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn merges_duplicate_fields() {
+        assert_eq!(
+            normalized(
+                r#"{
+  hero { id friends { id } }
+  hero { name: name friends { name } }
+  other: hero { id }
+  hero(episode: JEDI) { id }
+}"#,
+                None
+            ),
+            Ok(String::from(
+                r#"{
+  hero {
+    id
+    friends {
+      id
+      name
+    }
+    name
+  }
+  other: hero {
+    id
+  }
+  hero(episode: JEDI) {
+    id
+  }
+}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn applies_literal_conditions() {
+        assert_eq!(
+            normalized(
+                r#"query Hero($withFriends: Boolean!) {
+  hero {
+    id @skip(if: false)
+    name @include(if: false)
+    friends @include(if: $withFriends) { id }
+    ... on Human @skip(if: true) { height }
+    ... on Droid @include(if: true) { model }
+  }
+}"#,
+                None
+            ),
+            Ok(String::from(
+                r#"query Hero($withFriends: Boolean!) {
+  hero {
+    id
+    friends @include(if: $withFriends) {
+      id
+    }
+    ... on Droid {
+      model
+    }
+  }
+}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn sorts_arguments() {
+        assert_eq!(
+            normalized(
+                r#"{
+  hero(filter: {side: "light", minAge: 3}, episode: JEDI) { id }
+  hero(episode: JEDI, filter: {minAge: 3, side: "light"}) { name }
+}"#,
+                None
+            ),
+            Ok(String::from(
+                r#"{
+  hero(episode: JEDI, filter: {minAge: 3, side: "light"}) {
+    id
+    name
+  }
+}"#
+            ))
+        );
+    }
+
+    #[test]
+    fn picks_the_operation() {
+        let document = "query A { hero { id } }\nquery B { hero { name } }";
+        assert_eq!(
+            normalized(document, Some("B")),
+            Ok(String::from("query B {\n  hero {\n    name\n  }\n}"))
+        );
+        assert_eq!(
+            normalized(document, Some("C")),
+            Err(String::from("Unknown operation named C"))
+        );
+        assert_eq!(
+            normalized(document, None),
+            Err(String::from(
+                "Must provide an operation name when the document contains multiple operations"
+            ))
+        );
+    }
+}
@@ -0,0 +1,189 @@
+//! Canonical text and hashes of executable documents, so operations can be registered ahead of
+//! time and sent by hash instead of in full, as with automatic persisted queries.
+//!
+//! Documents that differ only in formatting, comments, the order of their definitions or the
+//! order of arguments have the same canonical text, and so the same hash. The canonical text:
+//! - keeps no comments or commas, and only the whitespace needed to separate names and values
+//! - prints every string quoted, so a block string and a quoted string of the same value match
+//! - lists operations by name followed by fragments by name, with any other definitions last
+//! - sorts arguments, variable definitions and the fields of input objects by name
+//!
+//! Selections keep their order, as it decides the order of fields in the response.
+//!
+//! # Example
+//!
+//! ```
+//! use syntax::persisted;
+//!
+//! let first = syntax::parse(
+//!     r#"# Loads a user
+//! query User($id: ID!) {
+//!   user(id: $id, active: true) { ...Names }
+//! }
+//! fragment Names on User { name }"#,
+//! )
+//! .unwrap();
+//! let second = syntax::parse(
+//!     "fragment Names on User{name} query User($id:ID!){user(active:true,id:$id){...Names}}",
+//! )
+//! .unwrap();
+//!
+//! assert_eq!(
+//!     persisted::canonical_text(&first),
+//!     "query User($id:ID!){user(active:true id:$id){...Names}}fragment Names on User{name}"
+//! );
+//! assert_eq!(persisted::hash(&first), persisted::hash(&second));
+//! ```
+
+use crate::document::Document;
+use crate::nodes::*;
+use crate::normalize::{sort_arguments, sort_value};
+use crate::printer;
+use crate::visitor::{walk_mut, VisitorMut};
+use sha2::{Digest, Sha256};
+
+/// Produces the canonical text of a document.
+pub fn canonical_text(document: &Document) -> String {
+    let mut document = document.clone();
+    document.definitions.sort_by_key(definition_order);
+    walk_mut::document(&mut Canonicalizer, &mut document);
+    printer::print_compact(&document)
+}
+
+/// The SHA-256 of the canonical text of a document, as lowercase hexadecimal.
+pub fn hash(document: &Document) -> String {
+    Sha256::digest(canonical_text(document).as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+fn definition_order(definition: &DefinitionNode) -> (u8, String) {
+    match definition {
+        DefinitionNode::Executable(ExecutableDefinitionNode::Operation(operation)) => (
+            0,
+            operation
+                .name()
+                .map(|name| name.value.clone())
+                .unwrap_or_default(),
+        ),
+        DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(fragment)) => {
+            (1, fragment.name.value.clone())
+        }
+        _ => (2, String::new()),
+    }
+}
+
+/// Sorts the arguments, variable definitions and input object fields of a document.
+struct Canonicalizer;
+
+impl VisitorMut for Canonicalizer {
+    fn enter_query_definition(&mut self, node: &mut QueryDefinitionNode) {
+        sort_variables(&mut node.variables);
+    }
+
+    fn enter_mutation_definition(&mut self, node: &mut MutationDefinitionNode) {
+        sort_variables(&mut node.variables);
+    }
+
+    fn enter_subscription_definition(&mut self, node: &mut SubscriptionDefinitionNode) {
+        sort_variables(&mut node.variables);
+    }
+
+    fn enter_variable_definition(&mut self, node: &mut VariableDefinitionNode) {
+        node.default_value = node.default_value.as_ref().map(sort_value);
+    }
+
+    fn enter_field(&mut self, node: &mut FieldNode) {
+        node.arguments = sort_arguments(&node.arguments);
+    }
+
+    fn enter_directive(&mut self, node: &mut DirectiveNode) {
+        node.arguments = sort_arguments(&node.arguments);
+    }
+}
+
+fn sort_variables(variables: &mut Option<Variables>) {
+    if let Some(variables) = variables {
+        variables
+            .sort_by(|first, second| first.variable.name.value.cmp(&second.variable.name.value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn canonical(source: &str) -> String {
+        canonical_text(&parse(source).unwrap())
+    }
+
+    #[test]
+    fn strips_whitespace_and_comments() {
+        assert_eq!(
+            canonical(
+                r#"
+# The current user
+query Me {
+  me {
+    # Only the name
+    name
+    friends(first: 10, names: ["a b", "c"]) @include(if: true) { id }
+    ... on Admin { level }
+  }
+}"#
+            ),
+            "query Me{me{name \
+             friends(first:10 names:[\"a b\" \"c\"])@include(if:true){id}...on Admin{level}}}"
+        );
+    }
+
+    #[test]
+    fn orders_definitions_and_arguments() {
+        assert_eq!(
+            canonical(
+                r#"fragment B on User { id }
+mutation Save($b: Int, $a: Filter = {z: 1, y: [{d: 2, c: 3}]}) @audit(when: NOW, by: "me") {
+  save(b: $b, a: $a) { ...B }
+}
+fragment A on User { name }
+query Load { user(id: 1, active: true) { ...A } }"#
+            ),
+            concat!(
+                "query Load{user(active:true id:1){...A}}",
+                "mutation Save($a:Filter={y:[{c:3 d:2}]z:1}$b:Int)@audit(by:\"me\" when:NOW)",
+                "{save(a:$a b:$b){...B}}",
+                "fragment A on User{name}fragment B on User{id}"
+            )
+        );
+    }
+
+    #[test]
+    fn quotes_every_string() {
+        assert_eq!(
+            canonical("{ user(bio: \"\"\"\n  A \"quote\"\n\"\"\", name: \"A\\tB\") { id } }"),
+            r#"{user(bio:"A \"quote\"" name:"A\tB"){id}}"#
+        );
+        assert_eq!(
+            canonical(r#"{ user(bio: """A "quote" """) { id } }"#),
+            canonical(r#"{ user(bio: "A \"quote\" ") { id } }"#)
+        );
+    }
+
+    #[test]
+    fn keeps_the_order_of_selections() {
+        assert_ne!(canonical("{ a b }"), canonical("{ b a }"));
+    }
+
+    #[test]
+    fn hashes_the_canonical_text() {
+        let document = parse("{\n  user {\n    id\n  }\n}").unwrap();
+        assert_eq!(canonical_text(&document), "{user{id}}");
+        assert_eq!(
+            hash(&document),
+            "56064d9c0c88312308a7cbb35c3ca6fe8d1689cb69eb63211e1b9d38f44f8d6e"
+        );
+        assert_eq!(hash(&document), hash(&parse("{user{id}}").unwrap()));
+    }
+}
@@ -0,0 +1,897 @@
+//! Turns a [`Document`] back into GraphQL text.
+//!
+//! The printer emits a canonical form of the document: one definition per block, two space
+//! indentation, descriptions as block strings and arguments on a single line. Printing a
+//! parsed document and parsing the result yields an equal document.
+//!
+//! # Example
+//!
+//! ```
+//! use syntax::parse;
+//! use syntax::printer::print;
+//!
+//! let document = parse("type Obj { name: String, id: Int! }").unwrap();
+//! assert_eq!(print(&document), "type Obj {\n  name: String\n  id: Int!\n}\n");
+//! ```
+//!
+//! [`Document`]: ../document/struct.Document.html
+
+use crate::document::Document;
+use crate::nodes::enum_type_extension::EnumTypeExtensionNode;
+use crate::nodes::input_type_extension::InputTypeExtensionNode;
+use crate::nodes::interface_type_extension::InterfaceTypeExtensionNode;
+use crate::nodes::object_type_extension::ObjectTypeExtensionNode;
+use crate::nodes::scalar_type_extension::ScalarTypeExtensionNode;
+use crate::nodes::schema_extension::SchemaExtensionNode;
+use crate::nodes::union_type_extension::UnionTypeExtensionNode;
+use crate::nodes::*;
+use crate::strings;
+
+const INDENT: &str = "  ";
+
+/// Print a [`Document`] as GraphQL text.
+///
+/// [`Document`]: ../document/struct.Document.html
+pub fn print(document: &Document) -> String {
+    let mut printer = Printer::new();
+    printer.print_document(document);
+    printer.output
+}
+
+/// Print a [`Document`] on a single line, with only the whitespace that separates names and
+/// values. Strings are printed quoted, even those written as block strings.
+///
+/// [`Document`]: ../document/struct.Document.html
+pub(crate) fn print_compact(document: &Document) -> String {
+    let mut printer = Printer::new();
+    printer.compact = true;
+    printer.print_document(document);
+    printer.output
+}
+
+/// Print a type reference such as `[String!]!`.
+pub(crate) fn print_type(type_node: &TypeNode) -> String {
+    let mut printer = Printer::new();
+    printer.print_type(type_node);
+    printer.output
+}
+
+/// Print a value such as `{name: "Luke", episodes: [NEWHOPE]}`.
+pub(crate) fn print_value(value: &ValueNode) -> String {
+    let mut printer = Printer::new();
+    printer.print_value(value);
+    printer.output
+}
+
+struct Printer {
+    output: String,
+    depth: usize,
+    /// Whether whitespace and commas are left out where they are not needed
+    compact: bool,
+}
+
+impl Printer {
+    fn new() -> Printer {
+        Printer {
+            output: String::new(),
+            depth: 0,
+            compact: false,
+        }
+    }
+
+    fn write(&mut self, content: &str) {
+        if !self.compact {
+            self.output.push_str(content);
+            return;
+        }
+        for token in content.split(|c: char| c.is_whitespace() || c == ',') {
+            self.write_token(token);
+        }
+    }
+
+    /// Writes a token in compact output, separating it from a name or value before it with a
+    /// space when it is a name or value itself.
+    fn write_token(&mut self, token: &str) {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '-' || c == '"';
+        if token.starts_with(is_word) && self.output.ends_with(is_word) {
+            self.output.push(' ');
+        }
+        self.output.push_str(token);
+    }
+
+    fn write_indent(&mut self) {
+        if self.compact {
+            return;
+        }
+        for _ in 0..self.depth {
+            self.output.push_str(INDENT);
+        }
+    }
+
+    fn print_document(&mut self, document: &Document) {
+        for (index, definition) in document.definitions.iter().enumerate() {
+            if index > 0 {
+                self.write("\n");
+            }
+            self.print_definition(definition);
+            self.write("\n");
+        }
+    }
+
+    fn print_definition(&mut self, definition: &DefinitionNode) {
+        match definition {
+            DefinitionNode::Executable(executable) => self.print_executable(executable),
+            DefinitionNode::TypeSystem(type_system) => self.print_type_system(type_system),
+            DefinitionNode::Extension(extension) => self.print_extension(extension),
+        }
+    }
+
+    fn print_description(&mut self, description: &Description) {
+        if let Some(description) = description {
+            self.write_indent();
+            self.write_block_string(&description.value);
+            self.write("\n");
+        }
+    }
+
+    fn print_name(&mut self, name: &NameNode) {
+        self.write(&name.value);
+    }
+
+    fn print_type(&mut self, type_node: &TypeNode) {
+        match type_node {
+            TypeNode::Named(named) => self.print_name(&named.name),
+            TypeNode::List(list) => {
+                self.write("[");
+                self.print_type(&list.list_type);
+                self.write("]");
+            }
+            TypeNode::NonNull(inner) => {
+                self.print_type(inner);
+                self.write("!");
+            }
+        }
+    }
+
+    fn print_value(&mut self, value: &ValueNode) {
+        match value {
+            ValueNode::Variable(variable) => {
+                self.write("$");
+                self.print_name(&variable.name);
+            }
+            ValueNode::Int(int) => self.write(&int.raw),
+            ValueNode::Float(float) => self.write(&float.raw),
+            ValueNode::Str(string) => self.print_string(string),
+            ValueNode::Bool(boolean) => self.write(if boolean.value { "true" } else { "false" }),
+            ValueNode::Null => self.write("null"),
+            ValueNode::Enum(enum_value) => self.write(&enum_value.value),
+            ValueNode::List(list) => {
+                self.write("[");
+                for (index, value) in list.values.iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+                    self.print_value(value);
+                }
+                self.write("]");
+            }
+            ValueNode::Object(object) => {
+                self.write("{");
+                for (index, field) in object.fields.iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+                    self.print_name(&field.name);
+                    self.write(": ");
+                    self.print_value(&field.value);
+                }
+                self.write("}");
+            }
+        }
+    }
+
+    fn print_string(&mut self, string: &StringValueNode) {
+        if self.compact {
+            self.write_token(&strings::print_string(&string.value));
+        } else if string.is_block() {
+            self.write_block_string(&string.value);
+        } else {
+            self.write(&strings::print_string(&string.value));
+        }
+    }
+
+    /// Writes a block string, falling back to a quoted string for values a block string cannot
+    /// hold.
+    fn write_block_string(&mut self, value: &str) {
+        if self.compact {
+            self.write_token(&strings::print_string(value));
+            return;
+        }
+        let indent = INDENT.repeat(self.depth);
+        match strings::print_block_string(value, &indent) {
+            Some(block) => self.write(&block),
+            None => self.write(&strings::print_string(value)),
+        }
+    }
+
+    fn print_arguments(&mut self, arguments: &Option<Arguments>) {
+        if let Some(arguments) = arguments {
+            if arguments.is_empty() {
+                return;
+            }
+            self.write("(");
+            for (index, argument) in arguments.iter().enumerate() {
+                if index > 0 {
+                    self.write(", ");
+                }
+                self.print_name(&argument.name);
+                self.write(": ");
+                self.print_value(&argument.value);
+            }
+            self.write(")");
+        }
+    }
+
+    fn print_directives(&mut self, directives: &Option<Directives>) {
+        if let Some(directives) = directives {
+            for directive in directives {
+                self.write(" @");
+                self.print_name(&directive.name);
+                self.print_arguments(&directive.arguments);
+            }
+        }
+    }
+
+    fn print_interfaces(&mut self, interfaces: &Option<Vec<NamedTypeNode>>) {
+        if let Some(interfaces) = interfaces {
+            if interfaces.is_empty() {
+                return;
+            }
+            self.write(" implements ");
+            for (index, interface) in interfaces.iter().enumerate() {
+                if index > 0 {
+                    self.write(" & ");
+                }
+                self.print_name(&interface.name);
+            }
+        }
+    }
+
+    fn print_input_value(&mut self, input_value: &InputValueDefinitionNode) {
+        self.print_name(&input_value.name);
+        self.write(": ");
+        self.print_type(&input_value.input_type);
+        if let Some(default_value) = &input_value.default_value {
+            self.write(" = ");
+            self.print_value(default_value);
+        }
+        self.print_directives(&input_value.directives);
+    }
+
+    /// Arguments are printed inline, unless one of them has a description.
+    fn print_arguments_definition(&mut self, arguments: &Option<ArgumentDefinitions>) {
+        if let Some(arguments) = arguments {
+            if arguments.is_empty() {
+                return;
+            }
+            if arguments
+                .iter()
+                .any(|argument| argument.description.is_some())
+            {
+                self.write("(\n");
+                self.depth += 1;
+                for argument in arguments {
+                    self.print_description(&argument.description);
+                    self.write_indent();
+                    self.print_input_value(argument);
+                    self.write("\n");
+                }
+                self.depth -= 1;
+                self.write_indent();
+                self.write(")");
+            } else {
+                self.write("(");
+                for (index, argument) in arguments.iter().enumerate() {
+                    if index > 0 {
+                        self.write(", ");
+                    }
+                    self.print_input_value(argument);
+                }
+                self.write(")");
+            }
+        }
+    }
+
+    /// Prints a `{ ... }` block, calling `print_item` for each item on its own line.
+    fn print_block<T, F>(&mut self, items: &[T], mut print_item: F)
+    where
+        F: FnMut(&mut Printer, &T),
+    {
+        if items.is_empty() {
+            self.write(" {}");
+            return;
+        }
+        self.write(" {\n");
+        self.depth += 1;
+        for item in items {
+            print_item(self, item);
+            self.write("\n");
+        }
+        self.depth -= 1;
+        self.write_indent();
+        self.write("}");
+    }
+
+    fn print_field_definition(&mut self, field: &FieldDefinitionNode) {
+        self.print_description(&field.description);
+        self.write_indent();
+        self.print_name(&field.name);
+        self.print_arguments_definition(&field.arguments);
+        self.write(": ");
+        self.print_type(&field.field_type);
+        self.print_directives(&field.directives);
+    }
+
+    fn print_input_field(&mut self, field: &InputValueDefinitionNode) {
+        self.print_description(&field.description);
+        self.write_indent();
+        self.print_input_value(field);
+    }
+
+    fn print_enum_value(&mut self, value: &EnumValueDefinitionNode) {
+        self.print_description(&value.description);
+        self.write_indent();
+        self.print_name(&value.name);
+        self.print_directives(&value.directives);
+    }
+
+    fn print_operation_type(&mut self, operation: &OperationTypeDefinitionNode) {
+        self.write_indent();
+        self.write(match operation.operation {
+            Operation::Query => "query",
+            Operation::Mutation => "mutation",
+            Operation::Subscription => "subscription",
+        });
+        self.write(": ");
+        self.print_name(&operation.node_type.name);
+    }
+
+    fn print_union_types(&mut self, types: &[NamedTypeNode]) {
+        self.write(" = ");
+        for (index, member) in types.iter().enumerate() {
+            if index > 0 {
+                self.write(" | ");
+            }
+            self.print_name(&member.name);
+        }
+    }
+
+    fn print_type_system(&mut self, definition: &TypeSystemDefinitionNode) {
+        match definition {
+            TypeSystemDefinitionNode::Schema(schema) => {
+                self.print_description(&schema.description);
+                self.write("schema");
+                self.print_directives(&schema.directives);
+                self.print_block(&schema.operations, Printer::print_operation_type);
+            }
+            TypeSystemDefinitionNode::Type(type_definition) => {
+                self.print_type_definition(type_definition)
+            }
+            TypeSystemDefinitionNode::Directive(directive) => {
+                self.print_directive_definition(directive)
+            }
+        }
+    }
+
+    fn print_type_definition(&mut self, definition: &TypeDefinitionNode) {
+        match definition {
+            TypeDefinitionNode::Scalar(scalar) => {
+                self.print_description(&scalar.description);
+                self.write("scalar ");
+                self.print_name(&scalar.name);
+                self.print_directives(&scalar.directives);
+            }
+            TypeDefinitionNode::Object(object) => {
+                self.print_description(&object.description);
+                self.write("type ");
+                self.print_name(&object.name);
+                self.print_interfaces(&object.interfaces);
+                self.print_directives(&object.directives);
+                self.print_block(&object.fields, Printer::print_field_definition);
+            }
+            TypeDefinitionNode::Interface(interface) => {
+                self.print_description(&interface.description);
+                self.write("interface ");
+                self.print_name(&interface.name);
+                self.print_interfaces(&interface.interfaces);
+                self.print_directives(&interface.directives);
+                self.print_block(&interface.fields, Printer::print_field_definition);
+            }
+            TypeDefinitionNode::Union(union) => {
+                self.print_description(&union.description);
+                self.write("union ");
+                self.print_name(&union.name);
+                self.print_directives(&union.directives);
+                self.print_union_types(&union.types);
+            }
+            TypeDefinitionNode::Enum(enum_type) => {
+                self.print_description(&enum_type.description);
+                self.write("enum ");
+                self.print_name(&enum_type.name);
+                self.print_directives(&enum_type.directives);
+                self.print_block(&enum_type.values, Printer::print_enum_value);
+            }
+            TypeDefinitionNode::Input(input) => {
+                self.print_description(&input.description);
+                self.write("input ");
+                self.print_name(&input.name);
+                self.print_directives(&input.directives);
+                self.print_block(&input.fields, Printer::print_input_field);
+            }
+        }
+    }
+
+    fn print_directive_definition(&mut self, directive: &DirectiveDefinitionNode) {
+        self.print_description(&directive.description);
+        self.write("directive @");
+        self.print_name(&directive.name);
+        self.print_arguments_definition(&directive.arguments);
+        if directive.repeatable {
+            self.write(" repeatable");
+        }
+        self.write(" on ");
+        for (index, location) in directive.locations.iter().enumerate() {
+            if index > 0 {
+                self.write(" | ");
+            }
+            self.write(directive_location_name(location));
+        }
+    }
+
+    fn print_extension(&mut self, extension: &TypeSystemExtensionNode) {
+        match extension {
+            TypeSystemExtensionNode::Schema(schema) => self.print_schema_extension(schema),
+            TypeSystemExtensionNode::Scalar(scalar) => self.print_scalar_extension(scalar),
+            TypeSystemExtensionNode::Object(object) => self.print_object_extension(object),
+            TypeSystemExtensionNode::Interface(interface) => {
+                self.print_interface_extension(interface)
+            }
+            TypeSystemExtensionNode::Union(union) => self.print_union_extension(union),
+            TypeSystemExtensionNode::Enum(enum_type) => self.print_enum_extension(enum_type),
+            TypeSystemExtensionNode::Input(input) => self.print_input_extension(input),
+        }
+    }
+
+    fn print_schema_extension(&mut self, schema: &SchemaExtensionNode) {
+        self.print_description(&schema.description);
+        self.write("extend schema");
+        self.print_directives(&schema.directives);
+        if let Some(operations) = &schema.operations {
+            self.print_block(operations, Printer::print_operation_type);
+        }
+    }
+
+    fn print_scalar_extension(&mut self, scalar: &ScalarTypeExtensionNode) {
+        self.print_description(&scalar.description);
+        self.write("extend scalar ");
+        self.print_name(&scalar.name);
+        self.print_directives(&scalar.directives);
+    }
+
+    fn print_object_extension(&mut self, object: &ObjectTypeExtensionNode) {
+        self.print_description(&object.description);
+        self.write("extend type ");
+        self.print_name(&object.name);
+        self.print_interfaces(&object.interfaces);
+        self.print_directives(&object.directives);
+        if let Some(fields) = &object.fields {
+            self.print_block(fields, Printer::print_field_definition);
+        }
+    }
+
+    fn print_interface_extension(&mut self, interface: &InterfaceTypeExtensionNode) {
+        self.print_description(&interface.description);
+        self.write("extend interface ");
+        self.print_name(&interface.name);
+        self.print_interfaces(&interface.interfaces);
+        self.print_directives(&interface.directives);
+        if let Some(fields) = &interface.fields {
+            self.print_block(fields, Printer::print_field_definition);
+        }
+    }
+
+    fn print_union_extension(&mut self, union: &UnionTypeExtensionNode) {
+        self.print_description(&union.description);
+        self.write("extend union ");
+        self.print_name(&union.name);
+        self.print_directives(&union.directives);
+        if let Some(types) = &union.types {
+            self.print_union_types(types);
+        }
+    }
+
+    fn print_enum_extension(&mut self, enum_type: &EnumTypeExtensionNode) {
+        self.print_description(&enum_type.description);
+        self.write("extend enum ");
+        self.print_name(&enum_type.name);
+        self.print_directives(&enum_type.directives);
+        if let Some(values) = &enum_type.values {
+            self.print_block(values, Printer::print_enum_value);
+        }
+    }
+
+    fn print_input_extension(&mut self, input: &InputTypeExtensionNode) {
+        self.print_description(&input.description);
+        self.write("extend input ");
+        self.print_name(&input.name);
+        self.print_directives(&input.directives);
+        if let Some(fields) = &input.fields {
+            self.print_block(fields, Printer::print_input_field);
+        }
+    }
+
+    fn print_executable(&mut self, definition: &ExecutableDefinitionNode) {
+        match definition {
+            ExecutableDefinitionNode::Operation(OperationTypeNode::Query(query)) => {
+                if query.name.is_none() && query.variables.is_none() && query.directives.is_none() {
+                    // Query shorthand
+                    self.write("{");
+                    self.print_selections(&query.selections);
+                } else {
+                    self.print_operation(
+                        "query",
+                        &query.name,
+                        &query.variables,
+                        &query.directives,
+                        &query.selections,
+                    );
+                }
+            }
+            ExecutableDefinitionNode::Operation(OperationTypeNode::Mutation(mutation)) => self
+                .print_operation(
+                    "mutation",
+                    &mutation.name,
+                    &mutation.variables,
+                    &mutation.directives,
+                    &mutation.selections,
+                ),
+            ExecutableDefinitionNode::Operation(OperationTypeNode::Subscription(subscription)) => {
+                self.print_operation(
+                    "subscription",
+                    &subscription.name,
+                    &subscription.variables,
+                    &subscription.directives,
+                    &subscription.selections,
+                )
+            }
+            ExecutableDefinitionNode::Fragment(fragment) => {
+                self.write("fragment ");
+                self.print_name(&fragment.name);
+                self.write(" on ");
+                self.print_name(&fragment.node_type.name);
+                self.print_directives(&fragment.directives);
+                self.print_selection_set(&fragment.selections);
+            }
+        }
+    }
+
+    fn print_operation(
+        &mut self,
+        keyword: &str,
+        name: &Option<NameNode>,
+        variables: &Option<Variables>,
+        directives: &Option<Directives>,
+        selections: &[Selection],
+    ) {
+        self.write(keyword);
+        if let Some(name) = name {
+            self.write(" ");
+            self.print_name(name);
+        }
+        self.print_variables(variables);
+        self.print_directives(directives);
+        self.print_selection_set(selections);
+    }
+
+    fn print_variables(&mut self, variables: &Option<Variables>) {
+        if let Some(variables) = variables {
+            if variables.is_empty() {
+                return;
+            }
+            self.write("(");
+            for (index, variable) in variables.iter().enumerate() {
+                if index > 0 {
+                    self.write(", ");
+                }
+                self.write("$");
+                self.print_name(&variable.variable.name);
+                self.write(": ");
+                self.print_type(&variable.variable_type);
+                if let Some(default_value) = &variable.default_value {
+                    self.write(" = ");
+                    self.print_value(default_value);
+                }
+            }
+            self.write(")");
+        }
+    }
+
+    fn print_selection_set(&mut self, selections: &[Selection]) {
+        self.write(" {");
+        self.print_selections(selections);
+    }
+
+    /// Prints the selections after an opening brace, along with the closing brace.
+    fn print_selections(&mut self, selections: &[Selection]) {
+        if selections.is_empty() {
+            self.write("}");
+            return;
+        }
+        self.write("\n");
+        self.depth += 1;
+        for selection in selections {
+            self.write_indent();
+            self.print_selection(selection);
+            self.write("\n");
+        }
+        self.depth -= 1;
+        self.write_indent();
+        self.write("}");
+    }
+
+    fn print_selection(&mut self, selection: &Selection) {
+        match selection {
+            Selection::Field(field) => {
+                if let Some(alias) = &field.alias {
+                    self.print_name(alias);
+                    self.write(": ");
+                }
+                self.print_name(&field.name);
+                self.print_arguments(&field.arguments);
+                self.print_directives(&field.directives);
+                if let Some(selections) = &field.selections {
+                    self.print_selection_set(selections);
+                }
+            }
+            Selection::Fragment(FragmentSpread::Node(spread)) => {
+                self.write("...");
+                self.print_name(&spread.name);
+                self.print_directives(&spread.directives);
+            }
+            Selection::Fragment(FragmentSpread::Inline(inline)) => {
+                self.write("...");
+                if let Some(node_type) = &inline.node_type {
+                    self.write(" on ");
+                    self.print_name(&node_type.name);
+                }
+                self.print_directives(&inline.directives);
+                self.print_selection_set(&inline.selections);
+            }
+        }
+    }
+}
+
+pub(crate) fn directive_location_name(location: &DirectiveLocation) -> &'static str {
+    match location {
+        DirectiveLocation::Executable(location) => match location {
+            ExecutableDirectiveLocation::Query => "QUERY",
+            ExecutableDirectiveLocation::Mutation => "MUTATION",
+            ExecutableDirectiveLocation::Subscription => "SUBSCRIPTION",
+            ExecutableDirectiveLocation::Field => "FIELD",
+            ExecutableDirectiveLocation::FragmentDefinition => "FRAGMENT_DEFINITION",
+            ExecutableDirectiveLocation::FragmentSpread => "FRAGMENT_SPREAD",
+            ExecutableDirectiveLocation::InlineFragment => "INLINE_FRAGMENT",
+            ExecutableDirectiveLocation::VariableDefinition => "VARIABLE_DEFINITION",
+        },
+        DirectiveLocation::TypeSystem(location) => match location {
+            TypeSystemDirectiveLocation::Schema => "SCHEMA",
+            TypeSystemDirectiveLocation::Scalar => "SCALAR",
+            TypeSystemDirectiveLocation::Object => "OBJECT",
+            TypeSystemDirectiveLocation::FieldDefinition => "FIELD_DEFINITION",
+            TypeSystemDirectiveLocation::ArgumentDefinition => "ARGUMENT_DEFINITION",
+            TypeSystemDirectiveLocation::Interface => "INTERFACE",
+            TypeSystemDirectiveLocation::Union => "UNION",
+            TypeSystemDirectiveLocation::Enum => "ENUM",
+            TypeSystemDirectiveLocation::EnumValue => "ENUM_VALUE",
+            TypeSystemDirectiveLocation::InputObject => "INPUT_OBJECT",
+            TypeSystemDirectiveLocation::InputFieldDefinition => "INPUT_FIELD_DEFINITION",
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn assert_round_trip(input: &str) {
+        let document = parse(input).unwrap();
+        let printed = print(&document);
+        println!("Printed:\n{}", printed);
+        let reparsed = parse(&printed);
+        assert!(reparsed.is_ok(), "{:?}", reparsed);
+        assert_eq!(reparsed.unwrap(), document);
+    }
+
+    #[test]
+    fn prints_type_system_definitions() {
+        let document = parse(
+            r#"
+"""A person"""
+type User implements Node & Entity @key(fields: "id") {
+  "The id"
+  id: ID!
+  friends(first: Int = 10, after: String): [User!]! @deprecated
+}
+enum Direction { NORTH SOUTH @deprecated }
+union SearchResult = User | Photo
+input UserFilter @oneOf { id: ID name: String }
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            print(&document),
+            r#""""A person"""
+type User implements Node & Entity @key(fields: "id") {
+  """The id"""
+  id: ID!
+  friends(first: Int = 10, after: String): [User!]! @deprecated
+}
+
+enum Direction {
+  NORTH
+  SOUTH @deprecated
+}
+
+union SearchResult = User | Photo
+
+input UserFilter @oneOf {
+  id: ID
+  name: String
+}
+"#
+        );
+    }
+
+    #[test]
+    fn prints_executable_definitions() {
+        let document = parse(
+            r#"query Hero($episode: Episode = JEDI, $withFriends: Boolean!) {
+  hero(episode: $episode) { name, ...Friends @include(if: $withFriends) }
+}
+fragment Friends on Character { friends { name } }
+{ me { id } }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            print(&document),
+            r#"query Hero($episode: Episode = JEDI, $withFriends: Boolean!) {
+  hero(episode: $episode) {
+    name
+    ...Friends @include(if: $withFriends)
+  }
+}
+
+fragment Friends on Character {
+  friends {
+    name
+  }
+}
+
+{
+  me {
+    id
+  }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn round_trips_type_system_document() {
+        assert_round_trip(
+            r#"
+"""
+A multiline
+description
+"""
+schema @core { query: Query, mutation: Mutation }
+scalar Date @format(pattern: "YYYY")
+interface Empty {}
+interface Node implements Entity { id: ID! }
+type Query {
+  node(
+    "The id to look up"
+    id: ID!
+    filter: Filter = {name: "x", tags: [1, 2], ratio: 1.0, exact: true, other: null}
+  ): Node
+}
+input Filter { name: String, tags: [Int!] = [] }
+enum Empty {}
+union U @dir = | A | B
+directive @index(unique: Boolean = false) repeatable on FIELD_DEFINITION | INPUT_FIELD_DEFINITION
+extend schema @extra { subscription: Subscription }
+extend scalar Date @tz
+extend type Query implements Root @extended { version: String }
+extend type Admin implements Sudo & Root
+extend interface Node @key { createdAt: Date }
+extend union U = C
+extend enum Direction { UP }
+extend input Filter { limit: Int }
+"#,
+        );
+    }
+
+    #[test]
+    fn round_trips_executable_document() {
+        assert_round_trip(
+            r#"
+query ($id: ID!, $n: Float = 2.5) {
+  user: node(id: $id) @skip(if: false) {
+    ... on User { name }
+    ... @include(if: true) { id }
+    ... { id }
+    ...Rest
+  }
+  empty {}
+}
+mutation Create($input: [UserInput!]!) @audit { createUser(input: $input) { id } }
+query @cached { me { id } }
+subscription { messageAdded(room: "general", limit: -10) { body } }
+fragment Rest on Node @traverse(depth: 1) { id }
+"#,
+        );
+    }
+
+    #[test]
+    fn round_trips_escaped_strings() {
+        let document = parse(
+            r#"type Query {
+  """
+  Finds a "user".
+    Indented line with \"""
+  """
+  user(greeting: String = "tab\t \"quote\" caf\u00e9 \uD83D\uDE00"): String
+}"#,
+        )
+        .unwrap();
+        let printed = print(&document);
+        assert_eq!(
+            printed,
+            r#"type Query {
+  """
+  Finds a "user".
+    Indented line with \"""
+  """
+  user(greeting: String = "tab\t \"quote\" café 😀"): String
+}
+"#
+        );
+        assert_eq!(parse(&printed).unwrap(), document);
+    }
+
+    #[test]
+    fn round_trips_numbers() {
+        let source = r#"query($id: BigUint = 340282366920938463463374607431768211455) {
+  items(ratio: 1.5E-3, scale: -2e+2, offset: 0) {
+    id
+  }
+}
+"#;
+        let document = parse(source).unwrap();
+        assert_eq!(print(&document), source);
+    }
+
+    #[test]
+    fn round_trips_default_document() {
+        let document = Document::default();
+        let printed = print(&document);
+        assert_eq!(parse(&printed).unwrap(), document);
+    }
+
+    #[test]
+    fn document_display_uses_printer() {
+        let document = parse("scalar Date").unwrap();
+        assert_eq!(document.to_string(), "scalar Date\n");
+    }
+}
@@ -0,0 +1,587 @@
+//! A registry of the types, directives and root operation types of a GraphQL schema.
+//!
+//! A [`Schema`] is built from one or more type system documents. The built-in scalars and
+//! directives of [`Document::default`] are always included, so a schema only has to define its
+//! own types.
+//!
+//! # Example
+//!
+//! ```
+//! use syntax::schema::Schema;
+//!
+//! let document = syntax::parse(
+//!     r#"type Query { hero: Character }
+//! interface Character { name: String }
+//! type Human implements Character { name: String }"#,
+//! )
+//! .unwrap();
+//! let schema = Schema::new(vec![document]).unwrap();
+//! assert!(schema.query_type().is_some());
+//! assert_eq!(schema.fields_of("Character").unwrap().len(), 1);
+//! assert_eq!(schema.possible_types("Character").len(), 1);
+//! ```
+//!
+//! [`Schema`]: struct.Schema.html
+//! [`Document::default`]: ../document/struct.Document.html
+
+use crate::document::Document;
+use crate::error::ValidationError;
+use crate::introspection;
+use crate::merge;
+use crate::nodes::*;
+use crate::validation::{executable, type_system};
+use crate::visitor::{walk, Visitor};
+use std::collections::HashMap;
+
+/// The types of a GraphQL schema, indexed by name.
+#[derive(Debug)]
+pub struct Schema {
+    types: Vec<TypeDefinitionNode>,
+    type_indices: HashMap<String, usize>,
+    directives: Vec<DirectiveDefinitionNode>,
+    /// `__typename`, `__schema` and `__type`, which are not defined by any type
+    meta_fields: Vec<FieldDefinitionNode>,
+    query_type: Option<String>,
+    mutation_type: Option<String>,
+    subscription_type: Option<String>,
+}
+
+impl Schema {
+    /// Builds a schema from type system documents.
+    ///
+    /// Type extensions are applied to the types they extend, including the built-in types, as
+    /// [`Document::merge`] does.
+    ///
+    /// Every problem found is returned: executable definitions in the documents, extensions
+    /// that cannot be applied, types and directives defined more than once, more than one
+    /// schema definition, root operation types that are not object types, references to types
+    /// that do not exist, and every broken type system rule of the spec, such as an object not
+    /// implementing the fields of its interfaces or a default value that does not match its
+    /// type.
+    ///
+    /// When no document contains a schema definition, the roots are the object types named
+    /// `Query`, `Mutation` and `Subscription`, for whichever of those exist.
+    ///
+    /// [`Document::merge`]: ../document/struct.Document.html#method.merge
+    pub fn new(documents: Vec<Document>) -> Result<Schema, Vec<ValidationError>> {
+        let mut schema = Schema {
+            types: Vec::new(),
+            type_indices: HashMap::new(),
+            directives: Vec::new(),
+            meta_fields: introspection::meta_fields(),
+            query_type: None,
+            mutation_type: None,
+            subscription_type: None,
+        };
+        let mut errors = Vec::new();
+
+        for definition in Document::default().definitions {
+            schema.add_definition(definition, &mut errors);
+        }
+
+        let mut schema_definition: Option<SchemaDefinitionNode> = None;
+        let mut extensions = Vec::new();
+        for document in documents {
+            for definition in document.definitions {
+                match definition {
+                    DefinitionNode::Extension(extension) => extensions.push(extension),
+                    DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Schema(definition)) => {
+                        if schema_definition.is_some() {
+                            errors.push(
+                                ValidationError::new("Must only define one schema")
+                                    .with_location(definition.span.start),
+                            );
+                        } else {
+                            schema_definition = Some(definition);
+                        }
+                    }
+                    definition => schema.add_definition(definition, &mut errors),
+                }
+            }
+        }
+
+        // Extensions apply to the built-in types as well, so they wait until every type is added
+        for extension in extensions {
+            let result = match extension {
+                TypeSystemExtensionNode::Schema(extension) => {
+                    merge::extend_schema(schema_definition.as_mut(), extension)
+                }
+                extension => {
+                    let index = extension
+                        .name()
+                        .and_then(|name| schema.type_indices.get(&name.value))
+                        .copied();
+                    merge::extend_type(index.map(|index| &mut schema.types[index]), extension)
+                }
+            };
+            if let Err(error) = result {
+                errors.push(error);
+            }
+        }
+
+        match schema_definition {
+            Some(definition) => schema.add_root_operations(&definition, &mut errors),
+            None => schema.add_default_root_operations(),
+        }
+        schema.validate_type_references(&mut errors);
+        errors.extend(type_system::validate(&schema));
+
+        if errors.is_empty() {
+            Ok(schema)
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn add_definition(&mut self, definition: DefinitionNode, errors: &mut Vec<ValidationError>) {
+        match definition {
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(type_definition)) => {
+                let name = type_definition.name().value.clone();
+                if self.type_indices.contains_key(&name) {
+                    errors.push(
+                        ValidationError::new(
+                            format!("Type {} is defined more than once", name).as_str(),
+                        )
+                        .with_location(type_definition.span().start),
+                    );
+                } else {
+                    self.type_indices.insert(name, self.types.len());
+                    self.types.push(type_definition);
+                }
+            }
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Directive(directive)) => {
+                if self.get_directive(&directive.name.value).is_some() {
+                    errors.push(
+                        ValidationError::new(
+                            format!(
+                                "Directive @{} is defined more than once",
+                                directive.name.value
+                            )
+                            .as_str(),
+                        )
+                        .with_location(directive.span.start),
+                    );
+                } else {
+                    self.directives.push(directive);
+                }
+            }
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Schema(_)) => (),
+            DefinitionNode::Executable(_) => errors.push(
+                ValidationError::new("A schema cannot contain executable definitions")
+                    .with_location(definition.span().start),
+            ),
+            DefinitionNode::Extension(_) => (),
+        }
+    }
+
+    fn add_root_operations(
+        &mut self,
+        definition: &SchemaDefinitionNode,
+        errors: &mut Vec<ValidationError>,
+    ) {
+        for operation in &definition.operations {
+            let name = &operation.node_type.name.value;
+            let root = match operation.operation {
+                Operation::Query => &mut self.query_type,
+                Operation::Mutation => &mut self.mutation_type,
+                Operation::Subscription => &mut self.subscription_type,
+            };
+            if root.is_some() {
+                errors.push(
+                    ValidationError::new(
+                        format!("Must only define one {:?} root type", operation.operation)
+                            .as_str(),
+                    )
+                    .with_location(operation.span.start),
+                );
+                continue;
+            }
+            *root = Some(name.clone());
+            match self.get_type(name) {
+                Some(TypeDefinitionNode::Object(_)) => (),
+                // Unknown types are reported with the rest of the type references
+                None => (),
+                Some(_) => errors.push(
+                    ValidationError::new(
+                        format!(
+                            "Invalid {:?} root type {}: Root types must be object types",
+                            operation.operation, name
+                        )
+                        .as_str(),
+                    )
+                    .with_location(operation.node_type.span.start),
+                ),
+            }
+        }
+    }
+
+    /// Without a schema definition, each root is the object type named after its operation.
+    fn add_default_root_operations(&mut self) {
+        let roots = [
+            ("Query", &mut self.query_type),
+            ("Mutation", &mut self.mutation_type),
+            ("Subscription", &mut self.subscription_type),
+        ];
+        for (name, root) in roots {
+            if let Some(&index) = self.type_indices.get(name) {
+                if let TypeDefinitionNode::Object(_) = self.types[index] {
+                    *root = Some(name.to_owned());
+                }
+            }
+        }
+    }
+
+    fn validate_type_references(&self, errors: &mut Vec<ValidationError>) {
+        let mut references = TypeReferences::default();
+        for type_definition in &self.types {
+            walk::type_definition(&mut references, type_definition);
+        }
+        for directive in &self.directives {
+            walk::directive_definition(&mut references, directive);
+        }
+        for named_type in references.named_types {
+            if self.get_type(&named_type.name.value).is_none() {
+                errors.push(
+                    ValidationError::new(
+                        format!("Unknown type {}", named_type.name.value).as_str(),
+                    )
+                    .with_location(named_type.span.start),
+                );
+            }
+        }
+        for root in [
+            &self.query_type,
+            &self.mutation_type,
+            &self.subscription_type,
+        ]
+        .iter()
+        .filter_map(|root| root.as_ref())
+        {
+            if self.get_type(root).is_none() {
+                errors.push(ValidationError::new(
+                    format!("Unknown root type {}", root).as_str(),
+                ));
+            }
+        }
+    }
+
+    /// Checks a query, mutation or subscription document against the schema using the
+    /// validation rules of the spec, returning every error found. Fields, arguments and their
+    /// values, fragments, variables and directives must all be valid for the schema before the
+    /// document is executed.
+    pub fn validate_document(&self, document: &Document) -> Result<(), Vec<ValidationError>> {
+        let errors = executable::validate(self, document);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Looks up a type by name.
+    pub fn get_type(&self, name: &str) -> Option<&TypeDefinitionNode> {
+        self.type_indices.get(name).map(|index| &self.types[*index])
+    }
+
+    /// Every type in the schema, in the order they were defined. The built-in types come first.
+    pub fn types(&self) -> impl Iterator<Item = &TypeDefinitionNode> {
+        self.types.iter()
+    }
+
+    /// Looks up the type named by a field or argument type, ignoring any list and non-null
+    /// wrappers.
+    pub fn named_type(&self, type_node: &TypeNode) -> Option<&TypeDefinitionNode> {
+        self.get_type(&type_node.named_type().name.value)
+    }
+
+    /// Looks up a directive definition by name, without the `@`.
+    pub fn get_directive(&self, name: &str) -> Option<&DirectiveDefinitionNode> {
+        self.directives
+            .iter()
+            .find(|directive| directive.name.value == name)
+    }
+
+    /// Every directive defined in the schema, in the order they were defined.
+    pub fn directives(&self) -> impl Iterator<Item = &DirectiveDefinitionNode> {
+        self.directives.iter()
+    }
+
+    /// The fields of an object or interface type. None when the type does not exist or has no
+    /// fields.
+    pub fn fields_of(&self, name: &str) -> Option<&[FieldDefinitionNode]> {
+        match self.get_type(name)? {
+            TypeDefinitionNode::Object(object) => Some(&object.fields),
+            TypeDefinitionNode::Interface(interface) => Some(&interface.fields),
+            _ => None,
+        }
+    }
+
+    /// Looks up a single field of an object or interface type. The `__typename` meta field is
+    /// found on every object, interface and union type, and the `__schema` and `__type` meta
+    /// fields on the query type.
+    pub fn field(&self, type_name: &str, field_name: &str) -> Option<&FieldDefinitionNode> {
+        if field_name.starts_with("__") {
+            return self.meta_field(type_name, field_name);
+        }
+        self.fields_of(type_name)?
+            .iter()
+            .find(|field| field.name.value == field_name)
+    }
+
+    /// The fields of an input type. None when the type does not exist or is not an input type.
+    pub fn input_fields_of(&self, name: &str) -> Option<&[InputValueDefinitionNode]> {
+        match self.get_type(name)? {
+            TypeDefinitionNode::Input(input) => Some(&input.fields),
+            _ => None,
+        }
+    }
+
+    /// The object types a value of the named type can be. An object type is only ever itself,
+    /// an interface can be any object that implements it and a union can be any of its members.
+    /// Other types have no possible types.
+    pub fn possible_types(&self, name: &str) -> Vec<&ObjectTypeDefinitionNode> {
+        match self.get_type(name) {
+            Some(TypeDefinitionNode::Object(object)) => vec![object],
+            Some(TypeDefinitionNode::Interface(_)) => self
+                .objects()
+                .filter(|object| {
+                    object.interfaces.as_ref().is_some_and(|interfaces| {
+                        interfaces
+                            .iter()
+                            .any(|interface| interface.name.value == name)
+                    })
+                })
+                .collect(),
+            Some(TypeDefinitionNode::Union(union)) => union
+                .types
+                .iter()
+                .filter_map(|member| match self.get_type(&member.name.value) {
+                    Some(TypeDefinitionNode::Object(object)) => Some(object),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the object type `object_name` is one of the possible types of `name`.
+    pub fn is_possible_type(&self, name: &str, object_name: &str) -> bool {
+        self.possible_types(name)
+            .iter()
+            .any(|object| object.name.value == object_name)
+    }
+
+    fn meta_field(&self, type_name: &str, field_name: &str) -> Option<&FieldDefinitionNode> {
+        let available = match field_name {
+            "__typename" => matches!(
+                self.get_type(type_name),
+                Some(TypeDefinitionNode::Object(_))
+                    | Some(TypeDefinitionNode::Interface(_))
+                    | Some(TypeDefinitionNode::Union(_))
+            ),
+            _ => self.query_type.as_deref() == Some(type_name),
+        };
+        if !available {
+            return None;
+        }
+        self.meta_fields
+            .iter()
+            .find(|field| field.name.value == field_name)
+    }
+
+    fn objects(&self) -> impl Iterator<Item = &ObjectTypeDefinitionNode> {
+        self.types
+            .iter()
+            .filter_map(|type_definition| match type_definition {
+                TypeDefinitionNode::Object(object) => Some(object),
+                _ => None,
+            })
+    }
+
+    fn root_type(&self, name: &Option<String>) -> Option<&ObjectTypeDefinitionNode> {
+        match self.get_type(name.as_ref()?)? {
+            TypeDefinitionNode::Object(object) => Some(object),
+            _ => None,
+        }
+    }
+
+    /// The type that queries start from.
+    pub fn query_type(&self) -> Option<&ObjectTypeDefinitionNode> {
+        self.root_type(&self.query_type)
+    }
+
+    /// The type that mutations start from, if the schema supports mutations.
+    pub fn mutation_type(&self) -> Option<&ObjectTypeDefinitionNode> {
+        self.root_type(&self.mutation_type)
+    }
+
+    /// The type that subscriptions start from, if the schema supports subscriptions.
+    pub fn subscription_type(&self) -> Option<&ObjectTypeDefinitionNode> {
+        self.root_type(&self.subscription_type)
+    }
+}
+
+/// Collects every named type referenced by the nodes it walks.
+#[derive(Default)]
+struct TypeReferences<'a> {
+    named_types: Vec<&'a NamedTypeNode>,
+}
+
+impl<'a> Visitor<'a> for TypeReferences<'a> {
+    fn enter_named_type(&mut self, node: &'a NamedTypeNode) {
+        self.named_types.push(node);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use crate::token::Location;
+
+    fn schema(source: &str) -> Result<Schema, Vec<ValidationError>> {
+        Schema::new(vec![parse(source).unwrap()])
+    }
+
+    #[test]
+    fn includes_builtin_scalars() {
+        let schema = Schema::new(Vec::new()).unwrap();
+        for name in &[
+            "Int", "Float", "Double", "String", "Boolean", "ID", "BigUint",
+        ] {
+            assert!(
+                matches!(schema.get_type(name), Some(TypeDefinitionNode::Scalar(_))),
+                "Missing built-in scalar {}",
+                name
+            );
+        }
+        assert!(schema.query_type().is_none());
+        assert!(schema.mutation_type().is_none());
+    }
+
+    #[test]
+    fn looks_up_types_and_fields() {
+        let schema = schema(
+            r#"type Query {
+  people(first: Int): [Person!]!
+}
+type Person {
+  name: String
+  friends: [Person]
+}
+input PersonFilter {
+  name: String
+}
+directive @audit(reason: String) on FIELD_DEFINITION"#,
+        )
+        .unwrap();
+        assert_eq!(schema.query_type().unwrap().name.value, "Query");
+        assert_eq!(schema.fields_of("Person").unwrap().len(), 2);
+        assert!(schema.fields_of("PersonFilter").is_none());
+        assert_eq!(schema.input_fields_of("PersonFilter").unwrap().len(), 1);
+        let people = schema.field("Query", "people").unwrap();
+        match schema.named_type(&people.field_type) {
+            Some(TypeDefinitionNode::Object(person)) => assert_eq!(person.name.value, "Person"),
+            _ => panic!("Expected people to resolve to the Person object"),
+        }
+        assert!(schema.field("Person", "age").is_none());
+        assert!(schema.get_directive("audit").is_some());
+        assert!(schema.get_type("Missing").is_none());
+    }
+
+    #[test]
+    fn finds_possible_types() {
+        let schema = schema(
+            r#"interface Node { id: ID! }
+type Person implements Node { id: ID! }
+type Photo implements Node { id: ID! }
+type Comment { id: ID! }
+union SearchResult = Person | Comment"#,
+        )
+        .unwrap();
+        let names = |name| -> Vec<String> {
+            schema
+                .possible_types(name)
+                .iter()
+                .map(|object| object.name.value.clone())
+                .collect()
+        };
+        assert_eq!(names("Node"), vec!["Person", "Photo"]);
+        assert_eq!(names("SearchResult"), vec!["Person", "Comment"]);
+        assert_eq!(names("Comment"), vec!["Comment"]);
+        assert!(names("String").is_empty());
+        assert!(schema.is_possible_type("Node", "Photo"));
+        assert!(!schema.is_possible_type("SearchResult", "Photo"));
+    }
+
+    #[test]
+    fn uses_explicit_root_operation_types() {
+        let schema = schema(
+            r#"schema {
+  query: RootQuery
+  subscription: Events
+}
+type RootQuery { version: String }
+type Events { tick: Int }
+type Mutation { reset: Boolean }"#,
+        )
+        .unwrap();
+        assert_eq!(schema.query_type().unwrap().name.value, "RootQuery");
+        assert!(schema.mutation_type().is_none());
+        assert_eq!(schema.subscription_type().unwrap().name.value, "Events");
+    }
+
+    #[test]
+    fn defaults_root_operation_types_to_their_names() {
+        let schema = schema("type Subscription { tick: Int }").unwrap();
+        assert!(schema.query_type().is_none());
+        assert_eq!(
+            schema.subscription_type().unwrap().name.value,
+            "Subscription"
+        );
+        assert!(schema
+            .validate_document(&parse("subscription { tick }").unwrap())
+            .is_ok());
+    }
+
+    #[test]
+    fn reports_unknown_types() {
+        let errors = schema(
+            r#"type Query {
+  people(filter: Filter): [Person]
+}"#,
+        )
+        .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "Unknown type Filter");
+        assert_eq!(errors[0].location, Some(Location::new(30, 2, 18)));
+        assert_eq!(errors[1].message, "Unknown type Person");
+        assert_eq!(errors[1].location, Some(Location::new(40, 2, 28)));
+    }
+
+    #[test]
+    fn reports_invalid_definitions() {
+        let errors = schema(
+            r#"schema { query: Date }
+schema { query: Query }
+type Query { name: String }
+scalar Timestamp
+scalar Timestamp
+extend type Query { name: Int }
+extend scalar Timestamp @specifiedBy(url: "https://tools.ietf.org/html/rfc3339")
+{ name }"#,
+        )
+        .unwrap_err();
+        let messages: Vec<&str> = errors.iter().map(|error| error.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Must only define one schema",
+                "Type Timestamp is defined more than once",
+                "A schema cannot contain executable definitions",
+                "Invalid Extension: Cannot redefine field(s) name",
+                "Invalid Query root type Date: Root types must be object types",
+            ]
+        );
+    }
+}
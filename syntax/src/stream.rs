@@ -0,0 +1,522 @@
+//! Incremental parsing of GraphQL that arrives in pieces, such as over a socket.
+//!
+//! A [`StreamParser`] is fed bytes as they are read and yields each top-level definition as soon
+//! as it is complete. Strings, block strings and comments are lexed rather than scanned, so the
+//! braces inside them do not affect where a definition ends.
+//!
+//! A definition with a body, such as a type or an operation, is complete once its closing brace
+//! is read. A definition without one, such as `scalar Date` or `union Pet = Dog | Cat`, is
+//! complete once the next definition starts, or once a line ends with nothing buffered after it.
+//! So is an extension that only adds directives, such as `extend schema @link`. A line of such a
+//! definition that arrives only after the previous line was complete is read as a definition of
+//! its own.
+//!
+//! A closing bracket that does not match the one it closes ends a definition, as does a
+//! definition keyword at the start of a line inside a body that is still open. The definition
+//! then fails to parse, and the ones after it are read as usual.
+//!
+//! # Example
+//!
+//! ```
+//! use syntax::stream::StreamParser;
+//!
+//! let mut parser = StreamParser::new();
+//! parser.feed(b"type User { \"The } in this string\" name: Str");
+//! assert!(parser.next().is_none());
+//!
+//! parser.feed(b"ing }\nscalar Date");
+//! let user = parser.next().unwrap();
+//! assert_eq!(user.source, "type User { \"The } in this string\" name: String }");
+//! assert!(user.document.is_ok());
+//! assert!(parser.next().is_none());
+//!
+//! parser.finish();
+//! assert_eq!(parser.next().unwrap().source, "scalar Date");
+//! ```
+//!
+//! [`StreamParser`]: struct.StreamParser.html
+
+use crate::document::Document;
+use crate::error::{LexError, ParseError, ParseResult};
+use crate::lexer::Lexer;
+use crate::parse;
+use crate::token::{Location, Token};
+use log::debug;
+
+const DEFINITION_KEYWORDS: [&str; 13] = [
+    "schema",
+    "scalar",
+    "type",
+    "interface",
+    "union",
+    "enum",
+    "input",
+    "directive",
+    "extend",
+    "query",
+    "mutation",
+    "subscription",
+    "fragment",
+];
+
+/// Parses top-level definitions out of input that is fed to it in pieces.
+///
+/// The parser is also an [`Iterator`] over the definitions that are complete. Iteration ends when
+/// the rest of the input is incomplete, and resumes once more is fed.
+///
+/// [`Iterator`]: https://doc.rust-lang.org/std/iter/trait.Iterator.html
+#[derive(Debug, Default)]
+pub struct StreamParser {
+    buffer: Vec<u8>,
+    offset: usize,
+    finished: bool,
+}
+
+/// A complete top-level definition read by a [`StreamParser`].
+///
+/// [`StreamParser`]: struct.StreamParser.html
+#[derive(Debug, PartialEq)]
+pub struct StreamedDefinition {
+    /// The text of the definition, from its first token to its last
+    pub source: String,
+    /// The number of bytes fed to the parser up to the end of the definition
+    pub end: usize,
+    /// The definition parsed into a document of its own, or the error it failed with
+    pub document: ParseResult<Document>,
+}
+
+enum Boundary {
+    Incomplete,
+    Definition {
+        start: usize,
+        end: usize,
+    },
+    Error {
+        start: usize,
+        consumed: usize,
+        error: LexError,
+    },
+}
+
+impl StreamParser {
+    /// Create a parser with nothing fed to it
+    pub fn new() -> Self {
+        StreamParser::default()
+    }
+
+    /// Add the next bytes of the input. They may end part way through a token or a character.
+    pub fn feed(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Mark the end of the input, so the definition it ends with is complete, and an unfinished
+    /// one is yielded as an error.
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    fn boundary(&self) -> Boundary {
+        let (text, invalid) = match std::str::from_utf8(&self.buffer) {
+            Ok(text) => (text, None),
+            Err(error) => {
+                let valid = &self.buffer[..error.valid_up_to()];
+                let text = std::str::from_utf8(valid).expect("Prefix is valid UTF-8");
+                (text, error.error_len())
+            }
+        };
+
+        let mut start = None;
+        let mut last_end = 0;
+        // The closing token of every bracket that is open
+        let mut open = Vec::new();
+        let mut head = Head::default();
+        for item in Lexer::new(text) {
+            let token = match item {
+                Ok(Token::Start) | Ok(Token::Comment(..)) => continue,
+                Ok(Token::End) => break,
+                Ok(token) => token,
+                Err(error) => {
+                    let ends_input = match error_location(&error) {
+                        Some(location) => !text[location.absolute_position..].contains('\n'),
+                        None => true,
+                    };
+                    return match start {
+                        Some(start) if open.is_empty() && head.is_bodyless() => {
+                            Boundary::Definition {
+                                start,
+                                end: last_end,
+                            }
+                        }
+                        _ if !self.finished
+                            && (ends_input || matches!(error, LexError::UnmatchedQuote(_))) =>
+                        {
+                            Boundary::Incomplete
+                        }
+                        _ => Boundary::Error {
+                            start: start.unwrap_or(last_end),
+                            consumed: self.buffer.len(),
+                            error,
+                        },
+                    };
+                }
+            };
+
+            let position = token.location().absolute_position;
+            let first = *start.get_or_insert(position);
+            if position > first && starts_next_definition(&token, open.is_empty(), &head) {
+                return Boundary::Definition {
+                    start: first,
+                    end: last_end,
+                };
+            }
+
+            last_end = token.end_location().absolute_position;
+            match token {
+                Token::OpenBrace(_) => open.push('}'),
+                Token::OpenParen(_) => open.push(')'),
+                Token::OpenSquare(_) => open.push(']'),
+                Token::CloseBrace(_) | Token::CloseParen(_) | Token::CloseSquare(_) => {
+                    let closes = open.pop() == closing_char(&token);
+                    // A mismatched closing token ends the definition, which then fails to parse
+                    if !closes || (open.is_empty() && matches!(token, Token::CloseBrace(_))) {
+                        return Boundary::Definition {
+                            start: first,
+                            end: last_end,
+                        };
+                    }
+                }
+                _ if open.is_empty() => head.push(&token),
+                _ => (),
+            }
+        }
+
+        match (start, invalid) {
+            (Some(start), _) if self.finished => Boundary::Definition {
+                start,
+                end: last_end,
+            },
+            (Some(start), None)
+                if open.is_empty()
+                    && head.is_bodyless()
+                    && text[last_end..].contains('\n')
+                    && parses(&text[start..last_end]) =>
+            {
+                Boundary::Definition {
+                    start,
+                    end: last_end,
+                }
+            }
+            (start, Some(length)) => Boundary::Error {
+                start: start.unwrap_or(last_end),
+                consumed: text.len() + length,
+                error: LexError::UnknownCharacter(location_at(text)),
+            },
+            _ => Boundary::Incomplete,
+        }
+    }
+
+    /// Removes the input up to `consumed` from the buffer, returning the definition in it.
+    fn take(&mut self, start: usize, consumed: usize) -> String {
+        let source = String::from_utf8_lossy(&self.buffer[start..consumed])
+            .trim()
+            .to_string();
+        debug!("Streamed definition: {:?}", source);
+        self.buffer.drain(..consumed);
+        self.offset += consumed;
+        source
+    }
+}
+
+impl Iterator for StreamParser {
+    type Item = StreamedDefinition;
+
+    fn next(&mut self) -> Option<StreamedDefinition> {
+        match self.boundary() {
+            Boundary::Incomplete => None,
+            Boundary::Definition { start, end } => {
+                let source = self.take(start, end);
+                Some(StreamedDefinition {
+                    document: parse(&source),
+                    source,
+                    end: self.offset,
+                })
+            }
+            Boundary::Error {
+                start,
+                consumed,
+                error,
+            } => {
+                let error = ParseError::LexError(error);
+                let source = self.take(start, consumed);
+                Some(StreamedDefinition {
+                    source,
+                    end: self.offset,
+                    document: Err(error),
+                })
+            }
+        }
+    }
+}
+
+fn parses(source: &str) -> bool {
+    parse(source).is_ok()
+}
+
+/// What is known about a definition from its tokens outside of brackets.
+#[derive(Default)]
+struct Head<'a> {
+    /// The first two names, such as `extend type`
+    keywords: Vec<&'a str>,
+    /// The last token, unless it was in brackets
+    previous: Option<Token<'a>>,
+    directives: bool,
+}
+
+impl<'a> Head<'a> {
+    fn push(&mut self, token: &Token<'a>) {
+        match token {
+            Token::Name(_, name) if self.keywords.len() < 2 => self.keywords.push(name),
+            Token::At(_) => self.directives = true,
+            _ => (),
+        }
+        self.previous = Some(token.clone());
+    }
+
+    /// Definitions like `scalar Date` have no body to close, so they end where the next begins.
+    fn is_braceless(&self) -> bool {
+        matches!(
+            self.keywords[..],
+            ["scalar", ..] | ["union", ..] | ["directive", ..] | ["extend", "scalar" | "union"]
+        )
+    }
+
+    /// Whether the definition can end without a body, as the brace-less kinds do and as an
+    /// extension that only adds directives does.
+    fn is_bodyless(&self) -> bool {
+        self.is_braceless()
+            || (self.directives
+                && matches!(
+                    self.keywords[..],
+                    ["extend", "type" | "interface" | "enum" | "input" | "schema"]
+                ))
+    }
+
+    /// Whether the last token leads into the next one, as `extend` and descriptions do.
+    fn continues(&self) -> bool {
+        matches!(
+            self.previous,
+            Some(Token::Name(_, "extend")) | Some(Token::Str(..)) | Some(Token::BlockStr(..))
+        )
+    }
+}
+
+/// Whether a token after the first of a definition starts the next definition. A definition
+/// keyword at the start of a line does even inside brackets, so one that is left open does not
+/// swallow the rest of the input.
+fn starts_next_definition(token: &Token, closed: bool, head: &Head) -> bool {
+    match token {
+        Token::Name(location, name) if !closed => {
+            location.column == 1 && DEFINITION_KEYWORDS.contains(name)
+        }
+        _ if !closed || head.continues() => false,
+        Token::Name(_, name) => DEFINITION_KEYWORDS.contains(name),
+        Token::Str(..) | Token::BlockStr(..) => true,
+        Token::OpenBrace(_) => head.is_braceless(),
+        _ => false,
+    }
+}
+
+fn closing_char(token: &Token) -> Option<char> {
+    match token {
+        Token::CloseBrace(_) => Some('}'),
+        Token::CloseParen(_) => Some(')'),
+        Token::CloseSquare(_) => Some(']'),
+        _ => None,
+    }
+}
+
+fn error_location(error: &LexError) -> Option<Location> {
+    match error {
+        LexError::UnmatchedQuote(location)
+        | LexError::UnknownCharacter(location)
+        | LexError::UnexpectedCharacter(location)
+        | LexError::UnableToConvert(location, _)
+        | LexError::InvalidEscape(location)
+        | LexError::LineTerminatorInString(location) => Some(*location),
+        LexError::EOF => None,
+    }
+}
+
+/// The location just past the end of `text`.
+fn location_at(text: &str) -> Location {
+    let line_start = text.rfind('\n').map_or(0, |index| index + 1);
+    Location::new(
+        text.len(),
+        text.matches('\n').count() + 1,
+        text[line_start..].chars().count() + 1,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sources(parser: &mut StreamParser) -> Vec<String> {
+        parser.map(|definition| definition.source).collect()
+    }
+
+    #[test]
+    fn ignores_braces_in_strings_and_comments() {
+        let mut parser = StreamParser::new();
+        parser.feed(
+            br#"type User {
+  # Closes with }
+  "Like { this }"
+  name(format: String = "}"): String
+  """
+  Or }}}
+  """
+  bio: String
+}"#,
+        );
+        let definition = parser.next().unwrap();
+        assert!(definition.document.is_ok());
+        assert!(definition.source.ends_with("bio: String\n}"));
+        assert_eq!(definition.end, definition.source.len());
+    }
+
+    #[test]
+    fn ends_braceless_definitions_at_the_next_definition() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"scalar Date union Pet = Dog | Cat \"A dog\" type Dog { name: String }");
+        assert_eq!(
+            sources(&mut parser),
+            vec![
+                "scalar Date",
+                "union Pet = Dog | Cat",
+                "\"A dog\" type Dog { name: String }"
+            ]
+        );
+    }
+
+    #[test]
+    fn ends_braceless_definitions_at_a_line_end() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"union Pet =\n  | Dog\n  | Ca");
+        assert!(parser.next().is_none());
+        parser.feed(b"t");
+        assert!(parser.next().is_none());
+        parser.feed(b"\n");
+        let pet = parser.next().unwrap();
+        assert_eq!(pet.source, "union Pet =\n  | Dog\n  | Cat");
+        assert_eq!(pet.end, 27);
+
+        parser.feed(b"directive @key(fields: String!) on OBJECT |");
+        assert!(parser.next().is_none());
+        parser.feed(b" INTERFACE\n{ user { name } }");
+        assert_eq!(
+            sources(&mut parser),
+            vec![
+                "directive @key(fields: String!) on OBJECT | INTERFACE",
+                "{ user { name } }"
+            ]
+        );
+    }
+
+    #[test]
+    fn waits_for_bodies_and_split_characters() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"type Query\n");
+        assert!(parser.next().is_none());
+        parser.feed(b"{ greeting(text: \"h");
+        assert!(parser.next().is_none());
+        parser.feed(&[0xc3]);
+        assert!(parser.next().is_none());
+        parser.feed(&[0xa9]);
+        parser.feed(b"llo\"): String }");
+        assert_eq!(
+            sources(&mut parser),
+            vec!["type Query\n{ greeting(text: \"h\u{e9}llo\"): String }"]
+        );
+    }
+
+    #[test]
+    fn reports_definitions_that_fail() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"type Empty {}\ntype Person { name: }\nscalar");
+        let definitions: Vec<StreamedDefinition> = parser.by_ref().collect();
+        assert_eq!(definitions.len(), 2);
+        assert!(definitions
+            .iter()
+            .all(|definition| definition.document.is_err()));
+
+        parser.finish();
+        let scalar = parser.next().unwrap();
+        assert_eq!(scalar.source, "scalar");
+        assert!(scalar.document.is_err());
+        assert!(parser.next().is_none());
+
+        let mut parser = StreamParser::new();
+        parser.feed(b"scalar Date\n^\nscalar Time\n");
+        assert_eq!(parser.next().unwrap().source, "scalar Date");
+        assert_eq!(
+            parser.next().unwrap().document,
+            Err(ParseError::LexError(LexError::UnknownCharacter(
+                Location::new(1, 2, 1)
+            )))
+        );
+        assert!(parser.next().is_none());
+    }
+
+    #[test]
+    fn ends_definitions_at_mismatched_brackets() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"type A { f(x: Int }\n{ a }\n");
+        let definition = parser.next().unwrap();
+        assert_eq!(definition.source, "type A { f(x: Int }");
+        assert!(definition.document.is_err());
+        let query = parser.next().unwrap();
+        assert_eq!(query.source, "{ a }");
+        assert!(query.document.is_ok());
+        assert!(parser.next().is_none());
+    }
+
+    #[test]
+    fn ends_open_bodies_at_line_start_keywords() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"type A { f: Int\n  type: Int\ntype B { g: Int }");
+        let definition = parser.next().unwrap();
+        assert_eq!(definition.source, "type A { f: Int\n  type: Int");
+        assert!(definition.document.is_err());
+        let b = parser.next().unwrap();
+        assert_eq!(b.source, "type B { g: Int }");
+        assert!(b.document.is_ok());
+
+        parser.feed(b"scalar\ntype X { a: Int }");
+        let definitions: Vec<StreamedDefinition> = parser.by_ref().collect();
+        assert_eq!(definitions[0].source, "scalar");
+        assert!(definitions[0].document.is_err());
+        assert_eq!(definitions[1].source, "type X { a: Int }");
+        assert!(definitions[1].document.is_ok());
+    }
+
+    #[test]
+    fn ends_extensions_without_bodies_at_a_line_end() {
+        let mut parser = StreamParser::new();
+        parser.feed(b"extend type User @key(fields: \"id\")\n");
+        let user = parser.next().unwrap();
+        assert_eq!(user.source, "extend type User @key(fields: \"id\")");
+        assert!(user.document.is_ok());
+
+        parser.feed(b"extend schema @link\n");
+        assert_eq!(sources(&mut parser), vec!["extend schema @link"]);
+
+        parser.feed(b"extend type User\n");
+        assert!(parser.next().is_none());
+        parser.feed(b"{ name: String }");
+        assert_eq!(
+            sources(&mut parser),
+            vec!["extend type User\n{ name: String }"]
+        );
+    }
+}
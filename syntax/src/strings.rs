@@ -0,0 +1,301 @@
+//! Decoding of string tokens into their values, and encoding of values back into GraphQL.
+//!
+//! Follows the StringValue and BlockStringValue() sections of the
+//! [GraphQL Spec](https://spec.graphql.org/October2021/#sec-String-Value).
+
+use crate::error::LexError;
+use crate::token::Location;
+
+/// Decodes the escape sequences in the raw content of a quoted string. `location` is the
+/// location of the opening quote and is used to point at an invalid escape.
+pub fn decode_string(raw: &str, location: Location) -> Result<String, LexError> {
+    let mut value = String::with_capacity(raw.len());
+    let mut chars = raw.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        if c != '\\' {
+            value.push(c);
+            continue;
+        }
+        let escape_location = Location::new(
+            location.absolute_position + 1 + index,
+            location.line,
+            location.column + 1 + raw[..index].chars().count(),
+        );
+        let escaped = match chars.next() {
+            Some((_, escaped)) => escaped,
+            None => return Err(LexError::InvalidEscape(escape_location)),
+        };
+        match escaped {
+            '"' => value.push('"'),
+            '\\' => value.push('\\'),
+            '/' => value.push('/'),
+            'b' => value.push('\u{0008}'),
+            'f' => value.push('\u{000C}'),
+            'n' => value.push('\n'),
+            'r' => value.push('\r'),
+            't' => value.push('\t'),
+            'u' => {
+                let code_point = read_unicode(&raw[index + 2..])
+                    .ok_or(LexError::InvalidEscape(escape_location))?;
+                let mut consumed = code_point.length;
+                let decoded = match code_point.value {
+                    // A leading surrogate must be followed by an escaped trailing surrogate
+                    0xD800..=0xDBFF if !code_point.braced => {
+                        let trailing = raw[index + 2 + consumed..]
+                            .strip_prefix("\\u")
+                            .and_then(read_unicode)
+                            .filter(|trailing| {
+                                !trailing.braced && (0xDC00..=0xDFFF).contains(&trailing.value)
+                            })
+                            .ok_or(LexError::InvalidEscape(escape_location))?;
+                        consumed += 2 + trailing.length;
+                        std::char::from_u32(
+                            0x10000
+                                + ((code_point.value - 0xD800) << 10)
+                                + (trailing.value - 0xDC00),
+                        )
+                    }
+                    value => std::char::from_u32(value),
+                };
+                value.push(decoded.ok_or(LexError::InvalidEscape(escape_location))?);
+                for _ in 0..consumed {
+                    chars.next();
+                }
+            }
+            _ => return Err(LexError::InvalidEscape(escape_location)),
+        }
+    }
+    Ok(value)
+}
+
+struct UnicodeEscape {
+    value: u32,
+    /// How many characters after the `\u` the escape takes up
+    length: usize,
+    /// Whether the escape used the variable width `\u{...}` form
+    braced: bool,
+}
+
+/// Reads the hex digits of a `\uXXXX` or `\u{X...}` escape from the text following the `\u`.
+fn read_unicode(rest: &str) -> Option<UnicodeEscape> {
+    if let Some(braced) = rest.strip_prefix('{') {
+        let end = braced.find('}')?;
+        let digits = &braced[..end];
+        if digits.is_empty() || digits.len() > 6 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        Some(UnicodeEscape {
+            value: u32::from_str_radix(digits, 16).ok()?,
+            length: end + 2,
+            braced: true,
+        })
+    } else {
+        let digits = rest.get(..4)?;
+        if !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        Some(UnicodeEscape {
+            value: u32::from_str_radix(digits, 16).ok()?,
+            length: 4,
+            braced: false,
+        })
+    }
+}
+
+/// The value of the raw content of a block string: escaped triple quotes are unescaped, the
+/// common indentation is removed and leading and trailing blank lines are dropped.
+pub fn block_string_value(raw: &str) -> String {
+    let unescaped = raw.replace("\\\"\"\"", "\"\"\"");
+    let lines: Vec<&str> = unescaped
+        .split("\r\n")
+        .flat_map(|line| line.split(['\n', '\r']))
+        .collect();
+
+    let common_indent = lines
+        .iter()
+        .skip(1)
+        .filter_map(|line| {
+            let indent = leading_whitespace(line);
+            if indent < line.len() {
+                Some(indent)
+            } else {
+                None
+            }
+        })
+        .min();
+
+    let mut dedented: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(index, line)| match common_indent {
+            Some(indent) if index > 0 => line.get(indent..).unwrap_or(""),
+            _ => line,
+        })
+        .collect();
+
+    while dedented.first().is_some_and(|line| is_blank(line)) {
+        dedented.remove(0);
+    }
+    while dedented.last().is_some_and(|line| is_blank(line)) {
+        dedented.pop();
+    }
+    dedented.join("\n")
+}
+
+fn leading_whitespace(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+fn is_blank(line: &str) -> bool {
+    leading_whitespace(line) == line.len()
+}
+
+/// Encodes a value as a quoted GraphQL string.
+pub fn print_string(value: &str) -> String {
+    let mut printed = String::with_capacity(value.len() + 2);
+    printed.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => printed.push_str("\\\""),
+            '\\' => printed.push_str("\\\\"),
+            '\u{0008}' => printed.push_str("\\b"),
+            '\u{000C}' => printed.push_str("\\f"),
+            '\n' => printed.push_str("\\n"),
+            '\r' => printed.push_str("\\r"),
+            '\t' => printed.push_str("\\t"),
+            c if c.is_control() => printed.push_str(&format!("\\u{:04X}", c as u32)),
+            c => printed.push(c),
+        }
+    }
+    printed.push('"');
+    printed
+}
+
+/// Encodes a value as a GraphQL block string whose lines after the first are indented by
+/// `indent`. Returns None when the value would not survive the block string dedent, for
+/// example because it starts with a blank line, in which case it must be printed as a quoted
+/// string instead.
+pub fn print_block_string(value: &str, indent: &str) -> Option<String> {
+    let escaped = value.replace("\"\"\"", "\\\"\"\"");
+    let raw = if escaped.contains('\n') {
+        let lines: Vec<String> = escaped
+            .split('\n')
+            .map(|line| {
+                if line.is_empty() {
+                    String::new()
+                } else {
+                    format!("{}{}", indent, line)
+                }
+            })
+            .collect();
+        format!("\n{}\n{}", lines.join("\n"), indent)
+    } else if escaped.ends_with('"') || escaped.ends_with('\\') {
+        // Keep a trailing quote or backslash away from the closing quotes
+        format!("{}\n{}", escaped, indent)
+    } else {
+        escaped
+    };
+    if block_string_value(&raw) == value {
+        Some(format!("\"\"\"{}\"\"\"", raw))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn decode(raw: &str) -> Result<String, LexError> {
+        decode_string(raw, Location::new(0, 1, 1))
+    }
+
+    #[test]
+    fn decodes_escape_sequences() {
+        assert_eq!(decode(r#"a\nb"#).unwrap(), "a\nb");
+        assert_eq!(
+            decode(r#"\"\\\/\b\f\n\r\t"#).unwrap(),
+            "\"\\/\u{0008}\u{000C}\n\r\t"
+        );
+        assert_eq!(decode(r#"caf\u00e9"#).unwrap(), "café");
+        assert_eq!(decode(r#"\u{1F600}"#).unwrap(), "😀");
+        assert_eq!(decode("plain é").unwrap(), "plain é");
+    }
+
+    #[test]
+    fn decodes_surrogate_pairs() {
+        assert_eq!(decode(r#"\uD83D\uDE00!"#).unwrap(), "😀!");
+        assert_eq!(
+            decode(r#"\uD83D"#),
+            Err(LexError::InvalidEscape(Location::new(1, 1, 2)))
+        );
+        assert_eq!(
+            decode(r#"\uD83DA"#),
+            Err(LexError::InvalidEscape(Location::new(1, 1, 2)))
+        );
+        assert_eq!(
+            decode(r#"\uDE00"#),
+            Err(LexError::InvalidEscape(Location::new(1, 1, 2)))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_escapes() {
+        assert_eq!(
+            decode(r#"ab\q"#),
+            Err(LexError::InvalidEscape(Location::new(3, 1, 4)))
+        );
+        assert!(decode(r#"\u12"#).is_err());
+        assert!(decode(r#"\u{}"#).is_err());
+        assert!(decode(r#"\u{110000}"#).is_err());
+        assert!(decode(r#"\u{1F600"#).is_err());
+        assert!(decode("\\").is_err());
+    }
+
+    #[test]
+    fn dedents_block_strings() {
+        assert_eq!(
+            block_string_value("\n    Hello,\n      World!\n\n    Yours,\n      GraphQL.\n  "),
+            "Hello,\n  World!\n\nYours,\n  GraphQL."
+        );
+        assert_eq!(block_string_value("  first\n  second"), "  first\nsecond");
+        assert_eq!(block_string_value("\n\n   \n"), "");
+        assert_eq!(block_string_value("a\r\n  b\r  c"), "a\nb\nc");
+    }
+
+    #[test]
+    fn unescapes_block_string_quotes() {
+        assert_eq!(
+            block_string_value(r#"Say \""" and \n"#),
+            r#"Say """ and \n"#
+        );
+    }
+
+    #[test]
+    fn prints_escaped_strings() {
+        assert_eq!(print_string("a\"b\\c\nd\u{0001}"), r#""a\"b\\c\nd\u0001""#);
+        assert_eq!(print_string("café"), "\"café\"");
+    }
+
+    #[test]
+    fn prints_block_strings() {
+        assert_eq!(
+            print_block_string("single", "  ").unwrap(),
+            "\"\"\"single\"\"\""
+        );
+        assert_eq!(
+            print_block_string("a\n  b\n\nc", "  ").unwrap(),
+            "\"\"\"\n  a\n    b\n\n  c\n  \"\"\""
+        );
+        assert_eq!(
+            print_block_string("ends with \"", "").unwrap(),
+            "\"\"\"ends with \"\n\"\"\""
+        );
+        assert_eq!(
+            print_block_string("has \"\"\" inside", "").unwrap(),
+            "\"\"\"has \\\"\"\" inside\"\"\""
+        );
+        assert_eq!(print_block_string("\nleading blank line", ""), None);
+        assert_eq!(print_block_string("  a\n  b", ""), None);
+    }
+}
@@ -11,6 +11,11 @@
 
 /// Contains the information on the location of a lexer error relative to the input string.
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
 pub struct Location {
     /// The absolute position in the string. Disregards lines and columns.
     pub absolute_position: usize,
@@ -40,6 +45,68 @@ impl Location {
     pub fn ignored() -> Self {
         IGNORED_LOCATION
     }
+
+    /// Creates the location `length` characters further along the same line.
+    fn advance(&self, length: usize) -> Self {
+        Location {
+            absolute_position: self.absolute_position + length,
+            line: self.line,
+            column: self.column + length,
+        }
+    }
+}
+
+/// The part of the input string a node was parsed from. `start` is the location of the node's
+/// first character and `end` is the location just past its last character.
+///
+/// Spans never affect equality, so a node compares equal to the same node parsed from anywhere
+/// else. Use [`is_identical`] to compare the locations themselves.
+///
+/// ```
+/// use syntax::token::{Location, Span};
+///
+/// let first = Span::new(Location::new(0, 1, 1), Location::new(4, 1, 5));
+/// let second = Span::new(Location::new(10, 2, 1), Location::new(14, 2, 5));
+/// assert_eq!(first, second);
+/// assert!(!first.is_identical(&second));
+/// ```
+///
+/// [`is_identical`]: struct.Span.html#method.is_identical
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Span {
+    /// The location of the first character
+    pub start: Location,
+    /// The location just past the last character
+    pub end: Location,
+}
+
+impl Span {
+    /// Creates a new span between the two locations
+    pub fn new(start: Location, end: Location) -> Self {
+        Span { start, end }
+    }
+
+    /// Creates a span covering the provided token
+    pub fn from_token(token: &Token) -> Self {
+        Span::new(token.location(), token.end_location())
+    }
+
+    /// Creates a span that can be ignored. Used for nodes that were not parsed from a string.
+    pub fn ignored() -> Self {
+        Span::new(IGNORED_LOCATION, IGNORED_LOCATION)
+    }
+
+    /// Compares the locations of two spans.
+    pub fn is_identical(&self, other: &Span) -> bool {
+        self.start == other.start && self.end == other.end
+    }
+}
+
+impl PartialEq for Span {
+    fn eq(&self, _other: &Span) -> bool {
+        true
+    }
 }
 
 /// Enumeration of the possible tokens that can be found in a GraphQL String.
@@ -81,10 +148,12 @@ pub enum Token<'a> {
     /// Represents a series of alphanumeric and/or `_` characters. These characters are NOT
     /// surrouned in quotes.
     Name(Location, &'a str),
-    /// Represents an parsed integer and it's location in the string
-    Int(Location, i64),
-    /// Represents an parsed float and it's location in the string
-    Float(Location, f64),
+    /// Represents an integer and it's location in the string. The integer is kept as it was
+    /// written so that it can be converted to any size of number.
+    Int(Location, &'a str),
+    /// Represents a float and it's location in the string. The float is kept as it was written,
+    /// including any exponent.
+    Float(Location, &'a str),
     /// Represents a quoted series of characters. These characters can be any valid unicode
     /// character. It will capture all characters within a pair of double quotes
     Str(Location, &'a str),
@@ -97,6 +166,58 @@ pub enum Token<'a> {
 
 use std::mem;
 
+/// The kind of a [`Token`], without its location or value. Parse errors use it to say what
+/// could have come next.
+///
+/// [`Token`]: enum.Token.html
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TokenKind {
+    /// The start of the token stream
+    Start,
+    /// The end of the token stream
+    End,
+    /// `!`
+    Bang,
+    /// `$`
+    Dollar,
+    /// `&`
+    Amp,
+    /// `...`
+    Spread,
+    /// `:`
+    Colon,
+    /// `=`
+    Equals,
+    /// `@`
+    At,
+    /// `(`
+    OpenParen,
+    /// `)`
+    CloseParen,
+    /// `[`
+    OpenSquare,
+    /// `]`
+    CloseSquare,
+    /// `{`
+    OpenBrace,
+    /// `}`
+    CloseBrace,
+    /// `|`
+    Pipe,
+    /// A name
+    Name,
+    /// An integer
+    Int,
+    /// A float
+    Float,
+    /// A quoted string
+    Str,
+    /// A block string
+    BlockStr,
+    /// A comment
+    Comment,
+}
+
 impl<'a> Token<'a> {
     /// Helper function to determine if to tokens are of the same
     /// Enum variant.
@@ -108,7 +229,41 @@ impl<'a> Token<'a> {
     /// assert!(!Token::Start.is_same_type(&Token::End));
     /// ```
     pub fn is_same_type(&self, other: &Token) -> bool {
-        return mem::discriminant(self) == mem::discriminant(other);
+        mem::discriminant(self) == mem::discriminant(other)
+    }
+
+    /// The kind of the token.
+    ///
+    /// ```
+    /// use syntax::token::{Location, Token, TokenKind};
+    ///
+    /// assert_eq!(Token::Name(Location::new(0, 1, 1), "id").kind(), TokenKind::Name);
+    /// ```
+    pub fn kind(&self) -> TokenKind {
+        match self {
+            Token::Start => TokenKind::Start,
+            Token::End => TokenKind::End,
+            Token::Bang(_) => TokenKind::Bang,
+            Token::Dollar(_) => TokenKind::Dollar,
+            Token::Amp(_) => TokenKind::Amp,
+            Token::Spread(_) => TokenKind::Spread,
+            Token::Colon(_) => TokenKind::Colon,
+            Token::Equals(_) => TokenKind::Equals,
+            Token::At(_) => TokenKind::At,
+            Token::OpenParen(_) => TokenKind::OpenParen,
+            Token::CloseParen(_) => TokenKind::CloseParen,
+            Token::OpenSquare(_) => TokenKind::OpenSquare,
+            Token::CloseSquare(_) => TokenKind::CloseSquare,
+            Token::OpenBrace(_) => TokenKind::OpenBrace,
+            Token::CloseBrace(_) => TokenKind::CloseBrace,
+            Token::Pipe(_) => TokenKind::Pipe,
+            Token::Name(..) => TokenKind::Name,
+            Token::Int(..) => TokenKind::Int,
+            Token::Float(..) => TokenKind::Float,
+            Token::Str(..) => TokenKind::Str,
+            Token::BlockStr(..) => TokenKind::BlockStr,
+            Token::Comment(..) => TokenKind::Comment,
+        }
     }
 
     /// Extracts the token's location from the enum variant.
@@ -150,6 +305,36 @@ impl<'a> Token<'a> {
             | Token::Comment(location, _) => *location,
         }
     }
+
+    /// The location just past the last character of the token.
+    ///
+    /// ```
+    /// use syntax::token::{Token, Location};
+    ///
+    /// let name = Token::Name(Location::new(4, 2, 3), "query");
+    /// assert_eq!(name.end_location(), Location::new(9, 2, 8));
+    /// ```
+    pub fn end_location(&self) -> Location {
+        let location = self.location();
+        match self {
+            Token::Start | Token::End => location,
+            Token::Spread(_) => location.advance(3),
+            Token::Name(_, value)
+            | Token::Comment(_, value)
+            | Token::Int(_, value)
+            | Token::Float(_, value) => location.advance(value.len()),
+            Token::Str(_, value) => location.advance(value.len() + 2),
+            Token::BlockStr(_, value) => match value.rfind('\n') {
+                Some(last_newline) => Location {
+                    absolute_position: location.absolute_position + value.len() + 6,
+                    line: location.line + value.matches('\n').count(),
+                    column: value.len() - last_newline + 3,
+                },
+                None => location.advance(value.len() + 6),
+            },
+            _ => location.advance(1),
+        }
+    }
 }
 
 use std::fmt;
@@ -160,6 +345,36 @@ impl<'a> fmt::Display for Token<'a> {
     }
 }
 
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let description = match self {
+            TokenKind::Start => "start of document",
+            TokenKind::End => "end of document",
+            TokenKind::Bang => "`!`",
+            TokenKind::Dollar => "`$`",
+            TokenKind::Amp => "`&`",
+            TokenKind::Spread => "`...`",
+            TokenKind::Colon => "`:`",
+            TokenKind::Equals => "`=`",
+            TokenKind::At => "`@`",
+            TokenKind::OpenParen => "`(`",
+            TokenKind::CloseParen => "`)`",
+            TokenKind::OpenSquare => "`[`",
+            TokenKind::CloseSquare => "`]`",
+            TokenKind::OpenBrace => "`{`",
+            TokenKind::CloseBrace => "`}`",
+            TokenKind::Pipe => "`|`",
+            TokenKind::Name => "a name",
+            TokenKind::Int => "an integer",
+            TokenKind::Float => "a float",
+            TokenKind::Str => "a string",
+            TokenKind::BlockStr => "a block string",
+            TokenKind::Comment => "a comment",
+        };
+        write!(f, "{}", description)
+    }
+}
+
 use std::cmp::{Eq, PartialEq};
 
 impl<'a> PartialEq for Token<'a> {
@@ -194,27 +409,27 @@ mod tests {
         );
         assert_ne!(
             Token::Amp(Location::new(0, 0, 0)),
-            Token::Float(Location::new(0, 0, 0), 0.0)
+            Token::Float(Location::new(0, 0, 0), "0.0")
         );
         assert_ne!(
             Token::Dollar(Location::new(0, 0, 0)),
             Token::OpenBrace(Location::new(0, 1, 1))
         );
         assert_ne!(
-            Token::Int(Location::new(0, 0, 0), 0),
-            Token::Float(Location::new(0, 0, 0), 0.0)
+            Token::Int(Location::new(0, 0, 0), "0"),
+            Token::Float(Location::new(0, 0, 0), "0.0")
         );
     }
 
     #[test]
     fn compare_value() {
         assert_eq!(
-            Token::Int(Location::new(0, 0, 0), 10),
-            Token::Int(Location::new(12, 3, 14), 10)
+            Token::Int(Location::new(0, 0, 0), "10"),
+            Token::Int(Location::new(12, 3, 14), "10")
         );
         assert_eq!(
-            Token::Float(Location::new(0, 0, 0), 3.14),
-            Token::Float(Location::new(3, 1, 4), 3.14)
+            Token::Float(Location::new(0, 0, 0), "12.75"),
+            Token::Float(Location::new(3, 1, 4), "12.75")
         );
         assert_eq!(
             Token::Name(Location::new(0, 0, 0), "id"),
@@ -230,12 +445,12 @@ mod tests {
         );
 
         assert_ne!(
-            Token::Int(Location::new(0, 0, 0), 10),
-            Token::Int(Location::new(12, 3, 14), 11)
+            Token::Int(Location::new(0, 0, 0), "10"),
+            Token::Int(Location::new(12, 3, 14), "11")
         );
         assert_ne!(
-            Token::Float(Location::new(0, 0, 0), 3.14),
-            Token::Float(Location::new(3, 1, 4), 3.14159)
+            Token::Float(Location::new(0, 0, 0), "12.75"),
+            Token::Float(Location::new(3, 1, 4), "12.755")
         );
         assert_ne!(
             Token::Name(Location::new(0, 0, 0), "id"),
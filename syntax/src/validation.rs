@@ -1,12 +1,30 @@
+//! Validation of documents and the nodes in them.
+//!
+//! Nodes check themselves through [`ValidNode`] and extensions check themselves against the
+//! definition they extend through [`ValidExtensionNode`]. The [`executable`] and
+//! [`type_system`] rules check a whole document against a schema.
+//!
+//! [`ValidNode`]: trait.ValidNode.html
+//! [`ValidExtensionNode`]: trait.ValidExtensionNode.html
+//! [`executable`]: executable/index.html
+//! [`type_system`]: type_system/index.html
+
 use crate::error::ValidationError;
-use crate::nodes::NodeWithFields;
+use crate::nodes::{FieldDefinitionNode, NodeWithFields};
+use crate::token::Location;
+
+pub mod executable;
+pub mod type_system;
+pub(crate) mod values;
 
+/// The result of validating a single node.
 pub type ValidationResult = Result<(), ValidationError>;
 
 /// A trait used by Document to walk the tree and
 /// determine wheter or not the nodes are valid.
 /// Defaults to valid.
 pub trait ValidNode {
+    /// Checks the node, returning the first problem found.
     fn validate(&self) -> ValidationResult {
         Ok(())
     }
@@ -16,22 +34,33 @@ fn contains_any_element<T: PartialEq>(haystack: &[T], needles: &[T]) -> bool {
     needles.iter().any(|needle| haystack.contains(needle))
 }
 
+/// Fields are compared by name only, so an extension cannot redefine a field by
+/// changing its type, arguments or directives. The error points at the first
+/// redefined field.
 pub fn validate_extension_fields_against_original<E: NodeWithFields, O: NodeWithFields>(
     extension: &E,
     original: &O,
 ) -> ValidationResult {
-    if contains_any_element(original.get_fields(), extension.get_fields()) {
-        let original_fields = original.get_fields();
-        let conflicting_names: String = extension
+    let original_names: Vec<&str> = original
+        .get_fields()
+        .iter()
+        .map(|field| field.name.value.as_str())
+        .collect();
+    let extension_names: Vec<&str> = extension
+        .get_fields()
+        .iter()
+        .map(|field| field.name.value.as_str())
+        .collect();
+
+    if contains_any_element(&original_names, &extension_names) {
+        let conflicting_fields: Vec<&FieldDefinitionNode> = extension
             .get_fields()
             .iter()
-            .filter_map(|needle| {
-                if original_fields.contains(needle) {
-                    Some(needle.name.value.as_str())
-                } else {
-                    None
-                }
-            })
+            .filter(|field| original_names.contains(&field.name.value.as_str()))
+            .collect();
+        let conflicting_names: String = conflicting_fields
+            .iter()
+            .map(|field| field.name.value.as_str())
             .collect::<Vec<&str>>()
             .join(", ");
 
@@ -41,18 +70,49 @@ pub fn validate_extension_fields_against_original<E: NodeWithFields, O: NodeWith
                 conflicting_names
             )
             .as_str(),
-        ))
+        )
+        .with_location(conflicting_fields[0].span.start))
     } else {
         Ok(())
     }
 }
 
+/// Checks that an extension adds none of the `kind`s that the original already has, such as
+/// enum values or union members. The error points at `location`.
+pub fn validate_extension_names_against_original(
+    kind: &str,
+    extension_names: &[&str],
+    original_names: &[&str],
+    location: Location,
+) -> ValidationResult {
+    let conflicting_names: Vec<&str> = extension_names
+        .iter()
+        .filter(|name| original_names.contains(name))
+        .copied()
+        .collect();
+
+    if conflicting_names.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError::new(
+            format!(
+                "Invalid Extension: Cannot redefine {}(s) {}",
+                kind,
+                conflicting_names.join(", ")
+            )
+            .as_str(),
+        )
+        .with_location(location))
+    }
+}
+
 /// A trait used to determine if a type extension is valid.
 /// This requires passing in the original declaration. The original is then
 /// used to determine the validity of the extension.
 pub trait ValidExtensionNode<T> {
+    /// Checks the extension against the definition it extends, if there is one.
     fn validate_extension(&self, original: Option<&T>) -> ValidationResult {
-        if let Some(_) = original {
+        if original.is_some() {
             Ok(())
         } else {
             Err(ValidationError::new("Invalid Extension: No root element"))
@@ -70,4 +130,26 @@ mod tests {
         assert!(contains_any_element(&[1, 2], &[2]));
         assert!(contains_any_element(&[1], &[1, 2]));
     }
+
+    #[test]
+    fn validate_extension_names_against_original_fn() {
+        assert!(validate_extension_names_against_original(
+            "value",
+            &["A"],
+            &["B"],
+            Location::ignored()
+        )
+        .is_ok());
+        let res = validate_extension_names_against_original(
+            "value",
+            &["A", "B", "C"],
+            &["C", "A"],
+            Location::ignored(),
+        );
+        assert!(res.is_err());
+        assert_eq!(
+            res.unwrap_err().message,
+            "Invalid Extension: Cannot redefine value(s) A, C"
+        );
+    }
 }
@@ -0,0 +1,1310 @@
+//! The executable validation rules of the
+//! [GraphQL Spec](https://spec.graphql.org/October2021/#sec-Validation), checked against a
+//! [`Schema`] before a request is executed.
+//!
+//! [`Schema`]: ../../schema/struct.Schema.html
+
+use crate::document::Document;
+use crate::error::ValidationError;
+use crate::nodes::*;
+use crate::printer::{directive_location_name, print_type};
+use crate::schema::Schema;
+use crate::token::{Location, Span};
+use crate::validation::values::{is_required, value_error, value_location};
+use std::collections::{HashMap, HashSet};
+
+/// Checks an executable document against the schema and returns an error for each broken rule.
+pub fn validate(schema: &Schema, document: &Document) -> Vec<ValidationError> {
+    let mut validator = Validator {
+        schema,
+        fragments: HashMap::new(),
+        fragment_scopes: HashMap::new(),
+        reported_conflicts: HashSet::new(),
+        errors: Vec::new(),
+    };
+    validator.validate_document(document);
+    validator.errors
+}
+
+/// A variable given for an argument or input field of type `expected`.
+struct VariableUsage<'a> {
+    variable: &'a VariableNode,
+    expected: &'a TypeNode,
+    /// Whether the argument or input field has a default value to fall back on
+    has_default: bool,
+}
+
+/// The variables and fragments used by an operation or fragment, not counting those used by
+/// the fragments it spreads.
+#[derive(Default)]
+struct Scope<'a> {
+    variables: Vec<VariableUsage<'a>>,
+    fragments: Vec<&'a FragmentSpreadNode>,
+}
+
+/// The parts shared by queries, mutations and subscriptions.
+struct Operation<'a> {
+    kind: &'static str,
+    name: Option<&'a NameNode>,
+    variables: &'a [VariableDefinitionNode],
+    directives: &'a Option<Directives>,
+    selections: &'a [Selection],
+    span: &'a Span,
+}
+
+impl<'a> Operation<'a> {
+    fn new(operation: &'a OperationTypeNode) -> Operation<'a> {
+        match operation {
+            OperationTypeNode::Query(query) => Operation {
+                kind: "query",
+                name: query.name.as_ref(),
+                variables: query.variables.as_deref().unwrap_or(&[]),
+                directives: &query.directives,
+                selections: &query.selections,
+                span: &query.span,
+            },
+            OperationTypeNode::Mutation(mutation) => Operation {
+                kind: "mutation",
+                name: mutation.name.as_ref(),
+                variables: mutation.variables.as_deref().unwrap_or(&[]),
+                directives: &mutation.directives,
+                selections: &mutation.selections,
+                span: &mutation.span,
+            },
+            OperationTypeNode::Subscription(subscription) => Operation {
+                kind: "subscription",
+                name: subscription.name.as_ref(),
+                variables: subscription.variables.as_deref().unwrap_or(&[]),
+                directives: &subscription.directives,
+                selections: &subscription.selections,
+                span: &subscription.span,
+            },
+        }
+    }
+
+    fn display_name(&self) -> &str {
+        self.name.map_or("<anonymous>", |name| name.value.as_str())
+    }
+
+    fn directive_location(&self) -> ExecutableDirectiveLocation {
+        match self.kind {
+            "mutation" => ExecutableDirectiveLocation::Mutation,
+            "subscription" => ExecutableDirectiveLocation::Subscription,
+            _ => ExecutableDirectiveLocation::Query,
+        }
+    }
+}
+
+/// A field selected in a selection set, together with the type it was selected on.
+struct SelectedField<'a> {
+    parent: &'a TypeDefinitionNode,
+    field: &'a FieldNode,
+    definition: Option<&'a FieldDefinitionNode>,
+}
+
+struct Validator<'a> {
+    schema: &'a Schema,
+    fragments: HashMap<&'a str, &'a FragmentDefinitionNode>,
+    fragment_scopes: HashMap<&'a str, Scope<'a>>,
+    /// The absolute positions of field pairs already reported as conflicting
+    reported_conflicts: HashSet<(usize, usize)>,
+    errors: Vec<ValidationError>,
+}
+
+fn error(message: &str, location: Location) -> ValidationError {
+    ValidationError::new(message).with_location(location)
+}
+
+fn is_composite(type_definition: &TypeDefinitionNode) -> bool {
+    matches!(
+        type_definition,
+        TypeDefinitionNode::Object(_)
+            | TypeDefinitionNode::Interface(_)
+            | TypeDefinitionNode::Union(_)
+    )
+}
+
+fn is_input_type(type_definition: &TypeDefinitionNode) -> bool {
+    matches!(
+        type_definition,
+        TypeDefinitionNode::Scalar(_) | TypeDefinitionNode::Enum(_) | TypeDefinitionNode::Input(_)
+    )
+}
+
+fn response_key(field: &FieldNode) -> &str {
+    &field.alias.as_ref().unwrap_or(&field.name).value
+}
+
+/// Whether a variable of type `variable_type` can be passed where `location_type` is expected.
+fn is_input_subtype(variable_type: &TypeNode, location_type: &TypeNode) -> bool {
+    match (variable_type, location_type) {
+        (TypeNode::NonNull(variable_type), TypeNode::NonNull(location_type)) => {
+            is_input_subtype(variable_type, location_type)
+        }
+        (_, TypeNode::NonNull(_)) => false,
+        (TypeNode::NonNull(variable_type), location_type) => {
+            is_input_subtype(variable_type, location_type)
+        }
+        (TypeNode::List(variable_type), TypeNode::List(location_type)) => {
+            is_input_subtype(&variable_type.list_type, &location_type.list_type)
+        }
+        (TypeNode::List(_), _) | (_, TypeNode::List(_)) => false,
+        (TypeNode::Named(variable_type), TypeNode::Named(location_type)) => {
+            variable_type.name.value == location_type.name.value
+        }
+    }
+}
+
+/// Arguments are compared by name and value, ignoring their order.
+fn same_arguments(first: &Option<Arguments>, second: &Option<Arguments>) -> bool {
+    let first: &[Argument] = first.as_deref().unwrap_or(&[]);
+    let second: &[Argument] = second.as_deref().unwrap_or(&[]);
+    first.len() == second.len()
+        && first.iter().all(|argument| {
+            second.iter().any(|other| {
+                other.name.value == argument.name.value && other.value == argument.value
+            })
+        })
+}
+
+impl<'a> Validator<'a> {
+    fn validate_document(&mut self, document: &'a Document) {
+        let mut operations = Vec::new();
+        let mut fragments = Vec::new();
+        for definition in &document.definitions {
+            match definition {
+                DefinitionNode::Executable(ExecutableDefinitionNode::Operation(operation)) => {
+                    operations.push(Operation::new(operation));
+                }
+                DefinitionNode::Executable(ExecutableDefinitionNode::Fragment(fragment)) => {
+                    if self.fragments.contains_key(fragment.name.value.as_str()) {
+                        self.errors.push(error(
+                            &format!(
+                                "There can be only one fragment named {}",
+                                fragment.name.value
+                            ),
+                            fragment.name.span.start,
+                        ));
+                    } else {
+                        self.fragments.insert(&fragment.name.value, fragment);
+                        fragments.push(fragment);
+                    }
+                }
+                _ => self.errors.push(error(
+                    "An executable document cannot contain type system definitions",
+                    definition.span().start,
+                )),
+            }
+        }
+
+        let mut operation_names: Vec<&str> = Vec::new();
+        for operation in &operations {
+            match operation.name {
+                Some(name) if operation_names.contains(&name.value.as_str()) => {
+                    self.errors.push(error(
+                        &format!("There can be only one operation named {}", name.value),
+                        name.span.start,
+                    ))
+                }
+                Some(name) => operation_names.push(&name.value),
+                None if operations.len() > 1 => self.errors.push(error(
+                    "This anonymous operation must be the only defined operation",
+                    operation.span.start,
+                )),
+                None => (),
+            }
+        }
+
+        for fragment in &fragments {
+            let scope = self.validate_fragment(fragment);
+            self.fragment_scopes.insert(&fragment.name.value, scope);
+        }
+        self.validate_fragment_cycles(&fragments);
+
+        let mut used_fragments = HashSet::new();
+        for operation in &operations {
+            self.validate_operation(operation, &mut used_fragments);
+        }
+        for fragment in &fragments {
+            if !used_fragments.contains(fragment.name.value.as_str()) {
+                self.errors.push(error(
+                    &format!("Fragment {} is never used", fragment.name.value),
+                    fragment.span.start,
+                ));
+            }
+        }
+    }
+
+    fn validate_operation(
+        &mut self,
+        operation: &Operation<'a>,
+        used_fragments: &mut HashSet<&'a str>,
+    ) {
+        let root = match operation.kind {
+            "mutation" => self.schema.mutation_type(),
+            "subscription" => self.schema.subscription_type(),
+            _ => self.schema.query_type(),
+        };
+        let root = match root.and_then(|root| self.schema.get_type(&root.name.value)) {
+            Some(root) => root,
+            None => {
+                self.errors.push(error(
+                    &format!("The schema does not support {} operations", operation.kind),
+                    operation.span.start,
+                ));
+                return;
+            }
+        };
+
+        if operation.kind == "subscription" {
+            self.validate_single_root_field(root, operation);
+        }
+
+        let mut variable_names: Vec<&str> = Vec::new();
+        for definition in operation.variables {
+            let name = &definition.variable.name.value;
+            if variable_names.contains(&name.as_str()) {
+                self.errors.push(error(
+                    &format!("There can be only one variable named ${}", name),
+                    definition.variable.span.start,
+                ));
+            }
+            variable_names.push(name);
+            self.validate_variable_definition(definition);
+        }
+
+        let mut scope = Scope::default();
+        self.validate_directives(
+            operation.directives,
+            operation.directive_location(),
+            &mut scope,
+        );
+        self.validate_selection_set(root, operation.selections, &mut scope);
+
+        // Follow the fragments the operation spreads to find every variable it uses
+        let mut pending: Vec<&'a str> = scope
+            .fragments
+            .iter()
+            .map(|spread| spread.name.value.as_str())
+            .collect();
+        let mut reached: HashSet<&'a str> = HashSet::new();
+        let mut usages: Vec<&VariableUsage<'a>> = scope.variables.iter().collect();
+        while let Some(name) = pending.pop() {
+            if reached.insert(name) {
+                if let Some(fragment_scope) = self.fragment_scopes.get(name) {
+                    usages.extend(fragment_scope.variables.iter());
+                    pending.extend(
+                        fragment_scope
+                            .fragments
+                            .iter()
+                            .map(|spread| spread.name.value.as_str()),
+                    );
+                }
+            }
+        }
+        used_fragments.extend(reached);
+
+        let mut errors = Vec::new();
+        for usage in &usages {
+            let name = &usage.variable.name.value;
+            match operation
+                .variables
+                .iter()
+                .find(|definition| &definition.variable.name.value == name)
+            {
+                Some(definition) => {
+                    if !is_variable_allowed(definition, usage) {
+                        errors.push(error(
+                            &format!(
+                                "Variable ${} of type {} used in position expecting type {}",
+                                name,
+                                print_type(&definition.variable_type),
+                                print_type(usage.expected)
+                            ),
+                            usage.variable.span.start,
+                        ));
+                    }
+                }
+                None => errors.push(error(
+                    &format!(
+                        "Variable ${} is not defined by operation {}",
+                        name,
+                        operation.display_name()
+                    ),
+                    usage.variable.span.start,
+                )),
+            }
+        }
+        for definition in operation.variables {
+            let name = &definition.variable.name.value;
+            if !usages
+                .iter()
+                .any(|usage| &usage.variable.name.value == name)
+            {
+                errors.push(error(
+                    &format!(
+                        "Variable ${} is never used in operation {}",
+                        name,
+                        operation.display_name()
+                    ),
+                    definition.variable.span.start,
+                ));
+            }
+        }
+        self.errors.extend(errors);
+    }
+
+    fn validate_variable_definition(&mut self, definition: &'a VariableDefinitionNode) {
+        let name = &definition.variable.name.value;
+        let named_type = definition.variable_type.named_type();
+        match self.schema.get_type(&named_type.name.value) {
+            None => self.errors.push(error(
+                &format!("Unknown type {}", named_type.name.value),
+                named_type.span.start,
+            )),
+            Some(type_definition) if !is_input_type(type_definition) => self.errors.push(error(
+                &format!(
+                    "Variable ${} cannot be non-input type {}",
+                    name,
+                    print_type(&definition.variable_type)
+                ),
+                named_type.span.start,
+            )),
+            Some(_) => {
+                if let Some(default_value) = &definition.default_value {
+                    if let Some(message) =
+                        value_error(self.schema, default_value, &definition.variable_type, true)
+                    {
+                        self.errors.push(error(
+                            &format!("Invalid default value for variable ${}: {}", name, message),
+                            value_location(default_value).unwrap_or(definition.span.start),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    fn validate_fragment(&mut self, fragment: &'a FragmentDefinitionNode) -> Scope<'a> {
+        let mut scope = Scope::default();
+        self.validate_directives(
+            &fragment.directives,
+            ExecutableDirectiveLocation::FragmentDefinition,
+            &mut scope,
+        );
+        if let Some(type_condition) = self.type_condition(&fragment.node_type) {
+            self.validate_selection_set(type_condition, &fragment.selections, &mut scope);
+        }
+        scope
+    }
+
+    /// Looks up the type a fragment applies to, which must be an object, interface or union.
+    fn type_condition(&mut self, named_type: &NamedTypeNode) -> Option<&'a TypeDefinitionNode> {
+        match self.schema.get_type(&named_type.name.value) {
+            Some(type_definition) if is_composite(type_definition) => Some(type_definition),
+            Some(_) => {
+                self.errors.push(error(
+                    &format!(
+                        "Fragment cannot condition on non composite type {}",
+                        named_type.name.value
+                    ),
+                    named_type.span.start,
+                ));
+                None
+            }
+            None => {
+                self.errors.push(error(
+                    &format!("Unknown type {}", named_type.name.value),
+                    named_type.span.start,
+                ));
+                None
+            }
+        }
+    }
+
+    /// Reports fragments that spread themselves, directly or through other fragments.
+    fn validate_fragment_cycles(&mut self, fragments: &[&'a FragmentDefinitionNode]) {
+        let mut visited = HashSet::new();
+        let mut path = Vec::new();
+        let mut path_indices = HashMap::new();
+        for fragment in fragments {
+            self.detect_cycles(
+                &fragment.name.value,
+                &mut visited,
+                &mut path,
+                &mut path_indices,
+            );
+        }
+    }
+
+    fn detect_cycles(
+        &mut self,
+        name: &'a str,
+        visited: &mut HashSet<&'a str>,
+        path: &mut Vec<&'a FragmentSpreadNode>,
+        path_indices: &mut HashMap<&'a str, usize>,
+    ) {
+        if !visited.insert(name) {
+            return;
+        }
+        let spreads: Vec<&'a FragmentSpreadNode> = match self.fragment_scopes.get(name) {
+            Some(scope) => scope.fragments.clone(),
+            None => return,
+        };
+        path_indices.insert(name, path.len());
+        for spread in spreads {
+            let spread_name = spread.name.value.as_str();
+            path.push(spread);
+            match path_indices.get(spread_name) {
+                Some(index) => {
+                    let via: Vec<&str> = path[*index..path.len() - 1]
+                        .iter()
+                        .map(|spread| spread.name.value.as_str())
+                        .collect();
+                    let message = if via.is_empty() {
+                        format!("Cannot spread fragment {} within itself", spread_name)
+                    } else {
+                        format!(
+                            "Cannot spread fragment {} within itself via {}",
+                            spread_name,
+                            via.join(", ")
+                        )
+                    };
+                    self.errors.push(error(&message, spread.span.start));
+                }
+                None => self.detect_cycles(spread_name, visited, path, path_indices),
+            }
+            path.pop();
+        }
+        path_indices.remove(name);
+    }
+
+    fn validate_selection_set(
+        &mut self,
+        parent: &'a TypeDefinitionNode,
+        selections: &'a [Selection],
+        scope: &mut Scope<'a>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => self.validate_field(parent, field, scope),
+                Selection::Fragment(FragmentSpread::Node(spread)) => {
+                    self.validate_directives(
+                        &spread.directives,
+                        ExecutableDirectiveLocation::FragmentSpread,
+                        scope,
+                    );
+                    scope.fragments.push(spread);
+                    let fragment = match self.fragments.get(spread.name.value.as_str()) {
+                        Some(fragment) => *fragment,
+                        None => {
+                            self.errors.push(error(
+                                &format!("Unknown fragment {}", spread.name.value),
+                                spread.name.span.start,
+                            ));
+                            continue;
+                        }
+                    };
+                    if let Some(type_condition) =
+                        self.schema.get_type(&fragment.node_type.name.value)
+                    {
+                        self.validate_spread_possible(
+                            parent,
+                            type_condition,
+                            Some(&spread.name.value),
+                            spread.span.start,
+                        );
+                    }
+                }
+                Selection::Fragment(FragmentSpread::Inline(inline)) => {
+                    self.validate_directives(
+                        &inline.directives,
+                        ExecutableDirectiveLocation::InlineFragment,
+                        scope,
+                    );
+                    let type_condition = match &inline.node_type {
+                        Some(named_type) => match self.type_condition(named_type) {
+                            Some(type_condition) => {
+                                self.validate_spread_possible(
+                                    parent,
+                                    type_condition,
+                                    None,
+                                    inline.span.start,
+                                );
+                                type_condition
+                            }
+                            None => continue,
+                        },
+                        None => parent,
+                    };
+                    self.validate_selection_set(type_condition, &inline.selections, scope);
+                }
+            }
+        }
+        self.validate_field_merging(parent, selections);
+    }
+
+    /// A fragment can only be spread where some object could be of both the parent type and
+    /// the fragment's type.
+    fn validate_spread_possible(
+        &mut self,
+        parent: &TypeDefinitionNode,
+        type_condition: &TypeDefinitionNode,
+        fragment_name: Option<&str>,
+        location: Location,
+    ) {
+        let parent_types = self.schema.possible_types(&parent.name().value);
+        let condition_types = self.schema.possible_types(&type_condition.name().value);
+        let overlaps = parent_types.iter().any(|parent_type| {
+            condition_types
+                .iter()
+                .any(|condition_type| condition_type.name.value == parent_type.name.value)
+        });
+        if !overlaps {
+            let fragment = match fragment_name {
+                Some(name) => format!("Fragment {}", name),
+                None => String::from("Fragment"),
+            };
+            self.errors.push(error(
+                &format!(
+                    "{} cannot be spread here as objects of type {} can never be of type {}",
+                    fragment,
+                    parent.name().value,
+                    type_condition.name().value
+                ),
+                location,
+            ));
+        }
+    }
+
+    fn validate_field(
+        &mut self,
+        parent: &'a TypeDefinitionNode,
+        field: &'a FieldNode,
+        scope: &mut Scope<'a>,
+    ) {
+        self.validate_directives(&field.directives, ExecutableDirectiveLocation::Field, scope);
+        let name = &field.name.value;
+        let definition = match self.schema.field(&parent.name().value, name) {
+            Some(definition) => definition,
+            None => {
+                self.errors.push(error(
+                    &format!(
+                        "Cannot query field {} on type {}",
+                        name,
+                        parent.name().value
+                    ),
+                    field.name.span.start,
+                ));
+                return;
+            }
+        };
+        self.validate_arguments(
+            &format!("field {}.{}", parent.name().value, name),
+            &definition.arguments,
+            &field.arguments,
+            field.span.start,
+            scope,
+        );
+
+        let field_type = match self.schema.named_type(&definition.field_type) {
+            Some(field_type) => field_type,
+            None => return,
+        };
+        match &field.selections {
+            Some(selections) if is_composite(field_type) => {
+                self.validate_selection_set(field_type, selections, scope)
+            }
+            Some(_) => self.errors.push(error(
+                &format!(
+                    "Field {} must not have a selection since type {} has no subfields",
+                    name,
+                    print_type(&definition.field_type)
+                ),
+                field.span.start,
+            )),
+            None if is_composite(field_type) => self.errors.push(error(
+                &format!(
+                    "Field {} of type {} must have a selection of subfields",
+                    name,
+                    print_type(&definition.field_type)
+                ),
+                field.span.start,
+            )),
+            None => (),
+        }
+    }
+
+    /// Checks the arguments given to a field or directive against the arguments it defines.
+    fn validate_arguments(
+        &mut self,
+        owner: &str,
+        definitions: &'a Option<ArgumentDefinitions>,
+        arguments: &'a Option<Arguments>,
+        location: Location,
+        scope: &mut Scope<'a>,
+    ) {
+        let definitions: &'a [InputValueDefinitionNode] = definitions.as_deref().unwrap_or(&[]);
+        let arguments: &'a [Argument] = arguments.as_deref().unwrap_or(&[]);
+        let mut names: Vec<&str> = Vec::new();
+        for argument in arguments {
+            let name = &argument.name.value;
+            if names.contains(&name.as_str()) {
+                self.errors.push(error(
+                    &format!("There can be only one argument named {}", name),
+                    argument.name.span.start,
+                ));
+            }
+            names.push(name);
+            let definition = match definitions
+                .iter()
+                .find(|definition| &definition.name.value == name)
+            {
+                Some(definition) => definition,
+                None => {
+                    self.errors.push(error(
+                        &format!("Unknown argument {} on {}", name, owner),
+                        argument.name.span.start,
+                    ));
+                    continue;
+                }
+            };
+            if let Some(message) =
+                value_error(self.schema, &argument.value, &definition.input_type, false)
+            {
+                self.errors.push(error(
+                    &format!(
+                        "Invalid value for argument {} on {}: {}",
+                        name, owner, message
+                    ),
+                    value_location(&argument.value).unwrap_or(argument.span.start),
+                ));
+            }
+            self.collect_variable_usages(
+                &argument.value,
+                &definition.input_type,
+                definition.default_value.is_some(),
+                scope,
+            );
+        }
+        for definition in definitions
+            .iter()
+            .filter(|definition| is_required(definition))
+        {
+            if !names.contains(&definition.name.value.as_str()) {
+                self.errors.push(error(
+                    &format!(
+                        "Argument {} of type {} is required on {}, but it was not provided",
+                        definition.name.value,
+                        print_type(&definition.input_type),
+                        owner
+                    ),
+                    location,
+                ));
+            }
+        }
+    }
+
+    fn collect_variable_usages(
+        &self,
+        value: &'a ValueNode,
+        expected: &'a TypeNode,
+        has_default: bool,
+        scope: &mut Scope<'a>,
+    ) {
+        match value {
+            ValueNode::Variable(variable) => scope.variables.push(VariableUsage {
+                variable,
+                expected,
+                has_default,
+            }),
+            ValueNode::List(list) => {
+                let nullable: &'a TypeNode = match expected {
+                    TypeNode::NonNull(inner) => inner,
+                    _ => expected,
+                };
+                let item_type: &'a TypeNode = match nullable {
+                    TypeNode::List(list_type) => &list_type.list_type,
+                    _ => nullable,
+                };
+                for item in &list.values {
+                    self.collect_variable_usages(item, item_type, false, scope);
+                }
+            }
+            ValueNode::Object(object) => {
+                let input_fields = self
+                    .schema
+                    .input_fields_of(&expected.named_type().name.value)
+                    .unwrap_or(&[]);
+                for field in &object.fields {
+                    if let Some(definition) = input_fields
+                        .iter()
+                        .find(|definition| definition.name.value == field.name.value)
+                    {
+                        self.collect_variable_usages(
+                            &field.value,
+                            &definition.input_type,
+                            definition.default_value.is_some(),
+                            scope,
+                        );
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+
+    fn validate_directives(
+        &mut self,
+        directives: &'a Option<Directives>,
+        location: ExecutableDirectiveLocation,
+        scope: &mut Scope<'a>,
+    ) {
+        let location = DirectiveLocation::Executable(location);
+        let mut names: Vec<&str> = Vec::new();
+        for directive in directives.iter().flatten() {
+            let name = &directive.name.value;
+            let definition = match self.schema.get_directive(name) {
+                Some(definition) => definition,
+                None => {
+                    self.errors.push(error(
+                        &format!("Unknown directive @{}", name),
+                        directive.span.start,
+                    ));
+                    continue;
+                }
+            };
+            if !definition.locations.contains(&location) {
+                self.errors.push(error(
+                    &format!(
+                        "Directive @{} may not be used on {}",
+                        name,
+                        directive_location_name(&location)
+                    ),
+                    directive.span.start,
+                ));
+            }
+            if names.contains(&name.as_str()) && !definition.repeatable {
+                self.errors.push(error(
+                    &format!(
+                        "The directive @{} can only be used once at this location",
+                        name
+                    ),
+                    directive.span.start,
+                ));
+            }
+            names.push(name);
+            self.validate_arguments(
+                &format!("directive @{}", name),
+                &definition.arguments,
+                &directive.arguments,
+                directive.span.start,
+                scope,
+            );
+        }
+    }
+
+    /// A subscription must select exactly one root field, including the fields it selects
+    /// through fragments.
+    fn validate_single_root_field(
+        &mut self,
+        root: &'a TypeDefinitionNode,
+        operation: &Operation<'a>,
+    ) {
+        let mut fields = Vec::new();
+        self.collect_fields(root, operation.selections, &mut fields, &mut Vec::new());
+        let mut keys: Vec<&str> = Vec::new();
+        for selected in &fields {
+            let key = response_key(selected.field);
+            if !keys.contains(&key) {
+                keys.push(key);
+            }
+        }
+        if keys.len() > 1 {
+            self.errors.push(error(
+                &format!(
+                    "Invalid Subscription {}: Must select only one top level field, found {}",
+                    operation.display_name(),
+                    keys.join(", ")
+                ),
+                operation.span.start,
+            ));
+        }
+    }
+
+    /// Collects the fields a selection set selects, including those selected through fragments.
+    fn collect_fields(
+        &self,
+        parent: &'a TypeDefinitionNode,
+        selections: &'a [Selection],
+        fields: &mut Vec<SelectedField<'a>>,
+        visited_fragments: &mut Vec<&'a str>,
+    ) {
+        for selection in selections {
+            match selection {
+                Selection::Field(field) => fields.push(SelectedField {
+                    parent,
+                    field,
+                    definition: self.schema.field(&parent.name().value, &field.name.value),
+                }),
+                Selection::Fragment(FragmentSpread::Inline(inline)) => {
+                    let parent = inline
+                        .node_type
+                        .as_ref()
+                        .and_then(|named_type| self.schema.get_type(&named_type.name.value))
+                        .unwrap_or(parent);
+                    self.collect_fields(parent, &inline.selections, fields, visited_fragments);
+                }
+                Selection::Fragment(FragmentSpread::Node(spread)) => {
+                    let name = spread.name.value.as_str();
+                    if visited_fragments.contains(&name) {
+                        continue;
+                    }
+                    visited_fragments.push(name);
+                    if let Some(fragment) = self.fragments.get(name) {
+                        if let Some(parent) = self.schema.get_type(&fragment.node_type.name.value) {
+                            self.collect_fields(
+                                parent,
+                                &fragment.selections,
+                                fields,
+                                visited_fragments,
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Fields with the same response key must be the same field with the same arguments,
+    /// unless they are selected on different object types, and must return values of the same
+    /// shape.
+    fn validate_field_merging(
+        &mut self,
+        parent: &'a TypeDefinitionNode,
+        selections: &'a [Selection],
+    ) {
+        let mut fields = Vec::new();
+        self.collect_fields(parent, selections, &mut fields, &mut Vec::new());
+        for (index, first) in fields.iter().enumerate() {
+            for second in &fields[index + 1..] {
+                self.find_conflict(first, second, false);
+            }
+        }
+    }
+
+    fn find_conflict(
+        &mut self,
+        first: &SelectedField<'a>,
+        second: &SelectedField<'a>,
+        parents_exclusive: bool,
+    ) {
+        let key = response_key(first.field);
+        if key != response_key(second.field) || std::ptr::eq(first.field, second.field) {
+            return;
+        }
+        let exclusive = parents_exclusive
+            || (first.parent.name().value != second.parent.name().value
+                && matches!(first.parent, TypeDefinitionNode::Object(_))
+                && matches!(second.parent, TypeDefinitionNode::Object(_)));
+
+        let reason = if !exclusive && first.field.name.value != second.field.name.value {
+            Some(format!(
+                "{} and {} are different fields",
+                first.field.name.value, second.field.name.value
+            ))
+        } else if !exclusive && !same_arguments(&first.field.arguments, &second.field.arguments) {
+            Some(String::from("they have differing arguments"))
+        } else {
+            match (first.definition, second.definition) {
+                (Some(first_definition), Some(second_definition))
+                    if !self.same_response_shape(
+                        &first_definition.field_type,
+                        &second_definition.field_type,
+                    ) =>
+                {
+                    Some(format!(
+                        "they return conflicting types {} and {}",
+                        print_type(&first_definition.field_type),
+                        print_type(&second_definition.field_type)
+                    ))
+                }
+                _ => None,
+            }
+        };
+        if let Some(reason) = reason {
+            let positions = (
+                first.field.span.start.absolute_position,
+                second.field.span.start.absolute_position,
+            );
+            if self.reported_conflicts.insert(positions) {
+                self.errors.push(error(
+                    &format!(
+                        "Fields {} conflict because {}. Use different aliases on the fields to \
+                         fetch both if this was intentional",
+                        key, reason
+                    ),
+                    second.field.span.start,
+                ));
+            }
+            return;
+        }
+
+        // The fields merge, so the fields selected below them have to merge as well
+        let (first_selections, second_selections) =
+            match (&first.field.selections, &second.field.selections) {
+                (Some(first_selections), Some(second_selections)) => {
+                    (first_selections, second_selections)
+                }
+                _ => return,
+            };
+        let (first_type, second_type) = match (first.definition, second.definition) {
+            (Some(first_definition), Some(second_definition)) => (
+                self.schema.named_type(&first_definition.field_type),
+                self.schema.named_type(&second_definition.field_type),
+            ),
+            _ => return,
+        };
+        if let (Some(first_type), Some(second_type)) = (first_type, second_type) {
+            let mut first_fields = Vec::new();
+            self.collect_fields(
+                first_type,
+                first_selections,
+                &mut first_fields,
+                &mut Vec::new(),
+            );
+            let mut second_fields = Vec::new();
+            self.collect_fields(
+                second_type,
+                second_selections,
+                &mut second_fields,
+                &mut Vec::new(),
+            );
+            for first in &first_fields {
+                for second in &second_fields {
+                    self.find_conflict(first, second, exclusive);
+                }
+            }
+        }
+    }
+
+    /// Two types have the same shape when they have the same list and non-null wrappers and,
+    /// for scalars and enums, are the same type.
+    fn same_response_shape(&self, first: &TypeNode, second: &TypeNode) -> bool {
+        match (first, second) {
+            (TypeNode::NonNull(first), TypeNode::NonNull(second)) => {
+                self.same_response_shape(first, second)
+            }
+            (TypeNode::NonNull(_), _) | (_, TypeNode::NonNull(_)) => false,
+            (TypeNode::List(first), TypeNode::List(second)) => {
+                self.same_response_shape(&first.list_type, &second.list_type)
+            }
+            (TypeNode::List(_), _) | (_, TypeNode::List(_)) => false,
+            (TypeNode::Named(first), TypeNode::Named(second)) => {
+                let is_leaf = |named: &NamedTypeNode| {
+                    matches!(
+                        self.schema.get_type(&named.name.value),
+                        Some(TypeDefinitionNode::Scalar(_)) | Some(TypeDefinitionNode::Enum(_))
+                    )
+                };
+                if is_leaf(first) || is_leaf(second) {
+                    first.name.value == second.name.value
+                } else {
+                    true
+                }
+            }
+        }
+    }
+}
+
+/// A nullable variable can only be used where a non-null value is expected when a default
+/// value makes up for it.
+fn is_variable_allowed(definition: &VariableDefinitionNode, usage: &VariableUsage) -> bool {
+    match (usage.expected, &definition.variable_type) {
+        (TypeNode::NonNull(location_type), variable_type)
+            if !matches!(variable_type, TypeNode::NonNull(_)) =>
+        {
+            let has_default = usage.has_default
+                || matches!(&definition.default_value, Some(value) if *value != ValueNode::Null);
+            has_default && is_input_subtype(variable_type, location_type)
+        }
+        (location_type, variable_type) => is_input_subtype(variable_type, location_type),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const SCHEMA: &str = r#"type Query {
+  hero(episode: Episode): Character
+  human(id: ID!): Human
+  search(text: String!, first: Int = 10): [SearchResult!]!
+  reviews(filter: ReviewFilter): [Review]
+}
+type Mutation {
+  addReview(stars: Int!, comment: String): Review
+}
+enum Episode { NEWHOPE EMPIRE JEDI }
+interface Character {
+  id: ID!
+  name: String
+  friends: [Character]
+}
+type Human implements Character {
+  id: ID!
+  name: String
+  friends: [Character]
+  height(metric: Boolean = true): Float
+}
+type Droid implements Character {
+  id: ID!
+  name: String
+  friends: [Character]
+  primaryFunction: String
+}
+type Review {
+  stars: Int!
+  comment: String
+}
+input ReviewFilter {
+  minStars: Int!
+  episodes: [Episode!]
+}
+union SearchResult = Human | Droid"#;
+
+    fn errors(query: &str) -> Vec<String> {
+        let schema = Schema::new(vec![parse(SCHEMA).unwrap()]).unwrap();
+        validate(&schema, &parse(query).unwrap())
+            .into_iter()
+            .map(|error| error.message)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_a_valid_document() {
+        assert_eq!(
+            errors(
+                r#"query Hero($episode: Episode = JEDI, $withFriends: Boolean!) {
+  hero(episode: $episode) {
+    __typename
+    ...CharacterFields
+    ... on Human { height(metric: false) }
+    friends @include(if: $withFriends) { name }
+  }
+  search(text: "luke") {
+    ... on Droid { primaryFunction }
+  }
+}
+mutation Review($stars: Int!) {
+  addReview(stars: $stars) { stars }
+}
+fragment CharacterFields on Character { id name }"#
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn reports_unknown_fields_and_arguments() {
+        assert_eq!(
+            errors(
+                r#"{
+  hero(season: 4) { age }
+  human { name }
+  search(text: "a") { name }
+}"#
+            ),
+            vec![
+                "Unknown argument season on field Query.hero",
+                "Cannot query field age on type Character",
+                "Argument id of type ID! is required on field Query.human, but it was not \
+                 provided",
+                "Cannot query field name on type SearchResult",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_argument_values() {
+        assert_eq!(
+            errors(
+                r#"{
+  hero(episode: PHANTOM) { id }
+  reviews(filter: {episodes: [JEDI]}) { stars }
+  human(id: 4, id: 5) { id }
+}"#
+            ),
+            vec![
+                "Invalid value for argument episode on field Query.hero: Expected type Episode, \
+                 found PHANTOM",
+                "Invalid value for argument filter on field Query.reviews: Field \
+                 ReviewFilter.minStars of required type Int! was not provided",
+                "There can be only one argument named id",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_leaf_selection_errors() {
+        assert_eq!(
+            errors("{ hero { id { value } } human(id: 1) }"),
+            vec![
+                "Field id must not have a selection since type ID! has no subfields",
+                "Field human of type Human must have a selection of subfields",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_fragment_errors() {
+        assert_eq!(
+            errors(
+                r#"{
+  hero {
+    ...Unknown
+    ...ReviewFields
+    ... on Episode { id }
+  }
+}
+fragment ReviewFields on Review { stars }
+fragment Unused on Human { id }
+fragment A on Human { ...B }
+fragment B on Human { ...A }"#
+            ),
+            vec![
+                "Cannot spread fragment A within itself via B",
+                "Unknown fragment Unknown",
+                "Fragment ReviewFields cannot be spread here as objects of type Character can \
+                 never be of type Review",
+                "Fragment cannot condition on non composite type Episode",
+                "Fragment Unused is never used",
+                "Fragment A is never used",
+                "Fragment B is never used",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_variable_errors() {
+        assert_eq!(
+            errors(
+                r#"query Search($text: String, $unused: Int, $limit: Int) {
+  search(text: $text, first: $limit) { __typename }
+  human(id: $id) { id }
+}
+query Filtered($filter: ReviewFilter, $stars: Int) {
+  reviews(filter: {minStars: $stars}) { stars }
+  hero(episode: $filter) { id }
+}"#
+            ),
+            vec![
+                "Variable $text of type String used in position expecting type String!",
+                "Variable $id is not defined by operation Search",
+                "Variable $unused is never used in operation Search",
+                "Variable $stars of type Int used in position expecting type Int!",
+                "Variable $filter of type ReviewFilter used in position expecting type Episode",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_operation_errors() {
+        assert_eq!(
+            errors(
+                r#"query Hero { hero { id } }
+query Hero { hero { name } }
+{ hero { id } }
+subscription { hero { id } }
+type Extra { id: ID }"#
+            ),
+            vec![
+                "An executable document cannot contain type system definitions",
+                "There can be only one operation named Hero",
+                "This anonymous operation must be the only defined operation",
+                "This anonymous operation must be the only defined operation",
+                "The schema does not support subscription operations",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_directive_errors() {
+        assert_eq!(
+            errors(
+                r#"{
+  hero @skip(if: true) @skip(if: false) { id @deprecated }
+  human(id: 1) @include { id @unknown }
+}"#
+            ),
+            vec![
+                "The directive @skip can only be used once at this location",
+                "Directive @deprecated may not be used on FIELD",
+                "Argument if of type Boolean! is required on directive @include, but it was not \
+                 provided",
+                "Unknown directive @unknown",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_conflicting_fields() {
+        assert_eq!(
+            errors(
+                r#"{
+  hero {
+    name: id
+    name
+    ... on Human { height(metric: true) }
+    ... on Human { height(metric: false) }
+    ... on Droid { friends: primaryFunction }
+    ... on Human { friends { id } }
+  }
+  a: hero { id }
+  a: hero { id: name }
+}"#
+            ),
+            vec![
+                "Fields name conflict because id and name are different fields. Use different \
+                 aliases on the fields to fetch both if this was intentional",
+                "Fields height conflict because they have differing arguments. Use different \
+                 aliases on the fields to fetch both if this was intentional",
+                "Fields friends conflict because they return conflicting types String and \
+                 [Character]. Use different aliases on the fields to fetch both if this was \
+                 intentional",
+                "Fields id conflict because id and name are different fields. Use different \
+                 aliases on the fields to fetch both if this was intentional",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_subscriptions_with_more_than_one_root_field() {
+        let schema = Schema::new(vec![parse(
+            "type Query { a: Int }\ntype Subscription { messageAdded: Int userJoined: Int }",
+        )
+        .unwrap()])
+        .unwrap();
+        let validate_query = |query: &str| validate(&schema, &parse(query).unwrap());
+
+        assert!(validate_query(
+            "subscription { ...Added }\nfragment Added on Subscription { messageAdded }"
+        )
+        .is_empty());
+        let errors = validate_query(
+            r#"subscription TooMany {
+  ... on Subscription { messageAdded }
+  ...Joined
+}
+fragment Joined on Subscription { userJoined messageAdded }"#,
+        );
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors[0].message,
+            "Invalid Subscription TooMany: Must select only one top level field, found \
+             messageAdded, userJoined"
+        );
+        assert_eq!(errors[0].location, Some(Location::new(0, 1, 1)));
+    }
+}
@@ -0,0 +1,631 @@
+//! The type system validation rules of the
+//! [GraphQL Spec](https://spec.graphql.org/October2021/#sec-Type-System), checked against a
+//! built [`Schema`].
+//!
+//! [`Schema`]: ../../schema/struct.Schema.html
+
+use crate::error::ValidationError;
+use crate::introspection::is_introspection_type;
+use crate::nodes::*;
+use crate::printer::print_type;
+use crate::schema::Schema;
+use crate::validation::values::{value_error, value_location};
+
+/// Checks every type and directive in the schema and returns an error for each broken rule.
+pub fn validate(schema: &Schema) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    for type_definition in schema.types() {
+        if !is_introspection_type(&type_definition.name().value) {
+            validate_name(type_definition.name(), &mut errors);
+        }
+        match type_definition {
+            TypeDefinitionNode::Scalar(_) => (),
+            TypeDefinitionNode::Object(object) => {
+                validate_fields(schema, &object.name, &object.fields, &mut errors);
+                validate_implementations(
+                    schema,
+                    &object.name,
+                    &object.interfaces,
+                    &object.fields,
+                    &mut errors,
+                );
+            }
+            TypeDefinitionNode::Interface(interface) => {
+                validate_fields(schema, &interface.name, &interface.fields, &mut errors);
+                validate_implementations(
+                    schema,
+                    &interface.name,
+                    &interface.interfaces,
+                    &interface.fields,
+                    &mut errors,
+                );
+            }
+            TypeDefinitionNode::Union(union) => validate_union(schema, union, &mut errors),
+            TypeDefinitionNode::Enum(enum_type) => validate_enum(enum_type, &mut errors),
+            TypeDefinitionNode::Input(input) => validate_input_values(
+                schema,
+                "Input field",
+                &input.name.value,
+                &input.fields,
+                &mut errors,
+            ),
+        }
+    }
+    for directive in schema.directives() {
+        validate_name(&directive.name, &mut errors);
+        if let Some(arguments) = &directive.arguments {
+            validate_input_values(
+                schema,
+                "Argument",
+                &format!("@{}", directive.name.value),
+                arguments,
+                &mut errors,
+            );
+        }
+    }
+    errors
+}
+
+/// Names starting with `__` are reserved for introspection.
+fn validate_name(name: &NameNode, errors: &mut Vec<ValidationError>) {
+    if name.value.starts_with("__") {
+        errors.push(
+            ValidationError::new(
+                format!(
+                    "Name {} must not begin with \"__\", which is reserved by introspection",
+                    name.value
+                )
+                .as_str(),
+            )
+            .with_location(name.span.start),
+        );
+    }
+}
+
+/// Reports every name after the first use of that name.
+fn validate_unique_names<'a>(
+    kind: &str,
+    owner: &str,
+    names: impl Iterator<Item = &'a NameNode>,
+    errors: &mut Vec<ValidationError>,
+) {
+    let mut seen: Vec<&str> = Vec::new();
+    for name in names {
+        if seen.contains(&name.value.as_str()) {
+            errors.push(
+                ValidationError::new(
+                    format!(
+                        "{} {} is defined more than once in {}",
+                        kind, name.value, owner
+                    )
+                    .as_str(),
+                )
+                .with_location(name.span.start),
+            );
+        } else {
+            seen.push(&name.value);
+        }
+    }
+}
+
+fn is_input_type(type_definition: &TypeDefinitionNode) -> bool {
+    matches!(
+        type_definition,
+        TypeDefinitionNode::Scalar(_) | TypeDefinitionNode::Enum(_) | TypeDefinitionNode::Input(_)
+    )
+}
+
+fn validate_fields(
+    schema: &Schema,
+    owner: &NameNode,
+    fields: &[FieldDefinitionNode],
+    errors: &mut Vec<ValidationError>,
+) {
+    validate_unique_names(
+        "Field",
+        &owner.value,
+        fields.iter().map(|field| &field.name),
+        errors,
+    );
+    for field in fields {
+        validate_name(&field.name, errors);
+        if let Some(TypeDefinitionNode::Input(input)) = schema.named_type(&field.field_type) {
+            errors.push(
+                ValidationError::new(
+                    format!(
+                        "Field {}.{} must be an output type, but {} is an input type",
+                        owner.value, field.name.value, input.name.value
+                    )
+                    .as_str(),
+                )
+                .with_location(field.field_type.named_type().span.start),
+            );
+        }
+        if let Some(arguments) = &field.arguments {
+            validate_input_values(
+                schema,
+                "Argument",
+                &format!("{}.{}", owner.value, field.name.value),
+                arguments,
+                errors,
+            );
+        }
+    }
+}
+
+/// Checks arguments and input fields: their names, that their types are input types and that
+/// their default values match those types.
+fn validate_input_values(
+    schema: &Schema,
+    kind: &str,
+    owner: &str,
+    values: &[InputValueDefinitionNode],
+    errors: &mut Vec<ValidationError>,
+) {
+    validate_unique_names(kind, owner, values.iter().map(|value| &value.name), errors);
+    for value in values {
+        validate_name(&value.name, errors);
+        match schema.named_type(&value.input_type) {
+            Some(type_definition) if !is_input_type(type_definition) => errors.push(
+                ValidationError::new(
+                    format!(
+                        "{} {} of {} must be an input type, but {} is an output type",
+                        kind,
+                        value.name.value,
+                        owner,
+                        type_definition.name().value
+                    )
+                    .as_str(),
+                )
+                .with_location(value.input_type.named_type().span.start),
+            ),
+            _ => {
+                if let Some(default_value) = &value.default_value {
+                    if let Some(message) =
+                        value_error(schema, default_value, &value.input_type, true)
+                    {
+                        errors.push(
+                            ValidationError::new(
+                                format!(
+                                    "Invalid default value for {} {} of {}: {}",
+                                    kind.to_lowercase(),
+                                    value.name.value,
+                                    owner,
+                                    message
+                                )
+                                .as_str(),
+                            )
+                            .with_location(
+                                value_location(default_value).unwrap_or(value.span.start),
+                            ),
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether `sub_type` can be used where `super_type` is expected: the same type, a non-null
+/// version of it, or an object or interface that implements it.
+fn is_subtype(schema: &Schema, sub_type: &TypeNode, super_type: &TypeNode) -> bool {
+    match (sub_type, super_type) {
+        (TypeNode::NonNull(sub_type), TypeNode::NonNull(super_type)) => {
+            is_subtype(schema, sub_type, super_type)
+        }
+        (TypeNode::NonNull(sub_type), super_type) => is_subtype(schema, sub_type, super_type),
+        (_, TypeNode::NonNull(_)) => false,
+        (TypeNode::List(sub_type), TypeNode::List(super_type)) => {
+            is_subtype(schema, &sub_type.list_type, &super_type.list_type)
+        }
+        (TypeNode::List(_), _) | (_, TypeNode::List(_)) => false,
+        (TypeNode::Named(sub_type), TypeNode::Named(super_type)) => {
+            let sub_name = &sub_type.name.value;
+            let super_name = &super_type.name.value;
+            sub_name == super_name
+                || schema.is_possible_type(super_name, sub_name)
+                || match schema.get_type(sub_name) {
+                    Some(TypeDefinitionNode::Interface(interface)) => interface
+                        .interfaces
+                        .iter()
+                        .flatten()
+                        .any(|implemented| &implemented.name.value == super_name),
+                    _ => false,
+                }
+        }
+    }
+}
+
+/// Checks that an object or interface implements every interface it lists, and every interface
+/// those interfaces implement.
+fn validate_implementations(
+    schema: &Schema,
+    name: &NameNode,
+    interfaces: &Option<Vec<NamedTypeNode>>,
+    fields: &[FieldDefinitionNode],
+    errors: &mut Vec<ValidationError>,
+) {
+    let interfaces: &[NamedTypeNode] = match interfaces {
+        Some(interfaces) => interfaces,
+        None => return,
+    };
+    validate_unique_names(
+        "Interface",
+        &format!("the interfaces of {}", name.value),
+        interfaces.iter().map(|interface| &interface.name),
+        errors,
+    );
+    for reference in interfaces {
+        let location = reference.span.start;
+        if reference.name.value == name.value {
+            errors.push(
+                ValidationError::new(
+                    format!("Interface {} cannot implement itself", name.value).as_str(),
+                )
+                .with_location(location),
+            );
+            continue;
+        }
+        let interface = match schema.get_type(&reference.name.value) {
+            Some(TypeDefinitionNode::Interface(interface)) => interface,
+            // Unknown types are reported when the schema is built
+            None => continue,
+            Some(_) => {
+                errors.push(
+                    ValidationError::new(
+                        format!(
+                            "Type {} can only implement interfaces, but {} is not an interface",
+                            name.value, reference.name.value
+                        )
+                        .as_str(),
+                    )
+                    .with_location(location),
+                );
+                continue;
+            }
+        };
+
+        for inherited in interface.interfaces.iter().flatten() {
+            let listed = interfaces
+                .iter()
+                .any(|listed| listed.name.value == inherited.name.value);
+            if !listed && inherited.name.value != name.value {
+                errors.push(
+                    ValidationError::new(
+                        format!(
+                            "Type {} must implement {} because it is implemented by {}",
+                            name.value, inherited.name.value, interface.name.value
+                        )
+                        .as_str(),
+                    )
+                    .with_location(location),
+                );
+            }
+        }
+
+        for interface_field in &interface.fields {
+            match fields
+                .iter()
+                .find(|field| field.name.value == interface_field.name.value)
+            {
+                Some(field) => validate_implemented_field(
+                    schema,
+                    &name.value,
+                    field,
+                    &interface.name.value,
+                    interface_field,
+                    errors,
+                ),
+                None => errors.push(
+                    ValidationError::new(
+                        format!(
+                            "Interface field {}.{} expected but {} does not provide it",
+                            interface.name.value, interface_field.name.value, name.value
+                        )
+                        .as_str(),
+                    )
+                    .with_location(location),
+                ),
+            }
+        }
+    }
+}
+
+fn validate_implemented_field(
+    schema: &Schema,
+    type_name: &str,
+    field: &FieldDefinitionNode,
+    interface_name: &str,
+    interface_field: &FieldDefinitionNode,
+    errors: &mut Vec<ValidationError>,
+) {
+    if !is_subtype(schema, &field.field_type, &interface_field.field_type) {
+        errors.push(
+            ValidationError::new(
+                format!(
+                    "Interface field {}.{} expects type {} but {}.{} is type {}",
+                    interface_name,
+                    interface_field.name.value,
+                    print_type(&interface_field.field_type),
+                    type_name,
+                    field.name.value,
+                    print_type(&field.field_type)
+                )
+                .as_str(),
+            )
+            .with_location(field.field_type.named_type().span.start),
+        );
+    }
+
+    let arguments: &[InputValueDefinitionNode] = field.arguments.as_deref().unwrap_or(&[]);
+    let interface_arguments: &[InputValueDefinitionNode] =
+        interface_field.arguments.as_deref().unwrap_or(&[]);
+    for interface_argument in interface_arguments {
+        match arguments
+            .iter()
+            .find(|argument| argument.name.value == interface_argument.name.value)
+        {
+            Some(argument) if argument.input_type != interface_argument.input_type => errors.push(
+                ValidationError::new(
+                    format!(
+                        "Interface field argument {}.{}({}:) expects type {} but {}.{}({}:) is \
+                         type {}",
+                        interface_name,
+                        interface_field.name.value,
+                        interface_argument.name.value,
+                        print_type(&interface_argument.input_type),
+                        type_name,
+                        field.name.value,
+                        argument.name.value,
+                        print_type(&argument.input_type)
+                    )
+                    .as_str(),
+                )
+                .with_location(argument.span.start),
+            ),
+            Some(_) => (),
+            None => errors.push(
+                ValidationError::new(
+                    format!(
+                        "Interface field argument {}.{}({}:) expected but {}.{} does not \
+                         provide it",
+                        interface_name,
+                        interface_field.name.value,
+                        interface_argument.name.value,
+                        type_name,
+                        field.name.value
+                    )
+                    .as_str(),
+                )
+                .with_location(field.span.start),
+            ),
+        }
+    }
+    for argument in arguments {
+        let on_interface = interface_arguments
+            .iter()
+            .any(|interface_argument| interface_argument.name.value == argument.name.value);
+        if !on_interface && crate::validation::values::is_required(argument) {
+            errors.push(
+                ValidationError::new(
+                    format!(
+                        "Field {}.{} includes required argument {} that is missing from the \
+                         interface field {}.{}",
+                        type_name,
+                        field.name.value,
+                        argument.name.value,
+                        interface_name,
+                        interface_field.name.value
+                    )
+                    .as_str(),
+                )
+                .with_location(argument.span.start),
+            );
+        }
+    }
+}
+
+fn validate_union(
+    schema: &Schema,
+    union: &UnionTypeDefinitionNode,
+    errors: &mut Vec<ValidationError>,
+) {
+    validate_unique_names(
+        "Member type",
+        &union.name.value,
+        union.types.iter().map(|member| &member.name),
+        errors,
+    );
+    for member in &union.types {
+        match schema.get_type(&member.name.value) {
+            Some(TypeDefinitionNode::Object(_)) | None => (),
+            Some(_) => errors.push(
+                ValidationError::new(
+                    format!(
+                        "Union {} can only include object types, but {} is not an object type",
+                        union.name.value, member.name.value
+                    )
+                    .as_str(),
+                )
+                .with_location(member.span.start),
+            ),
+        }
+    }
+}
+
+fn validate_enum(enum_type: &EnumTypeDefinitionNode, errors: &mut Vec<ValidationError>) {
+    validate_unique_names(
+        "Enum value",
+        &enum_type.name.value,
+        enum_type.values.iter().map(|value| &value.name),
+        errors,
+    );
+    for value in &enum_type.values {
+        validate_name(&value.name, errors);
+        if ["true", "false", "null"].contains(&value.name.value.as_str()) {
+            errors.push(
+                ValidationError::new(
+                    format!(
+                        "Enum {} cannot include the value {}",
+                        enum_type.name.value, value.name.value
+                    )
+                    .as_str(),
+                )
+                .with_location(value.name.span.start),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+    use crate::token::Location;
+
+    /// Builds the schema and returns the messages of every error found.
+    fn errors(source: &str) -> Vec<String> {
+        match Schema::new(vec![parse(source).unwrap()]) {
+            Ok(_) => Vec::new(),
+            Err(errors) => errors.into_iter().map(|error| error.message).collect(),
+        }
+    }
+
+    #[test]
+    fn accepts_a_valid_schema() {
+        assert_eq!(
+            errors(
+                r#"type Query {
+  node(id: ID!): Node
+  search(term: String = "", limit: TinyUint = 10): [SearchResult!]!
+}
+interface Node { id: ID! }
+interface Resource implements Node { id: ID! url: String }
+type Photo implements Node & Resource {
+  id: ID!
+  url: String!
+  size(unit: Unit = PIXELS): Int
+}
+type Person implements Node { id: ID! friends: [Person] }
+union SearchResult = Photo | Person
+enum Unit { PIXELS POINTS }
+input Range { from: Int = 0, to: Int }
+directive @limit(range: Range = {to: 10}) on FIELD"#
+            ),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn reports_duplicate_and_reserved_names() {
+        assert_eq!(
+            errors(
+                r#"type Query { name: String, name: Int, __secret: String
+  find(id: ID, id: ID): ID }
+enum Color { RED RED null }
+input Range { from: Int, from: Int }
+directive @audit(reason: String, reason: String) on FIELD_DEFINITION"#
+            ),
+            vec![
+                "Field name is defined more than once in Query",
+                "Name __secret must not begin with \"__\", which is reserved by introspection",
+                "Argument id is defined more than once in Query.find",
+                "Enum value RED is defined more than once in Color",
+                "Enum Color cannot include the value null",
+                "Input field from is defined more than once in Range",
+                "Argument reason is defined more than once in @audit",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unsatisfied_interfaces() {
+        assert_eq!(
+            errors(
+                r#"type Query { node: Node }
+interface Node { id: ID! }
+interface Named implements Node { id: ID! name(short: Boolean): String }
+type Person implements Named {
+  id: ID
+  name(short: Int, locale: String!): String
+}
+type Photo implements Photo & Query { id: ID! }"#
+            ),
+            vec![
+                "Type Person must implement Node because it is implemented by Named",
+                "Interface field Named.id expects type ID! but Person.id is type ID",
+                "Interface field argument Named.name(short:) expects type Boolean but \
+                 Person.name(short:) is type Int",
+                "Field Person.name includes required argument locale that is missing from the \
+                 interface field Named.name",
+                "Interface Photo cannot implement itself",
+                "Type Photo can only implement interfaces, but Query is not an interface",
+            ]
+        );
+    }
+
+    #[test]
+    fn accepts_covariant_field_types() {
+        assert_eq!(
+            errors(
+                r#"type Query { node: Node }
+interface Node { parent: Node, children: [Node] }
+type Folder implements Node { parent: Folder!, children: [Folder!]! }"#
+            ),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            errors(
+                r#"type Query { node: Node }
+interface Node { children: [Node] }
+type Folder implements Node { children: Folder }"#
+            ),
+            vec![
+                "Interface field Node.children expects type [Node] but Folder.children is type \
+                 Folder"
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_input_and_output_type_misuse() {
+        assert_eq!(
+            errors(
+                r#"type Query { filter: Filter, search(by: Query): Int }
+input Filter { owner: Query }
+union Result = Query | Filter | String"#
+            ),
+            vec![
+                "Field Query.filter must be an output type, but Filter is an input type",
+                "Argument by of Query.search must be an input type, but Query is an output type",
+                "Input field owner of Filter must be an input type, but Query is an output type",
+                "Union Result can only include object types, but Filter is not an object type",
+                "Union Result can only include object types, but String is not an object type",
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_invalid_default_values() {
+        let errors = Schema::new(vec![parse(
+            r#"type Query {
+  people(limit: TinyInt = 1000, sort: Sort = NAME): Int
+}
+enum Sort { AGE }"#,
+        )
+        .unwrap()])
+        .unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].message,
+            "Invalid default value for argument limit of Query.people: \
+             Expected type TinyInt, found 1000"
+        );
+        assert_eq!(errors[0].location, Some(Location::new(39, 2, 27)));
+        assert_eq!(
+            errors[1].message,
+            "Invalid default value for argument sort of Query.people: \
+             Expected type Sort, found NAME"
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Checks that a literal value can be used as an input type.
+
+use crate::nodes::*;
+use crate::printer::{print_type, print_value};
+use crate::schema::Schema;
+use crate::token::Location;
+
+/// Describes why `value` cannot be coerced to `type_node`, or returns None when it can.
+///
+/// Default values are constant, so `constant` rejects variables. Outside of a constant the
+/// variable's type is checked separately, so any variable is accepted here. Types missing from
+/// the schema are reported elsewhere and accept any value.
+pub fn value_error(
+    schema: &Schema,
+    value: &ValueNode,
+    type_node: &TypeNode,
+    constant: bool,
+) -> Option<String> {
+    match (type_node, value) {
+        (_, ValueNode::Variable(variable)) => {
+            if constant {
+                Some(format!(
+                    "Unexpected variable ${} in constant value",
+                    variable.name.value
+                ))
+            } else {
+                None
+            }
+        }
+        (TypeNode::NonNull(_), ValueNode::Null) => Some(format!(
+            "Expected non-null type {}, found null",
+            print_type(type_node)
+        )),
+        (TypeNode::NonNull(inner), value) => value_error(schema, value, inner, constant),
+        (_, ValueNode::Null) => None,
+        (TypeNode::List(list), ValueNode::List(values)) => values
+            .values
+            .iter()
+            .find_map(|item| value_error(schema, item, &list.list_type, constant)),
+        // A single value is coerced to a list containing that value
+        (TypeNode::List(list), value) => value_error(schema, value, &list.list_type, constant),
+        (TypeNode::Named(named), value) => named_value_error(schema, value, named, constant),
+    }
+}
+
+fn named_value_error(
+    schema: &Schema,
+    value: &ValueNode,
+    named: &NamedTypeNode,
+    constant: bool,
+) -> Option<String> {
+    let mismatch = || {
+        Some(format!(
+            "Expected type {}, found {}",
+            named.name.value,
+            print_value(value)
+        ))
+    };
+    match schema.get_type(&named.name.value) {
+        None => None,
+        Some(TypeDefinitionNode::Scalar(scalar)) => {
+            if is_scalar_value(&scalar.name.value, value) {
+                None
+            } else {
+                mismatch()
+            }
+        }
+        Some(TypeDefinitionNode::Enum(enum_type)) => match value {
+            ValueNode::Enum(enum_value)
+                if enum_type
+                    .values
+                    .iter()
+                    .any(|definition| definition.name.value == enum_value.value) =>
+            {
+                None
+            }
+            _ => mismatch(),
+        },
+        Some(TypeDefinitionNode::Input(input)) => match value {
+            ValueNode::Object(object) => input_object_error(schema, object, input, constant),
+            _ => mismatch(),
+        },
+        Some(_) => Some(format!("Type {} is not an input type", named.name.value)),
+    }
+}
+
+fn input_object_error(
+    schema: &Schema,
+    object: &ObjectValueNode,
+    input: &InputTypeDefinitionNode,
+    constant: bool,
+) -> Option<String> {
+    for field in &object.fields {
+        let definition = input
+            .fields
+            .iter()
+            .find(|definition| definition.name.value == field.name.value);
+        match definition {
+            Some(definition) => {
+                if let Some(error) =
+                    value_error(schema, &field.value, &definition.input_type, constant)
+                {
+                    return Some(error);
+                }
+            }
+            None => {
+                return Some(format!(
+                    "Field {} is not defined by input type {}",
+                    field.name.value, input.name.value
+                ))
+            }
+        }
+    }
+    input
+        .fields
+        .iter()
+        .filter(|definition| is_required(definition))
+        .find(|definition| {
+            !object
+                .fields
+                .iter()
+                .any(|field| field.name.value == definition.name.value)
+        })
+        .map(|definition| {
+            format!(
+                "Field {}.{} of required type {} was not provided",
+                input.name.value,
+                definition.name.value,
+                print_type(&definition.input_type)
+            )
+        })
+}
+
+/// An argument or input field that must be given a value.
+pub fn is_required(definition: &InputValueDefinitionNode) -> bool {
+    matches!(definition.input_type, TypeNode::NonNull(_)) && definition.default_value.is_none()
+}
+
+fn fits<T: std::str::FromStr>(value: &ValueNode) -> bool {
+    match value {
+        ValueNode::Int(int) => int.raw.parse::<T>().is_ok(),
+        _ => false,
+    }
+}
+
+fn is_string_of_at_most(value: &ValueNode, bytes: usize) -> bool {
+    match value {
+        ValueNode::Str(string) => string.value.len() <= bytes,
+        _ => false,
+    }
+}
+
+/// Whether a literal is valid for a scalar. The built-in scalars of the default document are
+/// checked against their ranges and custom scalars accept any literal.
+fn is_scalar_value(scalar: &str, value: &ValueNode) -> bool {
+    match scalar {
+        "TinyInt" => fits::<i8>(value),
+        "ShortInt" => fits::<i16>(value),
+        "Int" => fits::<i32>(value),
+        "LongInt" => fits::<i64>(value),
+        "BigInt" => fits::<i128>(value),
+        "TinyUint" => fits::<u8>(value),
+        "ShortUint" => fits::<u16>(value),
+        "Uint" => fits::<u32>(value),
+        "LongUint" => fits::<u64>(value),
+        "BigUint" => fits::<u128>(value),
+        "Float" | "Double" => matches!(value, ValueNode::Int(_) | ValueNode::Float(_)),
+        "TinyString" => is_string_of_at_most(value, 255),
+        "String" => is_string_of_at_most(value, 65_535),
+        "DateTime" | "Date" | "Time" => matches!(value, ValueNode::Str(_)),
+        "Boolean" => matches!(value, ValueNode::Bool(_)),
+        "ID" => matches!(value, ValueNode::Str(_) | ValueNode::Int(_)),
+        _ => true,
+    }
+}
+
+/// Where a value starts in the source. Null values do not keep a location.
+pub fn value_location(value: &ValueNode) -> Option<Location> {
+    match value {
+        ValueNode::Variable(variable) => Some(variable.span.start),
+        ValueNode::Int(int) => Some(int.span.start),
+        ValueNode::Float(float) => Some(float.span.start),
+        ValueNode::Str(string) => Some(string.span.start),
+        ValueNode::Bool(boolean) => Some(boolean.span.start),
+        ValueNode::Null => None,
+        ValueNode::Enum(enum_value) => Some(enum_value.span.start),
+        ValueNode::List(list) => Some(list.span.start),
+        ValueNode::Object(object) => Some(object.span.start),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    fn schema() -> Schema {
+        Schema::new(vec![parse(
+            r#"enum Color { RED GREEN }
+input Filter {
+  name: String!
+  colors: [Color!]
+  limit: TinyUint = 10
+}"#,
+        )
+        .unwrap()])
+        .unwrap()
+    }
+
+    /// Parses `value` as the default of an argument of type `type_name` and checks it.
+    fn check(type_name: &str, value: &str) -> Option<String> {
+        let document = parse(&format!(
+            "type Query {{ field(arg: {} = {}): Int }}",
+            type_name, value
+        ))
+        .unwrap();
+        match &document.definitions[0] {
+            DefinitionNode::TypeSystem(TypeSystemDefinitionNode::Type(
+                TypeDefinitionNode::Object(object),
+            )) => {
+                let argument = &object.fields[0].arguments.as_ref().unwrap()[0];
+                value_error(
+                    &schema(),
+                    argument.default_value.as_ref().unwrap(),
+                    &argument.input_type,
+                    true,
+                )
+            }
+            _ => panic!("Expected an object type definition"),
+        }
+    }
+
+    #[test]
+    fn checks_scalar_ranges() {
+        assert_eq!(check("Int", "42"), None);
+        assert_eq!(check("Float", "42"), None);
+        assert_eq!(check("ID", "\"abc\""), None);
+        assert_eq!(
+            check("TinyUint", "256"),
+            Some("Expected type TinyUint, found 256".into())
+        );
+        assert_eq!(
+            check("BigUint", "340282366920938463463374607431768211455"),
+            None
+        );
+        assert_eq!(
+            check("Int", "\"42\""),
+            Some("Expected type Int, found \"42\"".into())
+        );
+        assert_eq!(
+            check("Boolean", "1"),
+            Some("Expected type Boolean, found 1".into())
+        );
+    }
+
+    #[test]
+    fn checks_lists_and_null() {
+        assert_eq!(check("[Int]", "[1, null, 3]"), None);
+        assert_eq!(check("[Int]", "1"), None);
+        assert_eq!(check("Int", "null"), None);
+        assert_eq!(
+            check("[Int!]", "[1, null]"),
+            Some("Expected non-null type Int!, found null".into())
+        );
+        assert_eq!(
+            check("Int", "$limit"),
+            Some("Unexpected variable $limit in constant value".into())
+        );
+    }
+
+    #[test]
+    fn checks_enums_and_input_objects() {
+        assert_eq!(check("Color", "RED"), None);
+        assert_eq!(
+            check("Color", "BLUE"),
+            Some("Expected type Color, found BLUE".into())
+        );
+        assert_eq!(check("Filter", "{name: \"a\", colors: [RED]}"), None);
+        assert_eq!(
+            check("Filter", "{colors: GREEN}"),
+            Some("Field Filter.name of required type String! was not provided".into())
+        );
+        assert_eq!(
+            check("Filter", "{name: \"a\", size: 1}"),
+            Some("Field size is not defined by input type Filter".into())
+        );
+        assert_eq!(
+            check("Filter", "{name: \"a\", limit: -1}"),
+            Some("Expected type TinyUint, found -1".into())
+        );
+    }
+}
@@ -0,0 +1,1189 @@
+//! Traversal of a [`Document`] with enter and leave hooks for every node.
+//!
+//! A [`Visitor`] borrows the tree and can keep references to the nodes it sees. A
+//! [`VisitorMut`] gets mutable access and is used to rewrite the tree in place. Both are
+//! driven by the functions in [`walk`] and [`walk_mut`], which call `enter_*` before a node's
+//! children are walked and `leave_*` after. Every hook does nothing by default, so a visitor
+//! only implements the hooks for the nodes it cares about.
+//!
+//! # Example
+//!
+//! ```
+//! use syntax::nodes::FieldNode;
+//! use syntax::visitor::{walk, Visitor};
+//!
+//! #[derive(Default)]
+//! struct FieldNames<'a>(Vec<&'a str>);
+//!
+//! impl<'a> Visitor<'a> for FieldNames<'a> {
+//!     fn enter_field(&mut self, node: &'a FieldNode) {
+//!         self.0.push(&node.name.value);
+//!     }
+//! }
+//!
+//! let document = syntax::parse("{ user { name friends { name } } }").unwrap();
+//! let mut names = FieldNames::default();
+//! walk::document(&mut names, &document);
+//! assert_eq!(names.0, vec!["user", "name", "friends", "name"]);
+//! ```
+//!
+//! [`Document`]: ../document/struct.Document.html
+
+use crate::document::Document;
+use crate::nodes::enum_type_extension::EnumTypeExtensionNode;
+use crate::nodes::input_type_extension::InputTypeExtensionNode;
+use crate::nodes::interface_type_extension::InterfaceTypeExtensionNode;
+use crate::nodes::object_type_extension::ObjectTypeExtensionNode;
+use crate::nodes::scalar_type_extension::ScalarTypeExtensionNode;
+use crate::nodes::schema_extension::SchemaExtensionNode;
+use crate::nodes::union_type_extension::UnionTypeExtensionNode;
+use crate::nodes::*;
+use std::sync::Arc;
+
+/// Declares a pair of empty `enter_*` and `leave_*` hooks per node type. `$reference` is the
+/// reference the hooks receive, `&'a` for a Visitor and `&mut` for a VisitorMut.
+macro_rules! hooks {
+    ([$($reference:tt)*]) => {};
+    ([$($reference:tt)*] $enter:ident $leave:ident $node:ty; $($rest:tt)*) => {
+        #[doc = concat!("Called on a `", stringify!($node), "` before its children are walked.")]
+        fn $enter(&mut self, _node: $($reference)* $node) {}
+        #[doc = concat!("Called on a `", stringify!($node), "` after its children are walked.")]
+        fn $leave(&mut self, _node: $($reference)* $node) {}
+        hooks!([$($reference)*] $($rest)*);
+    };
+}
+
+/// Declares a visitor trait and the module of walk functions that drive it. The walk functions
+/// are written once and borrow with `& $($mutability)?`, so they produce shared references for
+/// a Visitor and mutable references for a VisitorMut.
+macro_rules! visitor {
+    (
+        $(#[$attribute:meta])*
+        trait $visitor:ident[$($lifetime:lifetime)?];
+        mod $walk:ident;
+        mutability = [$($mutability:tt)?];
+        arc = $arc:ident;
+    ) => {
+        $(#[$attribute])*
+        pub trait $visitor<$($lifetime)?> {
+            hooks! {
+                [& $($lifetime)? $($mutability)?]
+                enter_document leave_document Document;
+                enter_definition leave_definition DefinitionNode;
+                enter_executable_definition leave_executable_definition ExecutableDefinitionNode;
+                enter_operation leave_operation OperationTypeNode;
+                enter_query_definition leave_query_definition QueryDefinitionNode;
+                enter_mutation_definition leave_mutation_definition MutationDefinitionNode;
+                enter_subscription_definition leave_subscription_definition
+                    SubscriptionDefinitionNode;
+                enter_fragment_definition leave_fragment_definition FragmentDefinitionNode;
+                enter_variable_definition leave_variable_definition VariableDefinitionNode;
+                enter_selection leave_selection Selection;
+                enter_field leave_field FieldNode;
+                enter_fragment_spread leave_fragment_spread FragmentSpread;
+                enter_fragment_spread_node leave_fragment_spread_node FragmentSpreadNode;
+                enter_inline_fragment_spread leave_inline_fragment_spread InlineFragmentSpreadNode;
+                enter_argument leave_argument Argument;
+                enter_directive leave_directive DirectiveNode;
+                enter_value leave_value ValueNode;
+                enter_variable leave_variable VariableNode;
+                enter_int_value leave_int_value IntValueNode;
+                enter_float_value leave_float_value FloatValueNode;
+                enter_string_value leave_string_value StringValueNode;
+                enter_boolean_value leave_boolean_value BooleanValueNode;
+                enter_enum_value leave_enum_value EnumValueNode;
+                enter_list_value leave_list_value ListValueNode;
+                enter_object_value leave_object_value ObjectValueNode;
+                enter_object_field leave_object_field ObjectFieldNode;
+                enter_type leave_type TypeNode;
+                enter_named_type leave_named_type NamedTypeNode;
+                enter_list_type leave_list_type ListTypeNode;
+                enter_name leave_name NameNode;
+                enter_type_system_definition leave_type_system_definition TypeSystemDefinitionNode;
+                enter_schema_definition leave_schema_definition SchemaDefinitionNode;
+                enter_operation_type_definition leave_operation_type_definition
+                    OperationTypeDefinitionNode;
+                enter_directive_definition leave_directive_definition DirectiveDefinitionNode;
+                enter_type_definition leave_type_definition TypeDefinitionNode;
+                enter_scalar_type_definition leave_scalar_type_definition ScalarTypeDefinitionNode;
+                enter_object_type_definition leave_object_type_definition ObjectTypeDefinitionNode;
+                enter_interface_type_definition leave_interface_type_definition
+                    InterfaceTypeDefinitionNode;
+                enter_union_type_definition leave_union_type_definition UnionTypeDefinitionNode;
+                enter_enum_type_definition leave_enum_type_definition EnumTypeDefinitionNode;
+                enter_input_type_definition leave_input_type_definition InputTypeDefinitionNode;
+                enter_field_definition leave_field_definition FieldDefinitionNode;
+                enter_input_value_definition leave_input_value_definition InputValueDefinitionNode;
+                enter_enum_value_definition leave_enum_value_definition EnumValueDefinitionNode;
+                enter_type_system_extension leave_type_system_extension TypeSystemExtensionNode;
+                enter_schema_extension leave_schema_extension SchemaExtensionNode;
+                enter_scalar_type_extension leave_scalar_type_extension ScalarTypeExtensionNode;
+                enter_object_type_extension leave_object_type_extension ObjectTypeExtensionNode;
+                enter_interface_type_extension leave_interface_type_extension
+                    InterfaceTypeExtensionNode;
+                enter_union_type_extension leave_union_type_extension UnionTypeExtensionNode;
+                enter_enum_type_extension leave_enum_type_extension EnumTypeExtensionNode;
+                enter_input_type_extension leave_input_type_extension InputTypeExtensionNode;
+            }
+        }
+
+        /// Walks a node and all of its children, calling the visitor's hooks on the way.
+        pub mod $walk {
+            use super::*;
+
+            /// Walks a `Document` and its children.
+            pub fn document<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? Document,
+            ) {
+                visitor.enter_document(node);
+                for definition in & $($mutability)? node.definitions {
+                    self::definition(visitor, definition);
+                }
+                visitor.leave_document(node);
+            }
+
+            /// Walks a `DefinitionNode` and its children.
+            pub fn definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? DefinitionNode,
+            ) {
+                visitor.enter_definition(node);
+                match & $($mutability)? *node {
+                    DefinitionNode::Executable(definition) => {
+                        executable_definition(visitor, definition)
+                    }
+                    DefinitionNode::TypeSystem(definition) => {
+                        type_system_definition(visitor, definition)
+                    }
+                    DefinitionNode::Extension(extension) => {
+                        type_system_extension(visitor, extension)
+                    }
+                }
+                visitor.leave_definition(node);
+            }
+
+            /// Walks a `ExecutableDefinitionNode` and its children.
+            pub fn executable_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ExecutableDefinitionNode,
+            ) {
+                visitor.enter_executable_definition(node);
+                match & $($mutability)? *node {
+                    ExecutableDefinitionNode::Operation(operation) => {
+                        self::operation(visitor, operation)
+                    }
+                    ExecutableDefinitionNode::Fragment(fragment) => {
+                        fragment_definition(visitor, fragment)
+                    }
+                }
+                visitor.leave_executable_definition(node);
+            }
+
+            /// Walks a `OperationTypeNode` and its children.
+            pub fn operation<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? OperationTypeNode,
+            ) {
+                visitor.enter_operation(node);
+                match & $($mutability)? *node {
+                    OperationTypeNode::Query(query) => query_definition(visitor, query),
+                    OperationTypeNode::Mutation(mutation) => mutation_definition(visitor, mutation),
+                    OperationTypeNode::Subscription(subscription) => {
+                        subscription_definition(visitor, subscription)
+                    }
+                }
+                visitor.leave_operation(node);
+            }
+
+            /// Walks a `QueryDefinitionNode` and its children.
+            pub fn query_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? QueryDefinitionNode,
+            ) {
+                visitor.enter_query_definition(node);
+                if let Some(name) = & $($mutability)? node.name {
+                    self::name(visitor, name);
+                }
+                if let Some(variables) = & $($mutability)? node.variables {
+                    for variable in variables {
+                        variable_definition(visitor, variable);
+                    }
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for selection in & $($mutability)? node.selections {
+                    self::selection(visitor, selection);
+                }
+                visitor.leave_query_definition(node);
+            }
+
+            /// Walks a `MutationDefinitionNode` and its children.
+            pub fn mutation_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? MutationDefinitionNode,
+            ) {
+                visitor.enter_mutation_definition(node);
+                if let Some(name) = & $($mutability)? node.name {
+                    self::name(visitor, name);
+                }
+                if let Some(variables) = & $($mutability)? node.variables {
+                    for variable in variables {
+                        variable_definition(visitor, variable);
+                    }
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for selection in & $($mutability)? node.selections {
+                    self::selection(visitor, selection);
+                }
+                visitor.leave_mutation_definition(node);
+            }
+
+            /// Walks a `SubscriptionDefinitionNode` and its children.
+            pub fn subscription_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? SubscriptionDefinitionNode,
+            ) {
+                visitor.enter_subscription_definition(node);
+                if let Some(name) = & $($mutability)? node.name {
+                    self::name(visitor, name);
+                }
+                if let Some(variables) = & $($mutability)? node.variables {
+                    for variable in variables {
+                        variable_definition(visitor, variable);
+                    }
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for selection in & $($mutability)? node.selections {
+                    self::selection(visitor, selection);
+                }
+                visitor.leave_subscription_definition(node);
+            }
+
+            /// Walks a `FragmentDefinitionNode` and its children.
+            pub fn fragment_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? FragmentDefinitionNode,
+            ) {
+                visitor.enter_fragment_definition(node);
+                name(visitor, & $($mutability)? node.name);
+                named_type(visitor, & $($mutability)? node.node_type);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for selection in & $($mutability)? node.selections {
+                    self::selection(visitor, selection);
+                }
+                visitor.leave_fragment_definition(node);
+            }
+
+            /// Walks a `VariableDefinitionNode` and its children.
+            pub fn variable_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? VariableDefinitionNode,
+            ) {
+                visitor.enter_variable_definition(node);
+                variable(visitor, & $($mutability)? node.variable);
+                type_node(visitor, & $($mutability)? node.variable_type);
+                if let Some(default_value) = & $($mutability)? node.default_value {
+                    value(visitor, default_value);
+                }
+                visitor.leave_variable_definition(node);
+            }
+
+            /// Walks a `Selection` and its children.
+            pub fn selection<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? Selection,
+            ) {
+                visitor.enter_selection(node);
+                match & $($mutability)? *node {
+                    Selection::Field(field) => self::field(visitor, field),
+                    Selection::Fragment(fragment) => fragment_spread(visitor, fragment),
+                }
+                visitor.leave_selection(node);
+            }
+
+            /// Walks a `FieldNode` and its children.
+            pub fn field<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? FieldNode,
+            ) {
+                visitor.enter_field(node);
+                if let Some(alias) = & $($mutability)? node.alias {
+                    name(visitor, alias);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(arguments) = & $($mutability)? node.arguments {
+                    for argument in arguments {
+                        self::argument(visitor, argument);
+                    }
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                if let Some(selections) = & $($mutability)? node.selections {
+                    for selection in selections {
+                        self::selection(visitor, selection);
+                    }
+                }
+                visitor.leave_field(node);
+            }
+
+            /// Walks a `FragmentSpread` and its children.
+            pub fn fragment_spread<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? FragmentSpread,
+            ) {
+                visitor.enter_fragment_spread(node);
+                match & $($mutability)? *node {
+                    FragmentSpread::Node(spread) => fragment_spread_node(visitor, spread),
+                    FragmentSpread::Inline(spread) => inline_fragment_spread(visitor, spread),
+                }
+                visitor.leave_fragment_spread(node);
+            }
+
+            /// Walks a `FragmentSpreadNode` and its children.
+            pub fn fragment_spread_node<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? FragmentSpreadNode,
+            ) {
+                visitor.enter_fragment_spread_node(node);
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                visitor.leave_fragment_spread_node(node);
+            }
+
+            /// Walks a `InlineFragmentSpreadNode` and its children.
+            pub fn inline_fragment_spread<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? InlineFragmentSpreadNode,
+            ) {
+                visitor.enter_inline_fragment_spread(node);
+                if let Some(node_type) = & $($mutability)? node.node_type {
+                    named_type(visitor, node_type);
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for selection in & $($mutability)? node.selections {
+                    self::selection(visitor, selection);
+                }
+                visitor.leave_inline_fragment_spread(node);
+            }
+
+            /// Walks a `Argument` and its children.
+            pub fn argument<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? Argument,
+            ) {
+                visitor.enter_argument(node);
+                name(visitor, & $($mutability)? node.name);
+                value(visitor, & $($mutability)? node.value);
+                visitor.leave_argument(node);
+            }
+
+            /// Walks a `DirectiveNode` and its children.
+            pub fn directive<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? DirectiveNode,
+            ) {
+                visitor.enter_directive(node);
+                name(visitor, & $($mutability)? node.name);
+                if let Some(arguments) = & $($mutability)? node.arguments {
+                    for argument in arguments {
+                        self::argument(visitor, argument);
+                    }
+                }
+                visitor.leave_directive(node);
+            }
+
+            /// Walks a `ValueNode` and its children.
+            pub fn value<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ValueNode,
+            ) {
+                visitor.enter_value(node);
+                match & $($mutability)? *node {
+                    ValueNode::Variable(value) => variable(visitor, value),
+                    ValueNode::Int(value) => int_value(visitor, value),
+                    ValueNode::Float(value) => float_value(visitor, value),
+                    ValueNode::Str(value) => string_value(visitor, value),
+                    ValueNode::Bool(value) => boolean_value(visitor, value),
+                    ValueNode::Null => {}
+                    ValueNode::Enum(value) => enum_value(visitor, value),
+                    ValueNode::List(value) => list_value(visitor, value),
+                    ValueNode::Object(value) => object_value(visitor, value),
+                }
+                visitor.leave_value(node);
+            }
+
+            /// Walks a `VariableNode` and its children.
+            pub fn variable<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? VariableNode,
+            ) {
+                visitor.enter_variable(node);
+                name(visitor, & $($mutability)? node.name);
+                visitor.leave_variable(node);
+            }
+
+            /// Walks a `IntValueNode` and its children.
+            pub fn int_value<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? IntValueNode,
+            ) {
+                visitor.enter_int_value(node);
+                visitor.leave_int_value(node);
+            }
+
+            /// Walks a `FloatValueNode` and its children.
+            pub fn float_value<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? FloatValueNode,
+            ) {
+                visitor.enter_float_value(node);
+                visitor.leave_float_value(node);
+            }
+
+            /// Walks a `StringValueNode` and its children.
+            pub fn string_value<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? StringValueNode,
+            ) {
+                visitor.enter_string_value(node);
+                visitor.leave_string_value(node);
+            }
+
+            /// Walks a `BooleanValueNode` and its children.
+            pub fn boolean_value<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? BooleanValueNode,
+            ) {
+                visitor.enter_boolean_value(node);
+                visitor.leave_boolean_value(node);
+            }
+
+            /// Walks a `EnumValueNode` and its children.
+            pub fn enum_value<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? EnumValueNode,
+            ) {
+                visitor.enter_enum_value(node);
+                visitor.leave_enum_value(node);
+            }
+
+            /// Walks a `ListValueNode` and its children.
+            pub fn list_value<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ListValueNode,
+            ) {
+                visitor.enter_list_value(node);
+                for item in & $($mutability)? node.values {
+                    value(visitor, item);
+                }
+                visitor.leave_list_value(node);
+            }
+
+            /// Walks a `ObjectValueNode` and its children.
+            pub fn object_value<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ObjectValueNode,
+            ) {
+                visitor.enter_object_value(node);
+                for field in & $($mutability)? node.fields {
+                    object_field(visitor, field);
+                }
+                visitor.leave_object_value(node);
+            }
+
+            /// Walks a `ObjectFieldNode` and its children.
+            pub fn object_field<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ObjectFieldNode,
+            ) {
+                visitor.enter_object_field(node);
+                name(visitor, & $($mutability)? node.name);
+                value(visitor, & $($mutability)? node.value);
+                visitor.leave_object_field(node);
+            }
+
+            /// Walks a `TypeNode` and its children.
+            pub fn type_node<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? TypeNode,
+            ) {
+                visitor.enter_type(node);
+                match & $($mutability)? *node {
+                    TypeNode::Named(named) => named_type(visitor, named),
+                    TypeNode::List(list) => list_type(visitor, list),
+                    TypeNode::NonNull(inner) => {
+                        if let Some(inner) = $arc(inner) {
+                            type_node(visitor, inner);
+                        }
+                    }
+                }
+                visitor.leave_type(node);
+            }
+
+            /// Walks a `NamedTypeNode` and its children.
+            pub fn named_type<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? NamedTypeNode,
+            ) {
+                visitor.enter_named_type(node);
+                name(visitor, & $($mutability)? node.name);
+                visitor.leave_named_type(node);
+            }
+
+            /// Walks a `ListTypeNode` and its children.
+            pub fn list_type<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ListTypeNode,
+            ) {
+                visitor.enter_list_type(node);
+                if let Some(inner) = $arc(& $($mutability)? node.list_type) {
+                    type_node(visitor, inner);
+                }
+                visitor.leave_list_type(node);
+            }
+
+            /// Walks a `NameNode` and its children.
+            pub fn name<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? NameNode,
+            ) {
+                visitor.enter_name(node);
+                visitor.leave_name(node);
+            }
+
+            /// Walks a `TypeSystemDefinitionNode` and its children.
+            pub fn type_system_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? TypeSystemDefinitionNode,
+            ) {
+                visitor.enter_type_system_definition(node);
+                match & $($mutability)? *node {
+                    TypeSystemDefinitionNode::Schema(schema) => schema_definition(visitor, schema),
+                    TypeSystemDefinitionNode::Type(definition) => {
+                        type_definition(visitor, definition)
+                    }
+                    TypeSystemDefinitionNode::Directive(directive) => {
+                        directive_definition(visitor, directive)
+                    }
+                }
+                visitor.leave_type_system_definition(node);
+            }
+
+            /// Walks a `SchemaDefinitionNode` and its children.
+            pub fn schema_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? SchemaDefinitionNode,
+            ) {
+                visitor.enter_schema_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for operation in & $($mutability)? node.operations {
+                    operation_type_definition(visitor, operation);
+                }
+                visitor.leave_schema_definition(node);
+            }
+
+            /// Walks a `OperationTypeDefinitionNode` and its children.
+            pub fn operation_type_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? OperationTypeDefinitionNode,
+            ) {
+                visitor.enter_operation_type_definition(node);
+                named_type(visitor, & $($mutability)? node.node_type);
+                visitor.leave_operation_type_definition(node);
+            }
+
+            /// Walks a `DirectiveDefinitionNode` and its children.
+            pub fn directive_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? DirectiveDefinitionNode,
+            ) {
+                visitor.enter_directive_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(arguments) = & $($mutability)? node.arguments {
+                    for argument in arguments {
+                        input_value_definition(visitor, argument);
+                    }
+                }
+                visitor.leave_directive_definition(node);
+            }
+
+            /// Walks a `TypeDefinitionNode` and its children.
+            pub fn type_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? TypeDefinitionNode,
+            ) {
+                visitor.enter_type_definition(node);
+                match & $($mutability)? *node {
+                    TypeDefinitionNode::Scalar(scalar) => scalar_type_definition(visitor, scalar),
+                    TypeDefinitionNode::Object(object) => object_type_definition(visitor, object),
+                    TypeDefinitionNode::Interface(interface) => {
+                        interface_type_definition(visitor, interface)
+                    }
+                    TypeDefinitionNode::Union(union) => union_type_definition(visitor, union),
+                    TypeDefinitionNode::Enum(enum_type) => enum_type_definition(visitor, enum_type),
+                    TypeDefinitionNode::Input(input) => input_type_definition(visitor, input),
+                }
+                visitor.leave_type_definition(node);
+            }
+
+            /// Walks a `ScalarTypeDefinitionNode` and its children.
+            pub fn scalar_type_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ScalarTypeDefinitionNode,
+            ) {
+                visitor.enter_scalar_type_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                visitor.leave_scalar_type_definition(node);
+            }
+
+            /// Walks a `ObjectTypeDefinitionNode` and its children.
+            pub fn object_type_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ObjectTypeDefinitionNode,
+            ) {
+                visitor.enter_object_type_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(interfaces) = & $($mutability)? node.interfaces {
+                    for interface in interfaces {
+                        named_type(visitor, interface);
+                    }
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for field in & $($mutability)? node.fields {
+                    field_definition(visitor, field);
+                }
+                visitor.leave_object_type_definition(node);
+            }
+
+            /// Walks a `InterfaceTypeDefinitionNode` and its children.
+            pub fn interface_type_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? InterfaceTypeDefinitionNode,
+            ) {
+                visitor.enter_interface_type_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(interfaces) = & $($mutability)? node.interfaces {
+                    for interface in interfaces {
+                        named_type(visitor, interface);
+                    }
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for field in & $($mutability)? node.fields {
+                    field_definition(visitor, field);
+                }
+                visitor.leave_interface_type_definition(node);
+            }
+
+            /// Walks a `UnionTypeDefinitionNode` and its children.
+            pub fn union_type_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? UnionTypeDefinitionNode,
+            ) {
+                visitor.enter_union_type_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for member in & $($mutability)? node.types {
+                    named_type(visitor, member);
+                }
+                visitor.leave_union_type_definition(node);
+            }
+
+            /// Walks a `EnumTypeDefinitionNode` and its children.
+            pub fn enum_type_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? EnumTypeDefinitionNode,
+            ) {
+                visitor.enter_enum_type_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for value in & $($mutability)? node.values {
+                    enum_value_definition(visitor, value);
+                }
+                visitor.leave_enum_type_definition(node);
+            }
+
+            /// Walks a `InputTypeDefinitionNode` and its children.
+            pub fn input_type_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? InputTypeDefinitionNode,
+            ) {
+                visitor.enter_input_type_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                for field in & $($mutability)? node.fields {
+                    input_value_definition(visitor, field);
+                }
+                visitor.leave_input_type_definition(node);
+            }
+
+            /// Walks a `FieldDefinitionNode` and its children.
+            pub fn field_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? FieldDefinitionNode,
+            ) {
+                visitor.enter_field_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(arguments) = & $($mutability)? node.arguments {
+                    for argument in arguments {
+                        input_value_definition(visitor, argument);
+                    }
+                }
+                type_node(visitor, & $($mutability)? node.field_type);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                visitor.leave_field_definition(node);
+            }
+
+            /// Walks a `InputValueDefinitionNode` and its children.
+            pub fn input_value_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? InputValueDefinitionNode,
+            ) {
+                visitor.enter_input_value_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                type_node(visitor, & $($mutability)? node.input_type);
+                if let Some(default_value) = & $($mutability)? node.default_value {
+                    value(visitor, default_value);
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                visitor.leave_input_value_definition(node);
+            }
+
+            /// Walks a `EnumValueDefinitionNode` and its children.
+            pub fn enum_value_definition<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? EnumValueDefinitionNode,
+            ) {
+                visitor.enter_enum_value_definition(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                visitor.leave_enum_value_definition(node);
+            }
+
+            /// Walks a `TypeSystemExtensionNode` and its children.
+            pub fn type_system_extension<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? TypeSystemExtensionNode,
+            ) {
+                visitor.enter_type_system_extension(node);
+                match & $($mutability)? *node {
+                    TypeSystemExtensionNode::Schema(schema) => schema_extension(visitor, schema),
+                    TypeSystemExtensionNode::Scalar(scalar) => {
+                        scalar_type_extension(visitor, scalar)
+                    }
+                    TypeSystemExtensionNode::Object(object) => {
+                        object_type_extension(visitor, object)
+                    }
+                    TypeSystemExtensionNode::Interface(interface) => {
+                        interface_type_extension(visitor, interface)
+                    }
+                    TypeSystemExtensionNode::Union(union) => union_type_extension(visitor, union),
+                    TypeSystemExtensionNode::Enum(enum_type) => {
+                        enum_type_extension(visitor, enum_type)
+                    }
+                    TypeSystemExtensionNode::Input(input) => input_type_extension(visitor, input),
+                }
+                visitor.leave_type_system_extension(node);
+            }
+
+            /// Walks a `SchemaExtensionNode` and its children.
+            pub fn schema_extension<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? SchemaExtensionNode,
+            ) {
+                visitor.enter_schema_extension(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                if let Some(operations) = & $($mutability)? node.operations {
+                    for operation in operations {
+                        operation_type_definition(visitor, operation);
+                    }
+                }
+                visitor.leave_schema_extension(node);
+            }
+
+            /// Walks a `ScalarTypeExtensionNode` and its children.
+            pub fn scalar_type_extension<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ScalarTypeExtensionNode,
+            ) {
+                visitor.enter_scalar_type_extension(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                visitor.leave_scalar_type_extension(node);
+            }
+
+            /// Walks a `ObjectTypeExtensionNode` and its children.
+            pub fn object_type_extension<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? ObjectTypeExtensionNode,
+            ) {
+                visitor.enter_object_type_extension(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(interfaces) = & $($mutability)? node.interfaces {
+                    for interface in interfaces {
+                        named_type(visitor, interface);
+                    }
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                if let Some(fields) = & $($mutability)? node.fields {
+                    for field in fields {
+                        field_definition(visitor, field);
+                    }
+                }
+                visitor.leave_object_type_extension(node);
+            }
+
+            /// Walks a `InterfaceTypeExtensionNode` and its children.
+            pub fn interface_type_extension<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? InterfaceTypeExtensionNode,
+            ) {
+                visitor.enter_interface_type_extension(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(interfaces) = & $($mutability)? node.interfaces {
+                    for interface in interfaces {
+                        named_type(visitor, interface);
+                    }
+                }
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                if let Some(fields) = & $($mutability)? node.fields {
+                    for field in fields {
+                        field_definition(visitor, field);
+                    }
+                }
+                visitor.leave_interface_type_extension(node);
+            }
+
+            /// Walks a `UnionTypeExtensionNode` and its children.
+            pub fn union_type_extension<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? UnionTypeExtensionNode,
+            ) {
+                visitor.enter_union_type_extension(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                if let Some(types) = & $($mutability)? node.types {
+                    for member in types {
+                        named_type(visitor, member);
+                    }
+                }
+                visitor.leave_union_type_extension(node);
+            }
+
+            /// Walks a `EnumTypeExtensionNode` and its children.
+            pub fn enum_type_extension<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? EnumTypeExtensionNode,
+            ) {
+                visitor.enter_enum_type_extension(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                if let Some(values) = & $($mutability)? node.values {
+                    for value in values {
+                        enum_value_definition(visitor, value);
+                    }
+                }
+                visitor.leave_enum_type_extension(node);
+            }
+
+            /// Walks a `InputTypeExtensionNode` and its children.
+            pub fn input_type_extension<$($lifetime,)? V: $visitor<$($lifetime)?> + ?Sized>(
+                visitor: &mut V,
+                node: & $($lifetime)? $($mutability)? InputTypeExtensionNode,
+            ) {
+                visitor.enter_input_type_extension(node);
+                if let Some(description) = & $($mutability)? node.description {
+                    string_value(visitor, description);
+                }
+                name(visitor, & $($mutability)? node.name);
+                if let Some(directives) = & $($mutability)? node.directives {
+                    for directive in directives {
+                        self::directive(visitor, directive);
+                    }
+                }
+                if let Some(fields) = & $($mutability)? node.fields {
+                    for field in fields {
+                        input_value_definition(visitor, field);
+                    }
+                }
+                visitor.leave_input_type_extension(node);
+            }
+        }
+    };
+}
+
+visitor! {
+    /// Reads a document. The lifetime lets a visitor hold on to the nodes it visits.
+    trait Visitor['a];
+    mod walk;
+    mutability = [];
+    arc = arc_ref;
+}
+
+visitor! {
+    /// Rewrites a document in place. Changes made in an `enter_*` hook are seen by the walk of
+    /// the node's children.
+    ///
+    /// Types wrapped in an `Arc` that is shared with another tree are not walked, as they cannot
+    /// be borrowed mutably.
+    trait VisitorMut[];
+    mod walk_mut;
+    mutability = [mut];
+    arc = arc_mut;
+}
+
+fn arc_ref<T>(arc: &Arc<T>) -> Option<&T> {
+    Some(arc)
+}
+
+fn arc_mut<T>(arc: &mut Arc<T>) -> Option<&mut T> {
+    Arc::get_mut(arc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    #[derive(Default)]
+    struct FieldCollector<'a> {
+        fields: Vec<&'a str>,
+        depth: usize,
+        max_depth: usize,
+    }
+
+    impl<'a> Visitor<'a> for FieldCollector<'a> {
+        fn enter_field(&mut self, node: &'a FieldNode) {
+            self.fields.push(&node.name.value);
+            self.depth += 1;
+            self.max_depth = self.max_depth.max(self.depth);
+        }
+
+        fn leave_field(&mut self, _node: &'a FieldNode) {
+            self.depth -= 1;
+        }
+    }
+
+    #[test]
+    fn visits_fields_in_order() {
+        let document = parse(
+            r#"query Feed($first: Int = 10) {
+  feed(first: $first) {
+    author { name }
+    ... on Photo { url }
+  }
+  ...Viewer
+}
+fragment Viewer on Query { viewer { id } }"#,
+        )
+        .unwrap();
+        let mut collector = FieldCollector::default();
+        walk::document(&mut collector, &document);
+        assert_eq!(
+            collector.fields,
+            vec!["feed", "author", "name", "url", "viewer", "id"]
+        );
+        assert_eq!(collector.max_depth, 3);
+        assert_eq!(collector.depth, 0);
+    }
+
+    #[derive(Default)]
+    struct NamedTypeCollector {
+        types: Vec<String>,
+    }
+
+    impl<'a> Visitor<'a> for NamedTypeCollector {
+        fn enter_named_type(&mut self, node: &'a NamedTypeNode) {
+            self.types.push(node.name.value.clone());
+        }
+    }
+
+    #[test]
+    fn visits_type_system_references() {
+        let document = parse(
+            r#"type Person implements Node {
+  id: ID!
+  friends(first: Int): [Person!]
+}
+union Search = Person | Photo
+extend type Photo { owner: Person }"#,
+        )
+        .unwrap();
+        let mut collector = NamedTypeCollector::default();
+        walk::document(&mut collector, &document);
+        assert_eq!(
+            collector.types,
+            vec!["Node", "ID", "Int", "Person", "Person", "Photo", "Person"]
+        );
+    }
+
+    struct Renamer;
+
+    impl VisitorMut for Renamer {
+        fn enter_named_type(&mut self, node: &mut NamedTypeNode) {
+            if node.name.value == "Person" {
+                node.name.value = String::from("User");
+            }
+        }
+
+        fn leave_field(&mut self, node: &mut FieldNode) {
+            node.alias = None;
+        }
+    }
+
+    #[test]
+    fn rewrites_nodes_in_place() {
+        let mut document = parse("type Query { people: [Person!]! }").unwrap();
+        walk_mut::document(&mut Renamer, &mut document);
+        assert_eq!(
+            document.to_string(),
+            "type Query {\n  people: [User!]!\n}\n"
+        );
+
+        let mut document = parse("{ me: viewer { name } }").unwrap();
+        walk_mut::document(&mut Renamer, &mut document);
+        assert_eq!(document.to_string(), "{\n  viewer {\n    name\n  }\n}\n");
+    }
+}